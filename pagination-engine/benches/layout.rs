@@ -0,0 +1,83 @@
+//! Timing of the layout hot path on a 10,000-element script
+//!
+//! Run with `cargo bench --bench layout`. Every element's text is distinct,
+//! so the line cache inside `paginate` can't stand in for wrapping.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use verso_pagination_engine::layout::LineCalculator;
+use verso_pagination_engine::{paginate, Element, ElementType, PageConfig};
+
+const SCENES: usize = 2500;
+const RUNS: u32 = 20;
+
+fn script() -> Vec<Element> {
+    (0..SCENES)
+        .flat_map(|i| {
+            vec![
+                Element::new(format!("h{}", i), ElementType::SceneHeading, format!("INT. ROOM {} - DAY", i)),
+                Element::new(
+                    format!("a{}", i),
+                    ElementType::Action,
+                    format!(
+                        "Sarah crosses room {} and picks up the phone. She listens for a long moment before \
+                         she says anything at all to caller number {}, then sets it down and waits.",
+                        i,
+                        i * 7
+                    ),
+                ),
+                Element::new(format!("c{}", i), ElementType::Character, "SARAH"),
+                Element::new(
+                    format!("d{}", i),
+                    ElementType::Dialogue,
+                    format!(
+                        "I know what you did in room {}, and I am not going to let it slide this time. \
+                         Not again. Never again, do you hear me? Not after {} calls.",
+                        i,
+                        i * 3
+                    ),
+                )
+                .with_character_name("SARAH"),
+            ]
+        })
+        .collect()
+}
+
+/// Median and fastest milliseconds per run of `f`, after a few warm-up runs
+///
+/// Each run is timed on its own so a noisy neighbour skews one sample, not
+/// the result.
+fn time(mut f: impl FnMut()) -> (f64, f64) {
+    for _ in 0..3 {
+        f();
+    }
+    let mut samples: Vec<f64> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed().as_secs_f64() * 1000.0
+        })
+        .collect();
+    samples.sort_by(f64::total_cmp);
+    (samples[samples.len() / 2], samples[0])
+}
+
+fn main() {
+    let elements = script();
+    let config = PageConfig::feature_film();
+    let calc = LineCalculator::new(&config);
+
+    let paginate_ms = time(|| {
+        black_box(paginate(&elements, &config));
+    });
+    let calculate_ms = time(|| {
+        for element in &elements {
+            black_box(calc.calculate(element));
+        }
+    });
+
+    println!("{} elements, {} runs: median (fastest)", elements.len(), RUNS);
+    println!("paginate:             {:>8.2} ms ({:.2} ms)", paginate_ms.0, paginate_ms.1);
+    println!("LineCalculator only:  {:>8.2} ms ({:.2} ms)", calculate_ms.0, calculate_ms.1);
+}
@@ -0,0 +1,170 @@
+//! `verso` - paginate screenplay drafts from the command line
+//!
+//! Reads Fountain (`.fountain`, `.txt`, ...) or element-JSON (`.json`) files and
+//! prints a page-count report, the full JSON result, or rendered text pages.
+
+mod render;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use verso_pagination_engine::{fountain, paginate_with_options, Element, PageConfig, RenderOptions};
+
+const USAGE: &str = "\
+Usage: verso [OPTIONS] <FILE>...
+
+Options:
+  --preset <NAME>     Format preset: feature-film (default), a4-international,
+                      av-script, multi-cam-sitcom, single-cam-tv,
+                      bbc-screenplay, stage-play-us, audio-drama
+  --config <PATH>     PageConfig JSON file (overrides --preset)
+  --format <FORMAT>   report (default), json, or text
+  -h, --help          Print this help
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Report,
+    Json,
+    Text,
+}
+
+#[derive(Debug)]
+struct Args {
+    preset: String,
+    config: Option<PathBuf>,
+    format: OutputFormat,
+    files: Vec<PathBuf>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut parsed = Args {
+        preset: "feature-film".to_string(),
+        config: None,
+        format: OutputFormat::Report,
+        files: Vec::new(),
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or_else(|| format!("{} requires a value", flag));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--preset" => parsed.preset = value("--preset")?,
+            "--config" => parsed.config = Some(PathBuf::from(value("--config")?)),
+            "--format" => {
+                parsed.format = match value("--format")?.as_str() {
+                    "report" => OutputFormat::Report,
+                    "json" => OutputFormat::Json,
+                    "text" => OutputFormat::Text,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            file => parsed.files.push(PathBuf::from(file)),
+        }
+    }
+
+    if parsed.files.is_empty() {
+        return Err("no input files".to_string());
+    }
+
+    Ok(Some(parsed))
+}
+
+fn load_config(args: &Args) -> Result<PageConfig, String> {
+    if let Some(path) = &args.config {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        return serde_json::from_str(&json).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+
+    PageConfig::preset(&args.preset).ok_or_else(|| format!("unknown preset '{}'", args.preset))
+}
+
+fn load_elements(path: &Path) -> Result<Vec<Element>, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        serde_json::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))
+    } else {
+        Ok(fountain::parse(&source))
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let config = load_config(args)?;
+    let options = RenderOptions {
+        include_text: args.format == OutputFormat::Text,
+        ..Default::default()
+    };
+
+    for path in &args.files {
+        let elements = load_elements(path)?;
+        let result = paginate_with_options(&elements, &config, &options);
+
+        match args.format {
+            OutputFormat::Report => println!(
+                "{}: {} {}, {} {}",
+                path.display(),
+                result.page_count(),
+                if result.page_count() == 1 { "page" } else { "pages" },
+                result.scene_index.len(),
+                if result.scene_index.len() == 1 { "scene" } else { "scenes" },
+            ),
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&result).map_err(|e| e.to_string())?
+            ),
+            OutputFormat::Text => print!("{}", render::render_text(&result, &elements, &config)),
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => match run(&args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("verso: {}", e);
+                ExitCode::FAILURE
+            }
+        },
+        Ok(None) => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("verso: {}\n\n{}", e, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(list.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args(&["--format", "json", "a.fountain", "b.json"]).unwrap().unwrap();
+        assert_eq!(parsed.format, OutputFormat::Json);
+        assert_eq!(parsed.files.len(), 2);
+        assert_eq!(parsed.preset, "feature-film");
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(args(&[]).is_err());
+        assert!(args(&["--format", "pdf", "a.fountain"]).is_err());
+        assert!(args(&["--bogus", "a.fountain"]).is_err());
+        assert!(args(&["--help"]).unwrap().is_none());
+    }
+}
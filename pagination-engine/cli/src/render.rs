@@ -0,0 +1,245 @@
+use verso_pagination_engine::{
+    DualDialoguePosition, Element, ElementType, Page, PageConfig, PageIdentifier, PageNumberPosition, PaginationResult,
+};
+
+/// Render paginated pages as fixed-width text, one form feed between pages
+///
+/// Requires a result produced with `RenderOptions::include_text`.
+pub fn render_text(result: &PaginationResult, elements: &[Element], config: &PageConfig) -> String {
+    let pages: Vec<String> = result
+        .pages
+        .iter()
+        .map(|page| render_page(page, elements, config))
+        .collect();
+
+    pages.join("\u{c}\n")
+}
+
+fn render_page(page: &Page, elements: &[Element], config: &PageConfig) -> String {
+    let width = (config.printable_width_pt() / config.char_width_pt) as usize;
+    let mut rows = vec![String::new(); config.lines_per_page as usize];
+    let mut revised = vec![false; rows.len()];
+
+    for page_element in &page.elements {
+        let Some(element) = elements.iter().find(|e| e.id == page_element.element_id) else {
+            continue;
+        };
+        let Some(text) = &page_element.text else {
+            continue;
+        };
+
+        let style = config.resolved_style(element);
+        let mut indent = (style.margin_left * 72.0 / config.char_width_pt).round() as usize;
+
+        // Dual dialogue columns are narrower pages side by side
+        if let Some(column) = page_element.column {
+            let column_chars = config.dual_dialogue.column_width * 72.0 / config.char_width_pt;
+            let scale = column_chars / width as f64;
+            let column_left = if column == DualDialoguePosition::Right { column_chars } else { 0.0 };
+            indent = (column_left + indent as f64 * scale).round() as usize;
+        }
+        let markers = config.continuation_style.for_type(element.element_type);
+        let marker_indent = markers.indent.map_or_else(
+            || (config.style_for(ElementType::Character).margin_left * 72.0 / config.char_width_pt).round() as usize,
+            usize::from,
+        );
+
+        // A CONT'D prefix occupies the line above the continued content; one
+        // printed in the margin has no row in plain text and is left out
+        let has_prefix = page_element.continuation_prefix.is_some() && markers.consumes_line;
+        let text = &text[usize::from(page_element.continuation_prefix.is_some() && !has_prefix)..];
+        let first_row = page_element.start_line as usize - 1 - usize::from(has_prefix);
+
+        for (offset, line) in text.iter().enumerate() {
+            let is_marker = (has_prefix && offset == 0)
+                || (offset >= usize::from(has_prefix) + page_element.line_count as usize);
+            let line_indent = if is_marker {
+                marker_indent
+            } else if matches!(element.element_type, ElementType::Centered | ElementType::ActBreak) {
+                width.saturating_sub(line.chars().count()) / 2
+            } else {
+                indent
+            };
+
+            let content_line = offset.checked_sub(usize::from(has_prefix)).filter(|_| !is_marker);
+            let marked = page_element.revision_marks.as_ref().zip(content_line);
+            if marked.is_some_and(|(marks, line)| marks.get(line).copied().unwrap_or(false)) {
+                if let Some(flag) = revised.get_mut(first_row + offset) {
+                    *flag = true;
+                }
+            }
+
+            // AV cells and dual dialogue columns share rows, so append at the indent instead of replacing
+            if let Some(row) = rows.get_mut(first_row + offset) {
+                let width = row.chars().count();
+                let pad = if width == 0 { line_indent } else { line_indent.saturating_sub(width).max(1) };
+                row.push_str(&" ".repeat(pad));
+                row.push_str(line);
+            }
+        }
+    }
+
+    // Revised lines carry an asterisk in the right margin
+    for (row, _) in rows.iter_mut().zip(&revised).filter(|(_, &flag)| flag) {
+        let pad = (width + 2).saturating_sub(row.chars().count()).max(1);
+        row.push_str(&" ".repeat(pad));
+        row.push('*');
+    }
+
+    // The running header takes its reserved rows, or prints in the top margin without any
+    let running_lines = usize::from(config.header_footer.header_lines);
+    let margin_header = page.running_header.clone().filter(|_| running_lines == 0);
+    if let (Some(running), Some(row)) = (page.running_header.as_ref().filter(|_| running_lines > 0), rows.first_mut()) {
+        *row = running.clone();
+    }
+    let above = if page.running_header.is_some() { running_lines } else { 0 };
+    if let (Some(header), Some(row)) = (&page.header, rows.get_mut(above)) {
+        *row = header.clone();
+    }
+
+    if let (PageIdentifier::Omitted(_), Some(row)) = (&page.identifier, rows.first_mut()) {
+        *row = format!("{:^width$}", "OMITTED", width = width).trim_end().to_string();
+    }
+
+    let number = page.page_number.as_ref().map(|number| match config.page_numbering.position {
+        PageNumberPosition::TopRight | PageNumberPosition::BottomRight => {
+            format!("{:>width$}", number, width = width)
+        }
+        PageNumberPosition::TopCenter | PageNumberPosition::BottomCenter => {
+            format!("{:^width$}", number, width = width).trim_end().to_string()
+        }
+    });
+
+    let number_on_top = matches!(config.page_numbering.position, PageNumberPosition::TopRight | PageNumberPosition::TopCenter);
+    let top = match (&number, margin_header) {
+        (Some(number), Some(running)) if number_on_top => Some(overlay(&running, number)),
+        (Some(number), _) if number_on_top => Some(number.clone()),
+        (_, running) => running,
+    };
+    if let Some(top) = top {
+        rows.insert(0, top);
+        rows.insert(1, String::new());
+    }
+    if let Some(number) = number.filter(|_| !number_on_top) {
+        rows.push(String::new());
+        rows.push(number);
+    }
+
+    // Drop trailing blank rows so short pages stay compact
+    while rows.last().is_some_and(|r| r.is_empty()) {
+        rows.pop();
+    }
+
+    rows.join("\n") + "\n"
+}
+
+/// `text` at the start of a padded `line`, keeping the line's text past it
+fn overlay(text: &str, line: &str) -> String {
+    let width = text.chars().count();
+    let rest: String = line.chars().skip(width).collect();
+    if rest.starts_with(' ') || rest.is_empty() {
+        format!("{}{}", text, rest)
+    } else {
+        format!("{} {}", text, line.trim_start())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use verso_pagination_engine::layout::mark_revisions;
+    use verso_pagination_engine::{paginate_with_options, ElementId, RenderOptions, RevisionColor};
+
+    #[test]
+    fn test_render_text() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::SceneHeading, "int. office - day"),
+            Element::new("2", ElementType::Character, "SARAH"),
+            Element::new("3", ElementType::Dialogue, "Hello?").with_character_name("SARAH"),
+        ];
+        let options = RenderOptions { include_text: true, ..Default::default() };
+        let result = paginate_with_options(&elements, &config, &options);
+
+        let text = render_text(&result, &elements, &config);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "INT. OFFICE - DAY");
+        assert_eq!(lines[2], format!("{}SARAH", " ".repeat(22)));
+        assert_eq!(lines[3], format!("{}Hello?", " ".repeat(10)));
+    }
+
+    #[test]
+    fn test_render_running_header() {
+        let mut config = PageConfig::feature_film();
+        config.header_footer.draft_name = Some("Blue Rev.".to_string());
+        let elements = vec![
+            Element::new("1", ElementType::Action, "Page one.").with_force_page_break(),
+            Element::new("2", ElementType::Action, "Page two."),
+        ];
+        let options = RenderOptions { include_text: true, ..Default::default() };
+        let result = paginate_with_options(&elements, &config, &options);
+
+        let text = render_text(&result, &elements, &config);
+        let pages: Vec<&str> = text.split('\u{c}').collect();
+
+        // Without reserved lines the header shares the page number's margin row
+        assert!(pages[0].starts_with("Blue Rev.\n\nPage one."));
+        let first = pages[1].lines().nth(1).unwrap();
+        assert!(first.starts_with("Blue Rev.  "));
+        assert!(first.ends_with(" 2."));
+    }
+
+    #[test]
+    fn test_render_av_columns() {
+        let config = PageConfig::av_script();
+        let elements = vec![
+            Element::new("1", ElementType::AvVideo, "WIDE SHOT"),
+            Element::new("2", ElementType::AvAudio, "VO: Mornings."),
+        ];
+        let options = RenderOptions { include_text: true, ..Default::default() };
+        let result = paginate_with_options(&elements, &config, &options);
+
+        let text = render_text(&result, &elements, &config);
+        let first = text.lines().next().unwrap();
+
+        assert_eq!(first, format!("WIDE SHOT{}VO: Mornings.", " ".repeat(34 - 9)));
+    }
+
+    #[test]
+    fn test_render_dual_dialogue_columns() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::Character, "SARAH").with_dual_dialogue_position(DualDialoguePosition::Left),
+            Element::new("2", ElementType::Dialogue, "Now.").with_dual_dialogue_position(DualDialoguePosition::Left),
+            Element::new("3", ElementType::Character, "JOHN").with_dual_dialogue_position(DualDialoguePosition::Right),
+            Element::new("4", ElementType::Dialogue, "Never.").with_dual_dialogue_position(DualDialoguePosition::Right),
+        ];
+        let options = RenderOptions { include_text: true, ..Default::default() };
+        let result = paginate_with_options(&elements, &config, &options);
+
+        let text = render_text(&result, &elements, &config);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], format!("{}SARAH{}JOHN", " ".repeat(11), " ".repeat(30 + 11 - 16)));
+        assert_eq!(lines[1], format!("{}Now.{}Never.", " ".repeat(5), " ".repeat(30 + 5 - 9)));
+    }
+
+    #[test]
+    fn test_render_revision_asterisks() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::Action, "Unchanged."),
+            Element::new("2", ElementType::Action, "Rewritten."),
+        ];
+        let options = RenderOptions { include_text: true, ..Default::default() };
+        let mut result = paginate_with_options(&elements, &config, &options);
+        mark_revisions(&mut result, &[ElementId::new("2")], RevisionColor::Blue);
+
+        let text = render_text(&result, &elements, &config);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "Unchanged.");
+        assert_eq!(lines[2], format!("Rewritten.{}*", " ".repeat(60 + 2 - 10)));
+    }
+}
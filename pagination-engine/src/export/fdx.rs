@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+
+use crate::layout::{dual_columns, end_of_act_text, number_scenes};
+use crate::types::{Element, ElementType, PageConfig, PageIdentifier, PaginationResult};
+use crate::utils::emphasis_runs;
+use super::escape_xml;
+
+/// Serialize elements to Final Draft (FDX) XML with the computed pagination
+///
+/// Each element becomes a paragraph of the matching Final Draft type, or of
+/// a custom paragraph type named after its custom type; scene headings carry
+/// their scene numbers and dual dialogue blocks are nested the way Final
+/// Draft stores them. A paragraph that opens a page is marked
+/// `StartsNewPage="Yes"` so Final Draft breaks where this engine did, and a
+/// `VersoPages` block after the content lists every page with its identifier
+/// (47A, omitted pages) and first element for locking on re-import. Notes,
+/// sections, synopses and page break markers have no paragraph.
+pub fn export_fdx(elements: &[Element], result: &PaginationResult, config: &PageConfig) -> String {
+    let scene_numbers = number_scenes(elements);
+
+    // Elements that open a page other than the first
+    let page_starts: HashSet<&str> = result
+        .pages
+        .iter()
+        .skip(1)
+        .filter_map(|page| page.elements.first())
+        .filter(|first| !first.is_continuation)
+        .map(|first| first.element_id.0.as_str())
+        .collect();
+
+    let paragraph = |element: &Element, indent: &str| -> Option<String> {
+        let (fdx_type, alignment) = fdx_type(element.element_type)?;
+        let fdx_type = element.custom_type.as_deref().unwrap_or(fdx_type);
+        let mut attributes = format!(" Type=\"{}\"", escape_xml(fdx_type));
+        if let Some(number) = scene_numbers.get(element.id.0.as_str()) {
+            attributes.push_str(&format!(" Number=\"{}\"", escape_xml(number)));
+        }
+        if let Some(alignment) = alignment {
+            attributes.push_str(&format!(" Alignment=\"{}\"", alignment));
+        }
+        if page_starts.contains(element.id.0.as_str()) {
+            attributes.push_str(" StartsNewPage=\"Yes\"");
+        }
+
+        let text: String = emphasis_runs(&paragraph_text(element, config))
+            .into_iter()
+            .map(|(run, emphasis)| {
+                let styles: Vec<&str> = [(emphasis.bold, "Bold"), (emphasis.italic, "Italic"), (emphasis.underline, "Underline")]
+                    .into_iter()
+                    .filter_map(|(on, style)| on.then_some(style))
+                    .collect();
+                if styles.is_empty() {
+                    format!("<Text>{}</Text>", escape_xml(&run))
+                } else {
+                    format!("<Text Style=\"{}\">{}</Text>", styles.join("+"), escape_xml(&run))
+                }
+            })
+            .collect();
+
+        Some(format!("{indent}<Paragraph{attributes}>\n{indent}  {text}\n{indent}</Paragraph>\n"))
+    };
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\" ?>\n");
+    out.push_str("<FinalDraft DocumentType=\"Script\" Template=\"No\" Version=\"5\">\n");
+    out.push_str("  <Content>\n");
+
+    let mut idx = 0;
+    while idx < elements.len() {
+        let (left, right) = dual_columns(&elements[idx..]);
+        if right > 0 {
+            out.push_str("    <Paragraph>\n      <DualDialogue>\n");
+            for element in &elements[idx..idx + left + right] {
+                out.extend(paragraph(element, "        "));
+            }
+            out.push_str("      </DualDialogue>\n    </Paragraph>\n");
+            idx += left + right;
+        } else {
+            out.extend(paragraph(&elements[idx], "    "));
+            idx += 1;
+        }
+    }
+
+    out.push_str("  </Content>\n");
+    out.push_str("  <VersoPages>\n");
+    for page in &result.pages {
+        let (number, omitted) = match &page.identifier {
+            PageIdentifier::Omitted(n) => (n.to_string(), true),
+            other => (other.display(), false),
+        };
+        let first = page.elements.first().map(|e| e.element_id.0.as_str()).unwrap_or_default();
+        out.push_str(&format!(
+            "    <Page Number=\"{}\" FirstElement=\"{}\"{} />\n",
+            escape_xml(&number),
+            escape_xml(first),
+            if omitted { " Omitted=\"Yes\"" } else { "" },
+        ));
+    }
+    out.push_str("  </VersoPages>\n");
+    out.push_str("</FinalDraft>\n");
+
+    out
+}
+
+/// Final Draft paragraph type and alignment for an element type (None for no paragraph)
+fn fdx_type(element_type: ElementType) -> Option<(&'static str, Option<&'static str>)> {
+    let fdx_type = match element_type {
+        ElementType::SceneHeading | ElementType::OmittedScene => "Scene Heading",
+        ElementType::Action | ElementType::AvVideo | ElementType::AvAudio | ElementType::BlankLine => "Action",
+        ElementType::Character => "Character",
+        ElementType::Dialogue
+        | ElementType::Lyrics
+        | ElementType::DualDialogueLeft
+        | ElementType::DualDialogueRight => "Dialogue",
+        ElementType::Parenthetical => "Parenthetical",
+        ElementType::Transition => "Transition",
+        ElementType::Shot => "Shot",
+        ElementType::ActBreak => "End of Act",
+        ElementType::Centered => return Some(("Action", Some("Center"))),
+        ElementType::PageBreak
+        | ElementType::OmittedPage
+        | ElementType::Note
+        | ElementType::Section
+        | ElementType::Synopsis => return None,
+    };
+    Some((fdx_type, None))
+}
+
+/// Paragraph text as it prints, with emphasis markup left in
+fn paragraph_text(element: &Element, config: &PageConfig) -> String {
+    match element.element_type {
+        ElementType::OmittedScene => "OMITTED".to_string(),
+        ElementType::ActBreak if config.act_breaks.end_of_act_text => end_of_act_text(&element.content),
+        ElementType::Lyrics => element
+            .content
+            .lines()
+            .map(|line| line.strip_prefix('~').unwrap_or(line).trim_start())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => element.content.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::DualDialoguePosition;
+
+    #[test]
+    fn test_export_fdx_paragraphs() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::SceneHeading, "INT. BAR & GRILL - NIGHT"),
+            Element::new("2", ElementType::Action, "A **loud** crash.").with_force_page_break(),
+            Element::new("3", ElementType::Centered, "THE END"),
+            Element::new("4", ElementType::Note, "Cut this?"),
+        ];
+        let result = paginate(&elements, &config);
+
+        let fdx = export_fdx(&elements, &result, &config);
+
+        assert!(fdx.contains("<Paragraph Type=\"Scene Heading\" Number=\"1\">"));
+        assert!(fdx.contains("<Text>INT. BAR &amp; GRILL - NIGHT</Text>"));
+        assert!(fdx.contains("<Text>A </Text><Text Style=\"Bold\">loud</Text><Text> crash.</Text>"));
+        assert!(fdx.contains("<Paragraph Type=\"Action\" Alignment=\"Center\" StartsNewPage=\"Yes\">"));
+        assert!(!fdx.contains("Cut this?"));
+        assert!(fdx.contains("<Page Number=\"2\" FirstElement=\"3\" />"));
+    }
+
+    #[test]
+    fn test_export_fdx_dual_dialogue() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::Character, "BRICK").with_dual_dialogue_position(DualDialoguePosition::Left),
+            Element::new("2", ElementType::Dialogue, "Now.").with_dual_dialogue_position(DualDialoguePosition::Left),
+            Element::new("3", ElementType::Character, "STEEL").with_dual_dialogue_position(DualDialoguePosition::Right),
+            Element::new("4", ElementType::Dialogue, "Now!").with_dual_dialogue_position(DualDialoguePosition::Right),
+        ];
+        let result = paginate(&elements, &config);
+
+        let fdx = export_fdx(&elements, &result, &config);
+        let block = &fdx[fdx.find("<DualDialogue>").unwrap()..fdx.find("</DualDialogue>").unwrap()];
+
+        assert_eq!(block.matches("<Paragraph Type=").count(), 4);
+    }
+}
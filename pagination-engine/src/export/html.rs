@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use crate::layout::{apply_render_options, LineCalculator};
+use crate::types::{
+    Element, ElementType, Page, PageConfig, PageIdentifier, PageNumberPosition, PaginationResult, RenderOptions,
+    TextDirection,
+};
+use super::escape_xml;
+
+/// Render paginated pages as HTML, one `<div class="page">` per page
+///
+/// Every printed line is a `<div class="line">` absolutely positioned in
+/// points from the page's top-left corner, so web apps that can't run the
+/// WASM module show the exact layout of the result: (MORE)/(CONT'D) markers,
+/// dual dialogue columns, running and continued headers, page numbers and the
+/// title page included. Lines carry the element type as a class
+/// (`scene_heading`, `dialogue`, `title_page`, a custom type's name, ...)
+/// and revised lines a `revised` class; fonts and page chrome are left to
+/// the stylesheet.
+/// Right-to-left lines are styled `direction:rtl` and their markers mirrored.
+pub fn render_html(result: &PaginationResult, elements: &[Element], config: &PageConfig) -> String {
+    let options = RenderOptions { include_geometry: true, include_text: true, ..Default::default() };
+    let mut pages = result.pages.clone();
+    apply_render_options(&mut pages, elements, config, &options);
+
+    let by_id: HashMap<&str, &Element> = elements.iter().map(|e| (e.id.0.as_str(), e)).collect();
+
+    let mut out = String::from("<div class=\"screenplay\">\n");
+    for page in result.title_page.iter().chain(&pages) {
+        render_page(&mut out, page, &by_id, config);
+    }
+    out.push_str("</div>\n");
+
+    out
+}
+
+fn render_page(out: &mut String, page: &Page, by_id: &HashMap<&str, &Element>, config: &PageConfig) {
+    let number = match &page.identifier {
+        PageIdentifier::Omitted(n) => n.to_string(),
+        other => other.display(),
+    };
+    let mut attributes = format!(" data-page=\"{}\"", escape_xml(&number));
+    if let Some(color) = page.revision_color {
+        attributes.push_str(&format!(" data-revision=\"{}\"", serde_name(&color)));
+    }
+    out.push_str(&format!(
+        "  <div class=\"page\"{} style=\"position:relative;width:{}pt;height:{}pt\">\n",
+        attributes,
+        config.paper_size.width_pt(),
+        config.paper_size.height_pt(),
+    ));
+
+    let left = config.margins.left_pt();
+    let top = config.margins.top_pt();
+    let line_height = config.line_height_pt;
+    let full_width = format!("width:{}pt;", config.printable_width_pt());
+    let calc = LineCalculator::new(config);
+
+    // A running header without reserved lines prints in the top margin, on the page number's line
+    let running_lines = config.header_footer.header_lines;
+    if let Some(running) = &page.running_header {
+        let y_pt = if running_lines == 0 { top - 2.0 * line_height } else { top };
+        push_line(out, "running-header", left, y_pt, "", running);
+    }
+    if let Some(header) = &page.header {
+        let above = if page.running_header.is_some() { running_lines } else { 0 };
+        push_line(out, "header", left, top + above as f64 * line_height, "", header);
+    }
+    if let PageIdentifier::Omitted(_) = page.identifier {
+        push_line(out, "omitted", left, top, &format!("{}text-align:center;", full_width), "OMITTED");
+    }
+
+    for page_element in &page.elements {
+        // Title page blocks have no element behind them
+        let element = by_id.get(page_element.element_id.0.as_str());
+        let element_type = element.map(|element| element.element_type);
+        let rtl = element.is_some_and(|element| config.text_direction_for(element) == TextDirection::Rtl);
+        let style = if rtl { "direction:rtl;" } else { "" };
+        let (Some(text), Some(positions)) = (&page_element.text, &page_element.line_positions) else {
+            continue;
+        };
+        let (Some(first), Some(last)) = (positions.first(), positions.last()) else {
+            continue;
+        };
+
+        let class = match element {
+            Some(element) => element.custom_type.as_deref().map_or_else(|| serde_name(&element.element_type), escape_xml),
+            None => "title_page".to_string(),
+        };
+        let markers = config.continuation_style.for_type(element_type.unwrap_or_default());
+        // Markers that take a line go above and below the content at their
+        // indent; ones printed in the margin share the first and last lines.
+        // Right-to-left, both are mirrored and end at the indent or margin.
+        let (marker_edge, marker_offset) = if markers.consumes_line {
+            let indent = markers.indent.map_or_else(
+                || config.style_for(ElementType::Character).margin_left * 72.0,
+                |indent| f64::from(indent) * config.char_width_pt,
+            );
+            let edge = if rtl { left + config.printable_width_pt() - indent } else { left + indent };
+            (edge, line_height)
+        } else {
+            let edge = if rtl {
+                left - 2.0 * config.char_width_pt
+            } else {
+                left + config.printable_width_pt() + 2.0 * config.char_width_pt
+            };
+            (edge, 0.0)
+        };
+        let marker_left = |marker: &str| if rtl { marker_edge - calc.text_width_pt(marker) } else { marker_edge };
+
+        let mut lines = text.iter();
+        if page_element.continuation_prefix.is_some() {
+            if let Some(prefix) = lines.next() {
+                push_line(out, &format!("{} marker", class), marker_left(prefix), first.y_pt - marker_offset, style, prefix);
+            }
+        }
+
+        // Positions lead the zip so it doesn't swallow the marker after the last content line
+        for (i, (position, line)) in positions.iter().zip(lines.by_ref()).enumerate() {
+            let revised = page_element
+                .revision_marks
+                .as_ref()
+                .is_some_and(|marks| marks.get(i).copied().unwrap_or(false));
+            let class = if revised { format!("{} revised", class) } else { class.clone() };
+            push_line(out, &class, position.x_pt, position.y_pt, style, line);
+        }
+
+        // Whatever follows the content lines is the (MORE) marker of the page's last element
+        for (i, marker) in lines.enumerate() {
+            let y_pt = last.y_pt + marker_offset + i as f64 * line_height;
+            push_line(out, &format!("{} marker", class), marker_left(marker), y_pt, style, marker);
+        }
+    }
+
+    if let Some(number) = &page.page_number {
+        let (y_pt, align) = match config.page_numbering.position {
+            PageNumberPosition::TopRight => (top - 2.0 * line_height, "right"),
+            PageNumberPosition::TopCenter => (top - 2.0 * line_height, "center"),
+            PageNumberPosition::BottomRight => (top + (config.lines_per_page as f64 + 1.0) * line_height, "right"),
+            PageNumberPosition::BottomCenter => (top + (config.lines_per_page as f64 + 1.0) * line_height, "center"),
+        };
+        push_line(out, "page-number", left, y_pt, &format!("{}text-align:{};", full_width, align), number);
+    }
+
+    out.push_str("  </div>\n");
+}
+
+fn push_line(out: &mut String, class: &str, x_pt: f64, y_pt: f64, style: &str, text: &str) {
+    out.push_str(&format!(
+        "    <div class=\"line {}\" style=\"position:absolute;left:{}pt;top:{}pt;{}white-space:pre\">{}</div>\n",
+        class,
+        x_pt,
+        y_pt,
+        style,
+        escape_xml(text),
+    ));
+}
+
+/// Serialized (snake_case) name of a unit enum variant, for classes and data attributes
+fn serde_name<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+
+    #[test]
+    fn test_render_html_pages_and_lines() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::SceneHeading, "INT. BAR & GRILL - NIGHT"),
+            Element::new("2", ElementType::Action, "Page one.").with_force_page_break(),
+            Element::new("3", ElementType::Action, "Page two."),
+        ];
+        let result = paginate(&elements, &config);
+
+        let html = render_html(&result, &elements, &config);
+
+        assert_eq!(html.matches("<div class=\"page\"").count(), 2);
+        assert!(html.contains(
+            "<div class=\"line scene_heading\" style=\"position:absolute;left:108pt;top:72pt;white-space:pre\">INT. BAR &amp; GRILL - NIGHT</div>"
+        ));
+        assert!(html.contains("data-page=\"2\""));
+        assert!(html.contains("text-align:right;white-space:pre\">2.</div>"));
+    }
+
+    #[test]
+    fn test_render_html_continuation_markers() {
+        let config = PageConfig::feature_film();
+        let filler = "Filler line.\n".repeat(44);
+        let speech = "Speech line.\n".repeat(20);
+        let elements = vec![
+            Element::new("1", ElementType::Action, filler.trim_end()),
+            Element::new("2", ElementType::Dialogue, speech.trim_end()).with_character_name("SARAH"),
+        ];
+        let result = paginate(&elements, &config);
+
+        let html = render_html(&result, &elements, &config);
+
+        assert!(html.contains("class=\"line dialogue marker\""));
+        assert!(html.contains(">(MORE)</div>"));
+        assert!(html.contains(">SARAH (CONT&apos;D)</div>"));
+    }
+
+    #[test]
+    fn test_render_html_right_to_left() {
+        let mut config = PageConfig::feature_film();
+        config.text_direction = TextDirection::Rtl;
+        let elements = vec![Element::new("1", ElementType::Action, "שלום.")];
+        let result = paginate(&elements, &config);
+
+        let html = render_html(&result, &elements, &config);
+
+        assert!(html.contains("left:504pt;top:72pt;direction:rtl;white-space:pre\">שלום.</div>"));
+    }
+}
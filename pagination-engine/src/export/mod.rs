@@ -0,0 +1,23 @@
+//! Writers for other screenplay formats, carrying the computed pagination along
+
+mod fdx;
+mod html;
+
+pub use fdx::*;
+pub use html::*;
+
+/// Escape text for XML and HTML content and attribute values
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,374 @@
+//! Fountain screenplay markup (enabled by the `fountain` feature)
+
+use crate::layout::{layout_title_page, paginate};
+use crate::types::{DualDialoguePosition, Element, ElementType, PageConfig, PaginationResult, TitlePage};
+
+const TITLE_PAGE_KEYS: [&str; 11] = [
+    "title", "credit", "author", "authors", "source", "draft date", "date", "contact",
+    "copyright", "notes", "revision",
+];
+
+const SCENE_PREFIXES: [&str; 8] = ["INT.", "EXT.", "EST.", "INT/EXT", "INT./EXT", "EXT/INT", "I/E", "INT "];
+
+/// Parse Fountain source into pagination elements
+///
+/// Covers the constructs that affect page layout: title page (skipped; see
+/// `parse_title_page`),
+/// scene headings, action, character cues with parentheticals and dialogue,
+/// dual dialogue, lyrics, transitions, centered text, page breaks, sections,
+/// synopses and notes.
+pub fn parse(source: &str) -> Vec<Element> {
+    let source = source.replace("\r\n", "\n");
+    let body = skip_title_page(&source);
+    let mut elements = Vec::new();
+
+    for block in body.split("\n\n") {
+        let lines: Vec<&str> = block.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+        if lines.is_empty() {
+            continue;
+        }
+        parse_block(&lines, &mut elements);
+    }
+
+    for (i, element) in elements.iter_mut().enumerate() {
+        element.id.0 = (i + 1).to_string();
+    }
+
+    elements
+}
+
+/// Read the leading `Key: value` title page block, if any
+///
+/// Values may continue on indented lines below their key. Keys with no
+/// title page block (copyright, notes, revision) are ignored.
+pub fn parse_title_page(source: &str) -> Option<TitlePage> {
+    let source = source.replace("\r\n", "\n");
+    let block = &source[..source.len() - skip_title_page(&source).len()];
+    if block.is_empty() {
+        return None;
+    }
+
+    let mut fields: Vec<(String, Vec<String>)> = Vec::new();
+    for line in block.lines() {
+        let value = match line.split_once(':') {
+            Some((key, value)) if !line.starts_with([' ', '\t']) => {
+                fields.push((key.trim().to_lowercase(), Vec::new()));
+                value
+            }
+            _ => line,
+        };
+        if let Some((_, values)) = fields.last_mut().filter(|_| !value.trim().is_empty()) {
+            values.push(value.trim().to_string());
+        }
+    }
+
+    let mut title_page = TitlePage::default();
+    for (key, values) in fields {
+        match key.as_str() {
+            "title" => title_page.title = values.join("\n"),
+            "credit" => title_page.credit = Some(values.join("\n")),
+            "author" | "authors" => title_page.authors.extend(values),
+            "source" => title_page.source = Some(values.join("\n")),
+            "draft date" | "date" => title_page.draft_date = Some(values.join(" ")),
+            "contact" => title_page.contact.extend(values),
+            _ => {}
+        }
+    }
+
+    Some(title_page)
+}
+
+/// Parse Fountain source and paginate it, with its title page as page 0
+pub fn paginate_fountain(source: &str, config: &PageConfig) -> PaginationResult {
+    let mut result = paginate(&parse(source), config);
+    result.title_page = parse_title_page(source).map(|title_page| layout_title_page(&title_page, config));
+    result
+}
+
+/// Drop a leading `Key: value` title page block
+fn skip_title_page(source: &str) -> &str {
+    let first = source.lines().next().unwrap_or("");
+    let is_title_key = first
+        .split_once(':')
+        .is_some_and(|(key, _)| TITLE_PAGE_KEYS.contains(&key.trim().to_lowercase().as_str()));
+
+    if !is_title_key {
+        return source;
+    }
+
+    match source.find("\n\n") {
+        Some(end) => &source[end + 2..],
+        None => "",
+    }
+}
+
+fn parse_block(lines: &[&str], elements: &mut Vec<Element>) {
+    let first = lines[0];
+
+    // Single-line constructs
+    if lines.len() == 1 {
+        if let Some(element) = parse_single_line(first) {
+            elements.push(element);
+            return;
+        }
+    }
+
+    if first.starts_with("[[") && lines.last().is_some_and(|l| l.ends_with("]]")) {
+        let note = lines.join("\n");
+        let note = note.trim_start_matches("[[").trim_end_matches("]]").trim();
+        elements.push(Element::new("", ElementType::Note, note));
+        return;
+    }
+
+    if first.starts_with('~') {
+        elements.push(Element::new("", ElementType::Lyrics, lines.join("\n")));
+        return;
+    }
+
+    if lines.len() > 1 && is_character_cue(first) {
+        parse_dialogue_block(lines, elements);
+        return;
+    }
+
+    let content = lines.join("\n");
+    let content = content.strip_prefix('!').unwrap_or(&content);
+    elements.push(Element::new("", ElementType::Action, content));
+}
+
+fn parse_single_line(line: &str) -> Option<Element> {
+    if line.chars().all(|c| c == '=') && line.len() >= 3 {
+        return Some(Element::new("", ElementType::PageBreak, ""));
+    }
+
+    if let Some(section) = line.strip_prefix('#') {
+        return Some(Element::new("", ElementType::Section, section.trim_start_matches('#').trim()));
+    }
+
+    if let Some(synopsis) = line.strip_prefix('=') {
+        return Some(Element::new("", ElementType::Synopsis, synopsis.trim()));
+    }
+
+    if let Some(centered) = line.strip_prefix('>').and_then(|l| l.strip_suffix('<')) {
+        return Some(Element::new("", ElementType::Centered, centered.trim()));
+    }
+
+    if let Some(transition) = line.strip_prefix('>') {
+        return Some(Element::new("", ElementType::Transition, transition.trim()));
+    }
+
+    if is_uppercase(line) && line.ends_with("TO:") {
+        return Some(Element::new("", ElementType::Transition, line));
+    }
+
+    if let Some(heading) = line.strip_prefix('.').filter(|h| !h.starts_with('.')) {
+        return Some(scene_heading(heading.trim()));
+    }
+
+    let upper = line.to_uppercase();
+    if SCENE_PREFIXES.iter().any(|prefix| upper.starts_with(prefix)) {
+        return Some(scene_heading(line));
+    }
+
+    None
+}
+
+/// Scene heading with any trailing `#12A#` scene number taken off as its locked number
+fn scene_heading(line: &str) -> Element {
+    let numbered = line
+        .strip_suffix('#')
+        .and_then(|rest| rest.rsplit_once('#'))
+        .filter(|(_, number)| !number.trim().is_empty());
+
+    match numbered {
+        Some((heading, number)) => {
+            Element::new("", ElementType::SceneHeading, heading.trim_end()).with_scene_number(number.trim())
+        }
+        None => Element::new("", ElementType::SceneHeading, line),
+    }
+}
+
+fn is_uppercase(line: &str) -> bool {
+    line.chars().any(char::is_alphabetic) && !line.chars().any(char::is_lowercase)
+}
+
+fn is_character_cue(line: &str) -> bool {
+    if line.starts_with('@') {
+        return true;
+    }
+
+    // Extensions like "(V.O.)" or "(cont'd)" may be lowercase
+    let name = line.split('(').next().unwrap_or(line).trim_end_matches('^').trim();
+    !name.is_empty() && is_uppercase(name) && !line.starts_with('!')
+}
+
+fn parse_dialogue_block(lines: &[&str], elements: &mut Vec<Element>) {
+    let cue = lines[0].trim_start_matches('@');
+    let (cue, dual) = match cue.strip_suffix('^') {
+        Some(cue) => (cue.trim(), true),
+        None => (cue, false),
+    };
+    let name = cue.split('(').next().unwrap_or(cue).trim().to_string();
+
+    // Dual dialogue: the previous character block becomes the left column
+    if dual {
+        let start = elements
+            .iter()
+            .rposition(|e| e.element_type == ElementType::Character)
+            .unwrap_or(elements.len());
+        for element in &mut elements[start..] {
+            element.dual_dialogue_position = Some(DualDialoguePosition::Left);
+        }
+    }
+    let position = dual.then_some(DualDialoguePosition::Right);
+
+    let push = |element_type: ElementType, content: String, elements: &mut Vec<Element>| {
+        let mut element = Element::new("", element_type, content);
+        if element_type != ElementType::Character {
+            element = element.with_character_name(name.clone());
+        }
+        element.dual_dialogue_position = position;
+        elements.push(element);
+    };
+
+    push(ElementType::Character, cue.to_string(), elements);
+
+    let mut speech: Vec<&str> = Vec::new();
+    for line in &lines[1..] {
+        if line.starts_with('(') {
+            if !speech.is_empty() {
+                push(ElementType::Dialogue, speech.join("\n"), elements);
+                speech.clear();
+            }
+            push(ElementType::Parenthetical, line.to_string(), elements);
+        } else {
+            speech.push(line);
+        }
+    }
+    if !speech.is_empty() {
+        push(ElementType::Dialogue, speech.join("\n"), elements);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types(elements: &[Element]) -> Vec<ElementType> {
+        elements.iter().map(|e| e.element_type).collect()
+    }
+
+    #[test]
+    fn test_basic_scene() {
+        let source = "INT. OFFICE - DAY\n\nA busy office.\n\nSARAH\n(quietly)\nHello?\n\nCUT TO:";
+        let elements = parse(source);
+
+        assert_eq!(
+            types(&elements),
+            vec![
+                ElementType::SceneHeading,
+                ElementType::Action,
+                ElementType::Character,
+                ElementType::Parenthetical,
+                ElementType::Dialogue,
+                ElementType::Transition,
+            ]
+        );
+        assert_eq!(elements[4].character_name.as_deref(), Some("SARAH"));
+        assert_eq!(elements[0].id.0, "1");
+    }
+
+    #[test]
+    fn test_scene_numbers() {
+        let elements = parse("INT. OFFICE - DAY #12A#\n\n.FLASHBACK\n\nEXT. STREET - NIGHT #1");
+        assert_eq!(elements[0].content, "INT. OFFICE - DAY");
+        assert_eq!(elements[0].scene_number.as_deref(), Some("12A"));
+        assert_eq!(elements[1].scene_number, None);
+        assert_eq!(elements[2].content, "EXT. STREET - NIGHT #1");
+    }
+
+    #[test]
+    fn test_title_page_skipped() {
+        let source = "Title: Test\nAuthor: Someone\n\nEXT. STREET - NIGHT";
+        let elements = parse(source);
+        assert_eq!(types(&elements), vec![ElementType::SceneHeading]);
+
+        // A leading transition-like line is not a title page
+        let elements = parse("FADE IN:\n\nEXT. STREET - NIGHT");
+        assert_eq!(types(&elements), vec![ElementType::Action, ElementType::SceneHeading]);
+    }
+
+    #[test]
+    fn test_parse_title_page() {
+        let source = "Title: BRICK & STEEL\nCredit: Written by\nAuthor: Stu Maschwitz\nDraft date: 1/20/2012\nContact:\n    Next Level Productions\n    1588 Mission Dr.\n\nEXT. STREET - NIGHT";
+
+        let title_page = parse_title_page(source).unwrap();
+
+        assert_eq!(title_page.title, "BRICK & STEEL");
+        assert_eq!(title_page.credit.as_deref(), Some("Written by"));
+        assert_eq!(title_page.authors, vec!["Stu Maschwitz"]);
+        assert_eq!(title_page.draft_date.as_deref(), Some("1/20/2012"));
+        assert_eq!(title_page.contact, vec!["Next Level Productions", "1588 Mission Dr."]);
+        assert!(parse_title_page("EXT. STREET - NIGHT").is_none());
+
+        let result = paginate_fountain(source, &PageConfig::feature_film());
+        assert_eq!(result.title_page.unwrap().elements[0].text, Some(vec!["BRICK & STEEL".to_string()]));
+        assert_eq!(result.pages[0].identifier, crate::types::PageIdentifier::Sequential(1));
+    }
+
+    #[test]
+    fn test_indented_screenplay_text() {
+        let source = "                    JAKE (CONT'D)\n          What's the story about?";
+        let elements = parse(source);
+
+        assert_eq!(types(&elements), vec![ElementType::Character, ElementType::Dialogue]);
+        assert_eq!(elements[1].character_name.as_deref(), Some("JAKE"));
+    }
+
+    #[test]
+    fn test_forced_and_special_elements() {
+        let source = ".FLASHBACK\n\n> THE END <\n\n===\n\n# Act One\n\n= Setup\n\n[[fix this]]";
+        let elements = parse(source);
+
+        assert_eq!(
+            types(&elements),
+            vec![
+                ElementType::SceneHeading,
+                ElementType::Centered,
+                ElementType::PageBreak,
+                ElementType::Section,
+                ElementType::Synopsis,
+                ElementType::Note,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dual_dialogue() {
+        let source = "BRICK\nScrew retirement.\n\nSTEEL ^\nScrew retirement.";
+        let elements = parse(source);
+
+        assert_eq!(elements[0].dual_dialogue_position, Some(DualDialoguePosition::Left));
+        assert_eq!(elements[3].dual_dialogue_position, Some(DualDialoguePosition::Right));
+        assert_eq!(elements[2].content, "STEEL");
+    }
+
+    #[test]
+    fn test_forced_cue_and_transition() {
+        let elements = parse("@McCLANE\nYippee ki-yay.\n\n> SMASH CUT TO:");
+
+        assert_eq!(
+            types(&elements),
+            vec![ElementType::Character, ElementType::Dialogue, ElementType::Transition]
+        );
+        assert_eq!(elements[2].content, "SMASH CUT TO:");
+    }
+
+    #[test]
+    fn test_paginate_fountain() {
+        let config = PageConfig::feature_film();
+        let result = paginate_fountain("INT. OFFICE - DAY\n\nA busy office.\n\n===\n\nEXT. STREET - NIGHT", &config);
+
+        assert_eq!(result.page_count(), 2);
+        assert_eq!(result.scene_index.len(), 2);
+    }
+}
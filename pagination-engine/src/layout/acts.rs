@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use crate::types::{ActStats, Element, ElementPosition, ElementType, Page, PageIdentifier};
+
+/// Measure the pages spanned by each act
+///
+/// Act breaks close the act they end, so an act runs from the element after
+/// the previous break through its own break. Content after the last break
+/// forms a final, unlabelled act. Acts with nothing placed are skipped, and
+/// documents without act breaks report no acts.
+pub fn calculate_act_stats(
+    elements: &[Element],
+    pages: &[Page],
+    element_positions: &HashMap<String, ElementPosition>,
+) -> Vec<ActStats> {
+    if !elements.iter().any(|e| e.element_type == ElementType::ActBreak) {
+        return Vec::new();
+    }
+
+    let page_index: HashMap<&PageIdentifier, usize> =
+        pages.iter().enumerate().map(|(i, page)| (&page.identifier, i)).collect();
+
+    let mut acts = Vec::new();
+    let mut span: Option<(usize, usize)> = None;
+
+    let mut close_act = |span: &mut Option<(usize, usize)>, label: String| {
+        if let Some((first, last)) = span.take() {
+            acts.push(ActStats {
+                act: acts.len() as u32 + 1,
+                label,
+                start_page: pages[first].identifier.clone(),
+                end_page: pages[last].identifier.clone(),
+                page_count: (last - first + 1) as u32,
+            });
+        }
+    };
+
+    for element in elements {
+        let placed = element_positions
+            .get(&element.id.0)
+            .into_iter()
+            .flat_map(|position| &position.pages)
+            .filter_map(|page| page_index.get(page).copied());
+        for index in placed {
+            span = Some(match span {
+                Some((first, last)) => (first.min(index), last.max(index)),
+                None => (index, index),
+            });
+        }
+
+        if element.element_type == ElementType::ActBreak {
+            close_act(&mut span, act_label(&element.content));
+        }
+    }
+    close_act(&mut span, String::new());
+
+    acts
+}
+
+/// Act name from act break content, without any "END OF" prefix
+fn act_label(content: &str) -> String {
+    let name = content.trim();
+    let has_prefix = name.get(..6).is_some_and(|prefix| prefix.eq_ignore_ascii_case("END OF"));
+    if has_prefix { name[6..].trim_start() } else { name }.to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::PageConfig;
+
+    fn act(prefix: &str, scenes: usize) -> Vec<Element> {
+        (0..scenes)
+            .flat_map(|i| {
+                vec![
+                    Element::new(format!("{}h{}", prefix, i), ElementType::SceneHeading, "INT. ROOM - DAY"),
+                    Element::new(format!("{}a{}", prefix, i), ElementType::Action, "Things happen.\n".repeat(12)),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_act_stats_with_new_page_after() {
+        let mut config = PageConfig::feature_film();
+        config.act_breaks.end_of_act_text = true;
+        config.act_breaks.new_page_after = true;
+
+        let mut elements = act("one", 2);
+        elements.push(Element::new("b1", ElementType::ActBreak, "ACT ONE"));
+        elements.extend(act("two", 6));
+        elements.push(Element::new("b2", ElementType::ActBreak, "END OF ACT TWO"));
+
+        let result = paginate(&elements, &config);
+        let acts = &result.stats.acts;
+
+        assert_eq!(acts.len(), 2);
+        assert_eq!(acts[0].label, "ACT ONE");
+        assert_eq!(acts[1].label, "ACT TWO");
+        assert_eq!(acts[0].start_page, PageIdentifier::Sequential(1));
+
+        // Act two starts on the page after act one ends
+        let PageIdentifier::Sequential(end_one) = acts[0].end_page else { panic!("sequential page expected") };
+        assert_eq!(acts[1].start_page, PageIdentifier::Sequential(end_one + 1));
+        assert_eq!(acts[0].page_count + acts[1].page_count, result.page_count());
+    }
+
+    #[test]
+    fn test_end_of_act_text() {
+        let mut config = PageConfig::feature_film();
+        config.act_breaks.end_of_act_text = true;
+        let calc = crate::layout::LineCalculator::new(&config);
+
+        let lines = calc.calculate(&Element::new("1", ElementType::ActBreak, "ACT ONE"));
+        assert_eq!(lines.wrapped_lines, vec!["END OF ACT ONE"]);
+        let lines = calc.calculate(&Element::new("2", ElementType::ActBreak, "END OF ACT TWO"));
+        assert_eq!(lines.wrapped_lines, vec!["END OF ACT TWO"]);
+    }
+
+    #[test]
+    fn test_no_acts_without_act_breaks() {
+        let config = PageConfig::feature_film();
+        let result = paginate(&act("x", 3), &config);
+        assert!(result.stats.acts.is_empty());
+    }
+}
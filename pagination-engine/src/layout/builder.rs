@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use crate::types::{Element, ElementType, Page, PageConfig, PaginationResult};
+use super::page_breaker::dual_columns;
+use super::{ContinuationManager, LineCalculation, LineCalculator, PaginationState};
+
+/// Push-based pagination for documents that arrive incrementally
+///
+/// Each element is placed as soon as enough of what follows it is known for
+/// keep-with-next and keep-with-previous decisions, and finished pages are handed back from `push`.
+/// Only that lookahead window and the open page are held in memory.
+pub struct PaginationBuilder {
+    config: PageConfig,
+    pending: Vec<Element>,
+    pending_lines: Vec<Arc<LineCalculation>>,
+    state: PaginationState,
+    element_count: usize,
+}
+
+impl PaginationBuilder {
+    pub fn new(config: PageConfig) -> Self {
+        Self {
+            state: PaginationState::new(&config),
+            config,
+            pending: Vec::new(),
+            pending_lines: Vec::new(),
+            element_count: 0,
+        }
+    }
+
+    pub fn config(&self) -> &PageConfig {
+        &self.config
+    }
+
+    /// Add the next element, returning any pages it completed
+    pub fn push(&mut self, element: Element) -> Vec<Page> {
+        let lines = LineCalculator::new(&self.config).calculate(&element);
+        self.pending.push(element);
+        self.pending_lines.push(Arc::new(lines));
+        self.element_count += 1;
+
+        while !self.pending.is_empty() && self.has_lookahead() {
+            self.place_next();
+        }
+
+        self.state.take_completed_pages()
+    }
+
+    /// Place everything still buffered and close the last page
+    ///
+    /// The result's `pages` holds only the pages not already returned by
+    /// `push`; stats, warnings and element positions cover the whole document.
+    /// Scene lengths, the scene index and scene numbers need the full element
+    /// list and are left empty, as are break explanations.
+    pub fn finish(mut self) -> PaginationResult {
+        while !self.pending.is_empty() {
+            self.place_next();
+        }
+
+        let mut result = self.state.finalize(0, self.element_count);
+        result.break_explanations.clear();
+        result
+    }
+
+    /// Whether enough elements follow the first pending one to place it
+    fn has_lookahead(&self) -> bool {
+        // A dual dialogue block is placed whole, once its right column has ended
+        let (left, right) = dual_columns(&self.pending);
+        if left > 0 {
+            return left + right < self.pending.len();
+        }
+
+        // Cues and parentheticals decide for their whole speech block, so
+        // wait until something other than speech follows it
+        if matches!(self.pending[0].element_type, ElementType::Character | ElementType::Parenthetical) {
+            return self.pending[1..].iter().any(|e| {
+                self.config.is_visible(e.element_type)
+                    && !matches!(
+                        e.element_type,
+                        ElementType::Parenthetical | ElementType::Dialogue | ElementType::Lyrics
+                    )
+            });
+        }
+
+        let style = self.config.resolved_style(&self.pending[0]);
+        let needed = if style.keep_with_next { style.keep_with_next_lines as usize } else { 0 };
+
+        // Every element needs to see the next one, which may be a transition bound to it
+        let needed = needed.max(1);
+
+        let following = self.pending[1..]
+            .iter()
+            .filter(|e| self.config.is_visible(e.element_type))
+            .count();
+        following >= needed
+    }
+
+    fn place_next(&mut self) {
+        let continuation_mgr = ContinuationManager::new(&self.config);
+        self.state.place(&self.config, &continuation_mgr, &self.pending, &self.pending_lines);
+
+        self.pending.remove(0);
+        self.pending_lines.remove(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+
+    fn long_document() -> Vec<Element> {
+        (0..40)
+            .flat_map(|i| {
+                vec![
+                    Element::new(format!("h{}", i), ElementType::SceneHeading, format!("INT. ROOM {} - DAY", i)),
+                    Element::new(format!("a{}", i), ElementType::Action, "Something happens.\n".repeat(6).trim_end()),
+                    Element::new(format!("n{}", i), ElementType::Note, "Check this."),
+                    Element::new(format!("c{}", i), ElementType::Character, "SARAH"),
+                    Element::new(format!("d{}", i), ElementType::Dialogue, "Line of speech.\n".repeat(3).trim_end())
+                        .with_character_name("SARAH"),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_streamed_pages_match_batch() {
+        let config = PageConfig::feature_film();
+        let elements = long_document();
+        let mut builder = PaginationBuilder::new(config.clone());
+
+        let mut pages = Vec::new();
+        let mut emitted_early = 0;
+        for element in elements.iter().cloned() {
+            let done = builder.push(element);
+            emitted_early += done.len();
+            pages.extend(done);
+        }
+        let rest = builder.finish();
+        pages.extend(rest.pages);
+
+        // Scene numbers and content hashes need the whole script and are only
+        // filled in by `paginate`
+        let mut batch = paginate(&elements, &config);
+        for page in &mut batch.pages {
+            page.content_hash.clear();
+        }
+        for page_element in batch.pages.iter_mut().flat_map(|page| &mut page.elements) {
+            page_element.scene_number = None;
+        }
+        assert!(emitted_early > 0);
+        assert_eq!(rest.stats.page_count, batch.stats.page_count);
+        assert_eq!(
+            serde_json::to_value(&pages).unwrap(),
+            serde_json::to_value(&batch.pages).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_heading_waits_for_lookahead() {
+        let mut builder = PaginationBuilder::new(PageConfig::feature_film());
+
+        builder.push(Element::new("1", ElementType::SceneHeading, "INT. OFFICE - DAY"));
+        assert_eq!(builder.pending.len(), 1);
+
+        builder.push(Element::new("2", ElementType::Action, "A busy office."));
+        builder.push(Element::new("3", ElementType::Action, "Phones ring."));
+
+        // The last action waits to see whether a transition is bound to it
+        assert_eq!(builder.pending.len(), 1);
+
+        let result = builder.finish();
+        assert_eq!(result.stats.page_count, 1);
+        assert_eq!(result.stats.element_count, 3);
+    }
+}
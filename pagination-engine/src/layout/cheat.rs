@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::types::{
+    CheatConstraints, CheatResult, CheatSuggestion, Element, ElementType, Page, PageBreakReason, PageConfig,
+    PaginationError,
+};
+use super::simulate::{place_all, resized};
+use super::{paginate, try_paginate, LineCalculation, LineCalculator};
+
+/// Pages holding at most this many lines count as spill pages
+const MAX_SPILL_LINES: u8 = 3;
+
+/// Adjust lines per page and dialogue width within `constraints` to bring the
+/// page count as close to `target_pages` as they allow
+///
+/// Every allowed combination is paginated; among those closest to the target
+/// the smallest adjustment wins, so a draft already on target keeps the base
+/// configuration. Fails if the base configuration or elements can't be
+/// paginated.
+pub fn paginate_to_target(
+    elements: &[Element],
+    base_config: &PageConfig,
+    target_pages: u32,
+    constraints: &CheatConstraints,
+) -> Result<CheatResult, PaginationError> {
+    let base_width = base_config.style_for(ElementType::Dialogue).max_chars_per_line;
+
+    let mut best = CheatResult {
+        config: base_config.clone(),
+        result: try_paginate(elements, base_config)?,
+        lines_per_page_change: 0,
+        dialogue_width_change: 0,
+    };
+
+    let lines_range = constraints.max_lines_per_page_change as i16;
+    let width_range = constraints.max_dialogue_width_change as i16;
+    let mut candidates: Vec<(i16, i16)> = (-lines_range..=lines_range)
+        .flat_map(|lines| (-width_range..=width_range).map(move |width| (lines, width)))
+        .filter(|&change| change != (0, 0))
+        .collect();
+    candidates.sort_by_key(|&(lines, width)| lines.abs() + width.abs());
+
+    for (lines_change, width_change) in candidates {
+        if best.result.page_count() == target_pages {
+            break;
+        }
+
+        let lines_per_page = base_config.lines_per_page as i16 + lines_change;
+        let width = base_width as i16 + width_change;
+        if !(1..=u8::MAX as i16).contains(&lines_per_page) || !(1..=u8::MAX as i16).contains(&width) {
+            continue;
+        }
+
+        let mut config = base_config.clone();
+        config.lines_per_page = lines_per_page as u8;
+        let mut dialogue = config.style_for(ElementType::Dialogue).clone();
+        dialogue.max_chars_per_line = width as u8;
+        config.element_styles.insert(ElementType::Dialogue, dialogue);
+
+        let result = paginate(elements, &config);
+        if result.page_count().abs_diff(target_pages) < best.result.page_count().abs_diff(target_pages) {
+            best = CheatResult {
+                config,
+                result,
+                lines_per_page_change: lines_change,
+                dialogue_width_change: width_change,
+            };
+        }
+    }
+
+    Ok(best)
+}
+
+/// Elements that, shortened, would pull a spill page back onto the page
+/// before it
+///
+/// A spill page prints no more than three lines carried over from a page
+/// that ended naturally. For each, every element on it or on the page before
+/// is tried with fewer lines, and the smallest trim that brings the page
+/// count down is suggested. Results are in page order, smallest trims first.
+pub fn cheat_suggestions(elements: &[Element], config: &PageConfig) -> Vec<CheatSuggestion> {
+    let calc = LineCalculator::new(config);
+    let mut line_table: Vec<Arc<LineCalculation>> = elements.iter().map(|e| Arc::new(calc.calculate(e))).collect();
+    let pages = place_all(elements, config, &line_table);
+    let index: HashMap<&str, usize> = elements.iter().enumerate().map(|(i, e)| (e.id.0.as_str(), i)).collect();
+
+    let mut suggestions = Vec::new();
+    for (previous, page) in pages.iter().zip(pages.iter().skip(1)) {
+        let spill_lines = printed_lines(page);
+        let natural = !matches!(
+            previous.break_reason,
+            Some(PageBreakReason::Forced | PageBreakReason::ActBreak | PageBreakReason::Locked)
+        );
+        if spill_lines == 0 || spill_lines > MAX_SPILL_LINES || !natural {
+            continue;
+        }
+
+        let mut candidates: Vec<usize> = previous
+            .elements
+            .iter()
+            .chain(&page.elements)
+            .filter(|page_element| page_element.line_count > 0)
+            .filter_map(|page_element| index.get(page_element.element_id.0.as_str()).copied())
+            .collect();
+        candidates.dedup();
+
+        let mut found = Vec::new();
+        for idx in candidates {
+            let original = line_table[idx].clone();
+            let most = original.content_lines.saturating_sub(1).min(spill_lines as u32 + MAX_SPILL_LINES as u32);
+            let saving = (1..=most).find(|&saving| {
+                line_table[idx] = Arc::new(resized(&original, original.content_lines - saving));
+                place_all(elements, config, &line_table).len() < pages.len()
+            });
+            line_table[idx] = original;
+
+            if let Some(lines_to_save) = saving {
+                found.push(CheatSuggestion {
+                    element_id: elements[idx].id.clone(),
+                    lines_to_save,
+                    page: page.identifier.clone(),
+                    spill_lines,
+                });
+            }
+        }
+        found.sort_by_key(|suggestion| suggestion.lines_to_save);
+        suggestions.extend(found);
+    }
+
+    suggestions
+}
+
+/// Lines from the first printed line of a page to its last, CONT'D cues included
+fn printed_lines(page: &Page) -> u8 {
+    let printed = page.elements.iter().filter(|page_element| page_element.line_count > 0);
+    let first = printed
+        .clone()
+        .map(|page_element| page_element.start_line - u8::from(page_element.continuation_prefix.is_some()))
+        .min();
+    let last = printed.map(|page_element| page_element.start_line + page_element.line_count - 1).max();
+    match (first, last) {
+        (Some(first), Some(last)) => last - first + 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script() -> Vec<Element> {
+        (0..30)
+            .flat_map(|i| {
+                vec![
+                    Element::new(format!("h{}", i), ElementType::SceneHeading, format!("INT. ROOM {} - DAY", i)),
+                    Element::new(format!("a{}", i), ElementType::Action, "Something happens.\n".repeat(8).trim_end()),
+                    Element::new(format!("c{}", i), ElementType::Character, "SARAH"),
+                    Element::new(format!("d{}", i), ElementType::Dialogue, "A speech that wraps onto a few lines. ".repeat(4))
+                        .with_character_name("SARAH"),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_paginate_to_target() {
+        let config = PageConfig::feature_film();
+        let elements = script();
+        let base_pages = paginate(&elements, &config).page_count();
+
+        let same = paginate_to_target(&elements, &config, base_pages, &CheatConstraints::default()).unwrap();
+        assert_eq!((same.lines_per_page_change, same.dialogue_width_change), (0, 0));
+
+        let constraints = CheatConstraints { max_lines_per_page_change: 6, max_dialogue_width_change: 4 };
+        let tighter = paginate_to_target(&elements, &config, base_pages - 1, &constraints).unwrap();
+        assert_eq!(tighter.result.page_count(), base_pages - 1);
+        assert!(tighter.lines_per_page_change > 0 || tighter.dialogue_width_change > 0);
+        assert_eq!(
+            tighter.config.lines_per_page as i16,
+            config.lines_per_page as i16 + tighter.lines_per_page_change
+        );
+
+        let none = CheatConstraints { max_lines_per_page_change: 0, max_dialogue_width_change: 0 };
+        let unchanged = paginate_to_target(&elements, &config, 1, &none).unwrap();
+        assert_eq!(unchanged.result.page_count(), base_pages);
+    }
+
+    #[test]
+    fn test_cheat_suggestions() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::Action, "Something happens.\n".repeat(30).trim_end()),
+            Element::new("2", ElementType::Action, "Then more happens.\n".repeat(26).trim_end()),
+        ];
+        let result = paginate(&elements, &config);
+        assert_eq!(result.page_count(), 2);
+
+        let suggestions = cheat_suggestions(&elements, &config);
+        assert!(!suggestions.is_empty());
+        assert!(suggestions.iter().all(|s| s.page == result.pages[1].identifier && s.spill_lines <= 3));
+        assert!(suggestions.windows(2).all(|pair| pair[0].lines_to_save <= pair[1].lines_to_save));
+
+        // The suggested trim really saves the page
+        let first = &suggestions[0];
+        let mut trimmed = elements.clone();
+        let target = trimmed.iter_mut().find(|e| e.id == first.element_id).unwrap();
+        let kept = target.content.lines().count() - first.lines_to_save as usize;
+        target.content = target.content.lines().take(kept).collect::<Vec<_>>().join("\n");
+        assert_eq!(paginate(&trimmed, &config).page_count(), 1);
+
+        assert!(cheat_suggestions(&elements[..1], &config).is_empty());
+        assert!(cheat_suggestions(&[], &config).is_empty());
+    }
+}
@@ -1,5 +1,7 @@
-use crate::types::{Element, PageConfig};
-use super::LineCalculation;
+use std::borrow::Cow;
+
+use crate::types::{Element, ElementType, PageConfig, SplitPreference, StyleRun, TypeContinuationStyle};
+use super::{line_start_offsets, LineCalculation, WrappedLines};
 
 /// Result of splitting an element across pages
 #[derive(Debug, Clone)]
@@ -10,12 +12,6 @@ pub struct SplitResult {
     /// Lines to show on next page
     pub second_part_lines: u32,
 
-    /// Content lines for first part
-    pub first_part_content: Vec<String>,
-
-    /// Content lines for second part
-    pub second_part_content: Vec<String>,
-
     /// Marker at bottom of page (e.g., "(MORE)")
     pub more_marker: Option<String>,
 
@@ -23,6 +19,30 @@ pub struct SplitResult {
     pub contd_prefix: Option<String>,
 }
 
+impl SplitResult {
+    /// Content lines for the first part, from the calculation that was split
+    pub fn first_part_content<'c>(&self, line_calc: &'c LineCalculation) -> impl Iterator<Item = Cow<'c, str>> {
+        line_calc.wrapped_lines.iter().take(self.first_part_lines as usize)
+    }
+
+    /// Content lines for the second part, from the calculation that was split
+    pub fn second_part_content<'c>(&self, line_calc: &'c LineCalculation) -> impl Iterator<Item = Cow<'c, str>> {
+        line_calc.wrapped_lines.iter().skip(self.first_part_lines as usize)
+    }
+
+    /// Style runs on each content line of the first part (empty without runs)
+    pub fn first_part_style_runs<'c>(&self, line_calc: &'c LineCalculation) -> &'c [Vec<StyleRun>] {
+        let runs = &line_calc.line_style_runs;
+        &runs[..(self.first_part_lines as usize).min(runs.len())]
+    }
+
+    /// Style runs on each content line of the second part (empty without runs)
+    pub fn second_part_style_runs<'c>(&self, line_calc: &'c LineCalculation) -> &'c [Vec<StyleRun>] {
+        let runs = &line_calc.line_style_runs;
+        &runs[(self.first_part_lines as usize).min(runs.len())..]
+    }
+}
+
 /// Manages dialogue continuation (MORE/CONT'D) logic
 pub struct ContinuationManager<'a> {
     config: &'a PageConfig,
@@ -34,87 +54,111 @@ impl<'a> ContinuationManager<'a> {
     }
 
     /// Split a dialogue element at a given line
+    ///
+    /// With `orphan_control.prefer_sentence_breaks` the split moves back to
+    /// the last line ending a sentence when one leaves enough lines before it.
     pub fn split_dialogue(
         &self,
         element: &Element,
         line_calc: &LineCalculation,
         split_at_line: u32,
     ) -> SplitResult {
-        let continuation = &self.config.continuation_style;
-
-        // Adjust split point if we need space for MORE marker
-        let actual_split = if continuation.enabled {
-            split_at_line
-        } else {
-            split_at_line
-        };
-
-        // Split the wrapped lines
-        let actual_split = actual_split.min(line_calc.wrapped_lines.len() as u32) as usize;
-
-        let first_part_content: Vec<String> = line_calc.wrapped_lines
-            .iter()
-            .take(actual_split)
-            .cloned()
-            .collect();
-
-        let second_part_content: Vec<String> = line_calc.wrapped_lines
-            .iter()
-            .skip(actual_split)
-            .cloned()
-            .collect();
-
-        // Build continuation markers
-        let (more_marker, contd_prefix) = if continuation.enabled && !second_part_content.is_empty() {
-            let more = Some(continuation.more_marker.clone());
-            let contd = element.character_name.as_ref().map(|name| {
-                format!("{} {}", name.to_uppercase(), continuation.contd_marker)
-            });
-            (more, contd)
-        } else {
-            (None, None)
-        };
-
-        SplitResult {
-            first_part_lines: first_part_content.len() as u32,
-            second_part_lines: second_part_content.len() as u32,
-            first_part_content,
-            second_part_content,
-            more_marker,
-            contd_prefix,
+        // Split the wrapped lines (the caller already reserved room for MORE)
+        let mut actual_split = split_at_line.min(line_calc.wrapped_lines.len() as u32) as usize;
+
+        let orphan = &self.config.orphan_control;
+        if orphan.prefer_sentence_breaks {
+            let min_before = (orphan.dialogue_min_before_split as usize).max(1);
+            if let Some(end) = (min_before..=actual_split).rev().find(|&n| ends_sentence(&line_calc.wrapped_lines.line(n - 1))) {
+                actual_split = end;
+            }
         }
+
+        self.split_lines(element, line_calc, actual_split)
     }
 
-    /// Split an action element (no continuation markers, just raw split)
+    /// Split an action element
+    ///
+    /// The split moves back to the boundary the style's `split_preference`
+    /// asks for, keeping `min_lines_before_split` lines on the page. Carries
+    /// no markers unless the house style configures them for the element's
+    /// type, in which case CONT'D prints on its own line.
     pub fn split_action(
         &self,
+        element: &Element,
         line_calc: &LineCalculation,
         split_at_line: u32,
     ) -> SplitResult {
-        let actual_split = split_at_line.min(line_calc.wrapped_lines.len() as u32) as usize;
+        let style = self.config.resolved_style(element);
+        let actual_split = preferred_split(
+            element,
+            &line_calc.wrapped_lines,
+            split_at_line.min(line_calc.wrapped_lines.len() as u32) as usize,
+            style.min_lines_before_split as usize,
+            style.split_preference,
+        );
+
+        self.split_lines(element, line_calc, actual_split)
+    }
 
-        let first_part_content: Vec<String> = line_calc.wrapped_lines
-            .iter()
-            .take(actual_split)
-            .cloned()
-            .collect();
+    /// Split an element at exactly `split_at_line`, for a break pinned with
+    /// `PageConfig::forced_breaks`
+    ///
+    /// Markers are the ones `split_dialogue` or `split_action` would give it.
+    pub fn split_exact(&self, element: &Element, line_calc: &LineCalculation, split_at_line: u32) -> SplitResult {
+        self.split_lines(element, line_calc, split_at_line.min(line_calc.wrapped_lines.len() as u32) as usize)
+    }
 
-        let second_part_content: Vec<String> = line_calc.wrapped_lines
-            .iter()
-            .skip(actual_split)
-            .cloned()
-            .collect();
+    /// Split the wrapped lines after `actual_split`, with the markers for the
+    /// element's type
+    fn split_lines(&self, element: &Element, line_calc: &LineCalculation, actual_split: usize) -> SplitResult {
+        let first_part_lines = actual_split.min(line_calc.wrapped_lines.len());
+        let second_part_lines = line_calc.wrapped_lines.len() - first_part_lines;
+
+        // Build continuation markers; speech continues under the speaker's name
+        let (more_marker, contd_prefix) = if second_part_lines == 0 {
+            (None, None)
+        } else if matches!(element.element_type, ElementType::Dialogue | ElementType::Lyrics) {
+            let contd = element
+                .character_name
+                .as_deref()
+                .and_then(|name| self.contd_prefix_for(element.element_type, Some(name)));
+            (self.markers(element.element_type).more_marker, contd)
+        } else {
+            let markers = self.markers(element.element_type);
+            (markers.more_marker, self.contd_prefix_for(element.element_type, None))
+        };
 
         SplitResult {
-            first_part_lines: first_part_content.len() as u32,
-            second_part_lines: second_part_content.len() as u32,
-            first_part_content,
-            second_part_content,
-            more_marker: None,
-            contd_prefix: None,
+            first_part_lines: first_part_lines as u32,
+            second_part_lines: second_part_lines as u32,
+            more_marker,
+            contd_prefix,
         }
     }
 
+    /// Cue re-emitted at the top of the next page, e.g. "JOHN (CONT'D)"
+    pub fn contd_prefix(&self, character_name: &str) -> Option<String> {
+        self.contd_prefix_for(ElementType::Dialogue, Some(character_name))
+    }
+
+    /// Line opening the next page for a split element of this type
+    ///
+    /// Speech puts the marker after the character name; without a name the
+    /// marker stands alone.
+    pub fn contd_prefix_for(&self, element_type: ElementType, character_name: Option<&str>) -> Option<String> {
+        let marker = self.markers(element_type).contd_marker?;
+        Some(match character_name {
+            Some(name) => format!("{} {}", name.to_uppercase(), marker),
+            None => marker,
+        })
+    }
+
+    /// Resolved continuation markers for an element type
+    pub fn markers(&self, element_type: ElementType) -> TypeContinuationStyle {
+        self.config.continuation_style.for_type(element_type)
+    }
+
     /// Check if continuation markers are enabled
     pub fn is_enabled(&self) -> bool {
         self.config.continuation_style.enabled
@@ -131,10 +175,51 @@ impl<'a> ContinuationManager<'a> {
     }
 }
 
+/// Latest split at or before `split` on the preferred boundary that keeps
+/// `min_before` lines, falling back along the preference order
+fn preferred_split(
+    element: &Element,
+    wrapped: &WrappedLines,
+    split: usize,
+    min_before: usize,
+    preference: SplitPreference,
+) -> usize {
+    if preference == SplitPreference::AnyLine || split >= wrapped.len() {
+        return split;
+    }
+    let candidates = || (min_before.max(1)..=split).rev();
+
+    // A paragraph ends where the source has a newline between two wrapped lines
+    let paragraph = (preference == SplitPreference::ParagraphBreak)
+        .then(|| {
+            let source: Vec<char> = element.content.chars().collect();
+            let offsets = line_start_offsets(&element.content, wrapped);
+            candidates().find(|&n| {
+                let (start, end) = (offsets[n - 1] as usize, offsets[n] as usize);
+                source.get(start..end).is_some_and(|text| text.contains(&'\n'))
+            })
+        })
+        .flatten();
+
+    paragraph
+        .or_else(|| candidates().find(|&n| ends_sentence(&wrapped.line(n - 1))))
+        .unwrap_or(split)
+}
+
+/// Whether a wrapped line ends a sentence, ignoring closing quotes and brackets
+///
+/// Arabic question marks and full stops end sentences too.
+fn ends_sentence(line: &str) -> bool {
+    line.trim_end()
+        .trim_end_matches(['"', '\'', ')', ']', '\u{201d}', '\u{2019}'])
+        .ends_with(['.', '?', '!', '\u{061f}', '\u{06d4}'])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{ElementId, ElementType};
+    use crate::types::{ElementId, ElementStyleOverride, ElementType};
+    use crate::layout::LineCalculator;
 
     fn make_config() -> PageConfig {
         PageConfig::feature_film()
@@ -148,6 +233,11 @@ mod tests {
             character_name: Some(character.to_string()),
             dual_dialogue_position: None,
             force_page_break_after: false,
+            style_override: None,
+            scene_number: None,
+            no_break_ranges: Vec::new(),
+            style_runs: Vec::new(),
+            custom_type: None,
         }
     }
 
@@ -163,21 +253,137 @@ mod tests {
             space_before: 0,
             space_after: 0,
             total_lines: 3,
-            wrapped_lines: vec![
-                "Line one.".to_string(),
-                "Line two.".to_string(),
-                "Line three.".to_string(),
-            ],
+            wrapped_lines: ["Line one.", "Line two.", "Line three."].into_iter().collect(),
+            line_style_runs: Vec::new(),
         };
 
         let result = mgr.split_dialogue(&element, &line_calc, 2);
 
         assert_eq!(result.first_part_lines, 2);
         assert_eq!(result.second_part_lines, 1);
+        assert_eq!(result.second_part_content(&line_calc).collect::<Vec<_>>(), vec!["Line three."]);
         assert_eq!(result.more_marker, Some("(MORE)".to_string()));
         assert_eq!(result.contd_prefix, Some("JOHN (CONT'D)".to_string()));
     }
 
+    #[test]
+    fn test_split_dialogue_prefers_sentence_end() {
+        let mut config = make_config();
+        config.orphan_control.prefer_sentence_breaks = true;
+        let mgr = ContinuationManager::new(&config);
+
+        let wrapped = ["I told you.", "Then you went", "and did it", "anyway, so now", "we wait."];
+        let line_calc = LineCalculation {
+            content_lines: 5,
+            space_before: 0,
+            space_after: 0,
+            total_lines: 5,
+            wrapped_lines: wrapped.iter().collect(),
+            line_style_runs: Vec::new(),
+        };
+        let element = make_dialogue(&wrapped.join(" "), "JOHN");
+
+        // Only one line ends a sentence before the split, below the two-line minimum
+        assert_eq!(mgr.split_dialogue(&element, &line_calc, 4).first_part_lines, 4);
+
+        config.orphan_control.dialogue_min_before_split = 1;
+        let mgr = ContinuationManager::new(&config);
+        let result = mgr.split_dialogue(&element, &line_calc, 4);
+        assert_eq!(result.first_part_lines, 1);
+        assert_eq!(result.second_part_lines, 4);
+        assert_eq!(result.more_marker.as_deref(), Some("(MORE)"));
+
+        assert!(ends_sentence("Really?\")"));
+        assert!(!ends_sentence("and then --"));
+        assert!(ends_sentence("\u{0647}\u{0644}\u{061f}"));
+    }
+
+    #[test]
+    fn test_split_action_preferences() {
+        let config = make_config();
+        let mgr = ContinuationManager::new(&config);
+
+        let wrapped = ["First line here.", "Second line ends.", "Third line goes", "on and on", "and on."];
+        let line_calc = LineCalculation {
+            content_lines: 5,
+            space_before: 0,
+            space_after: 0,
+            total_lines: 5,
+            wrapped_lines: wrapped.iter().collect(),
+            line_style_runs: Vec::new(),
+        };
+        let content = "First line here.\nSecond line ends. Third line goes on and on\nand on.";
+        let split = |preference: SplitPreference, min_before: u8| {
+            let style = ElementStyleOverride {
+                split_preference: Some(preference),
+                min_lines_before_split: Some(min_before),
+                ..Default::default()
+            };
+            let element = Element::new("a", ElementType::Action, content).with_style_override(style);
+            mgr.split_action(&element, &line_calc, 3).first_part_lines
+        };
+
+        // The paragraph break after line one, else the sentence end after line two
+        assert_eq!(split(SplitPreference::ParagraphBreak, 1), 1);
+        assert_eq!(split(SplitPreference::ParagraphBreak, 2), 2);
+        assert_eq!(split(SplitPreference::SentenceEnd, 1), 2);
+        assert_eq!(split(SplitPreference::AnyLine, 1), 3);
+        assert_eq!(split(SplitPreference::SentenceEnd, 3), 3);
+    }
+
+    #[test]
+    fn test_split_carries_style_runs() {
+        let config = make_config();
+        let mgr = ContinuationManager::new(&config);
+
+        // "Stop" is italic across the split, "now" bold on the far side
+        let italic = StyleRun { start: 11, end: 15, italic: true, ..Default::default() };
+        let bold = StyleRun { start: 16, end: 19, bold: true, ..Default::default() };
+        let element = make_dialogue("I said it.\nStop\nnow.\nPlease.", "JOHN").with_style_run(italic).with_style_run(bold);
+        let line_calc = LineCalculator::new(&config).calculate(&element);
+
+        let result = mgr.split_dialogue(&element, &line_calc, 2);
+
+        assert_eq!(result.first_part_style_runs(&line_calc), [vec![], vec![StyleRun { start: 0, end: 4, ..italic }]]);
+        assert_eq!(result.second_part_style_runs(&line_calc), [vec![StyleRun { start: 0, end: 3, ..bold }], vec![]]);
+    }
+
+    #[test]
+    fn test_per_type_markers() {
+        let mut config = make_config();
+        config.continuation_style.by_type.insert(
+            ElementType::Action,
+            TypeContinuationStyle { contd_marker: Some("(CONTINUING)".to_string()), ..Default::default() },
+        );
+        config.continuation_style.by_type.insert(
+            ElementType::Dialogue,
+            TypeContinuationStyle {
+                more_marker: Some("(MORE...)".to_string()),
+                contd_marker: Some("(CONT)".to_string()),
+                ..Default::default()
+            },
+        );
+        let mgr = ContinuationManager::new(&config);
+
+        let line_calc = LineCalculation {
+            content_lines: 2,
+            space_before: 0,
+            space_after: 0,
+            total_lines: 2,
+            wrapped_lines: ["One.", "Two."].into_iter().collect(),
+            line_style_runs: Vec::new(),
+        };
+
+        let action = Element::new("a", ElementType::Action, "One. Two.");
+        let result = mgr.split_action(&action, &line_calc, 1);
+        assert!(result.more_marker.is_none());
+        assert_eq!(result.contd_prefix.as_deref(), Some("(CONTINUING)"));
+
+        let result = mgr.split_dialogue(&make_dialogue("One. Two.", "Ann"), &line_calc, 1);
+        assert_eq!(result.more_marker.as_deref(), Some("(MORE...)"));
+        assert_eq!(result.contd_prefix.as_deref(), Some("ANN (CONT)"));
+    }
+
     #[test]
     fn test_split_with_empty_second_part() {
         let config = make_config();
@@ -190,7 +396,8 @@ mod tests {
             space_before: 0,
             space_after: 0,
             total_lines: 1,
-            wrapped_lines: vec!["Short line".to_string()],
+            wrapped_lines: ["Short line"].into_iter().collect(),
+            line_style_runs: Vec::new(),
         };
 
         let result = mgr.split_dialogue(&element, &line_calc, 1);
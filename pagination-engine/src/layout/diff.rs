@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{Element, ElementDiff, MovedElement, Page, PageDiff, PageIdentifier, PaginationResult};
+use super::simulate::page_start;
+
+/// Summarize how an edit moved pages, for notices like "your edit moved 14
+/// pages"
+///
+/// Pages are matched by identifier. Elements are compared by the page they
+/// start on, so one that only grows onto a further page doesn't count as
+/// moved; elements in just one of the paginations are left out.
+pub fn diff_results(old: &PaginationResult, new: &PaginationResult) -> PageDiff {
+    let old_pages: HashMap<&PageIdentifier, &Page> = old.pages.iter().map(|page| (&page.identifier, page)).collect();
+    let new_ids: HashSet<&PageIdentifier> = new.pages.iter().map(|page| &page.identifier).collect();
+
+    let mut seen = HashSet::new();
+    let mut moved_elements = Vec::new();
+    for page_element in new.pages.iter().flat_map(|page| &page.elements) {
+        let id = page_element.element_id.0.as_str();
+        if !seen.insert(id) {
+            continue;
+        }
+        if let (Some(from), Some(to)) = (old.get_page_for_element(id), new.get_page_for_element(id)) {
+            if from != to {
+                moved_elements.push(MovedElement {
+                    element_id: page_element.element_id.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+    }
+
+    let shifted_pages = new
+        .pages
+        .iter()
+        .filter_map(|page| {
+            let before = old_pages.get(&page.identifier)?;
+            (page_start(before) != page_start(page)).then(|| page.identifier.clone())
+        })
+        .collect();
+
+    PageDiff {
+        moved_elements,
+        shifted_pages,
+        added_pages: new.pages.iter().filter(|p| !old_pages.contains_key(&p.identifier)).map(|p| p.identifier.clone()).collect(),
+        removed_pages: old.pages.iter().filter(|p| !new_ids.contains(&p.identifier)).map(|p| p.identifier.clone()).collect(),
+        page_count_change: new.pages.len() as i64 - old.pages.len() as i64,
+    }
+}
+
+/// Classify how `new` differs from `old`, by element ID and content hash
+///
+/// The one diff both consumers need: `ElementDiff::edits` replays it on a
+/// `Paginator` and `ElementDiff::changed` lists what `mark_revisions`
+/// flags. Moves are found by keeping the longest run of surviving elements
+/// still in their old order; the rest of the survivors moved.
+pub fn diff_elements(old: &[Element], new: &[Element]) -> ElementDiff {
+    let old_index: HashMap<&str, usize> = old.iter().enumerate().map(|(i, e)| (e.id.0.as_str(), i)).collect();
+    let new_ids: HashSet<&str> = new.iter().map(|e| e.id.0.as_str()).collect();
+
+    let mut diff = ElementDiff {
+        removed: old.iter().filter(|e| !new_ids.contains(e.id.0.as_str())).map(|e| e.id.clone()).collect(),
+        ..Default::default()
+    };
+
+    // Survivors in new order, with their old positions
+    let mut survivors: Vec<(&Element, usize)> = Vec::new();
+    for element in new {
+        match old_index.get(element.id.0.as_str()) {
+            Some(&index) => {
+                if old[index].content_hash() != element.content_hash() {
+                    diff.modified.push(element.id.clone());
+                }
+                survivors.push((element, index));
+            }
+            None => diff.added.push(element.id.clone()),
+        }
+    }
+
+    let positions: Vec<usize> = survivors.iter().map(|&(_, index)| index).collect();
+    let in_order = longest_increasing(&positions);
+    diff.moved = survivors
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !in_order.contains(i))
+        .map(|(_, (element, _))| element.id.clone())
+        .collect();
+
+    diff
+}
+
+/// Indices of a longest strictly increasing subsequence of `values`
+fn longest_increasing(values: &[usize]) -> HashSet<usize> {
+    // tails[k]: index of the smallest value ending an increasing run of length k + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; values.len()];
+    for (i, &value) in values.iter().enumerate() {
+        let length = tails.partition_point(|&tail| values[tail] < value);
+        previous[i] = length.checked_sub(1).map(|k| tails[k]);
+        if length == tails.len() {
+            tails.push(i);
+        } else {
+            tails[length] = i;
+        }
+    }
+
+    let mut kept = HashSet::new();
+    let mut cursor = tails.last().copied();
+    while let Some(i) = cursor {
+        kept.insert(i);
+        cursor = previous[i];
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::{Element, ElementId, ElementType, PageConfig};
+
+    fn script(first_lines: usize) -> Vec<Element> {
+        let mut elements = vec![Element::new("a", ElementType::Action, "Opening.\n".repeat(first_lines).trim_end())];
+        elements.extend((0..6).map(|i| {
+            Element::new(format!("s{}", i), ElementType::Action, "Something happens.\n".repeat(12).trim_end())
+        }));
+        elements
+    }
+
+    #[test]
+    fn test_diff_results() {
+        let config = PageConfig::feature_film();
+        let old = paginate(&script(5), &config);
+        let new = paginate(&script(40), &config);
+
+        let diff = diff_results(&old, &new);
+        assert_eq!(diff.page_count_change, new.pages.len() as i64 - old.pages.len() as i64);
+        assert!(diff.page_count_change > 0);
+        assert_eq!(diff.added_pages.len() as i64, diff.page_count_change);
+        assert!(diff.removed_pages.is_empty());
+        // Growing the opening only pushes later elements back
+        assert!(!diff.moved_elements.is_empty());
+        assert!(diff.moved_elements.iter().all(|moved| moved.to.sort_key() > moved.from.sort_key()));
+        assert!(diff.moved_elements.iter().all(|moved| moved.element_id != ElementId::new("a")));
+        assert!(!diff.shifted_pages.contains(&PageIdentifier::Sequential(1)));
+
+        let same = diff_results(&old, &old);
+        assert!(same.moved_elements.is_empty() && same.shifted_pages.is_empty() && same.page_count_change == 0);
+    }
+
+    #[test]
+    fn test_diff_elements() {
+        let old: Vec<Element> =
+            (0..6).map(|i| Element::new(i.to_string(), ElementType::Action, format!("Paragraph {}.", i))).collect();
+        let mut new = old.clone();
+        new.remove(1);
+        new[2].content = "Rewritten.".to_string();
+        let moved = new.remove(0);
+        new.push(moved);
+        new.insert(1, Element::new("new", ElementType::Action, "Inserted."));
+        // new: 2, new, 3*, 4, 5, 0
+
+        let diff = diff_elements(&old, &new);
+        assert_eq!(diff.added, vec![ElementId::new("new")]);
+        assert_eq!(diff.removed, vec![ElementId::new("1")]);
+        assert_eq!(diff.modified, vec![ElementId::new("3")]);
+        assert_eq!(diff.moved, vec![ElementId::new("0")]);
+        assert_eq!(diff.changed().len(), 3);
+
+        // Replaying the edits reproduces the new list
+        let mut paginator = crate::layout::Paginator::new(PageConfig::feature_film());
+        paginator.set_elements(old.clone());
+        assert!(paginator.apply_edits(diff.edits(&new)).is_empty());
+        let hashes = |elements: &[Element]| elements.iter().map(|e| e.content_hash()).collect::<Vec<_>>();
+        assert_eq!(hashes(paginator.elements()), hashes(&new));
+
+        assert!(diff_elements(&old, &old).is_empty());
+    }
+}
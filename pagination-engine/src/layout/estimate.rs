@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+
+use crate::types::{Element, ElementType, PageConfig};
+use super::page_breaker::{dual_columns, parse_page_range};
+use super::LineCalculator;
+
+/// Approximate page count with simplified break decisions
+///
+/// Moves unsplittable elements and keep-with-next runs (scene heading plus
+/// what follows, character cue plus dialogue) whole to the next page like the
+/// full pass, but lets splittable elements flow across pages without orphan
+/// limits or (MORE)/(CONT'D) lines. Typically within one page of `paginate`
+/// for feature-length scripts; intended for live status displays, with the
+/// exact pass run once editing settles. Breaks in `PageConfig::forced_breaks`
+/// count as falling after their element, split lines aside.
+pub fn estimate_page_count(elements: &[Element], config: &PageConfig) -> u32 {
+    let calc = LineCalculator::new(config);
+    let pinned: HashSet<&str> = config.forced_breaks.iter().map(|pinned| pinned.after_element.0.as_str()).collect();
+    let header_lines = if config.header_footer.text().is_some() { config.header_footer.header_lines } else { 0 };
+    let lines_per_page = config.lines_per_page.saturating_sub(header_lines).max(1) as u32;
+
+    // (space_before, content lines incl. space after) of each visible element
+    let visible: Vec<Option<(u32, u32)>> = elements
+        .iter()
+        .map(|element| {
+            (element.element_type != ElementType::PageBreak && config.is_visible(element.element_type))
+                .then(|| {
+                    let lines = calc.calculate(element);
+                    (lines.space_before as u32, lines.total_lines)
+                })
+        })
+        .collect();
+
+    let mut pages = 0;
+    let mut used = 0;
+    // End of the dual dialogue block being skipped, its lines already counted
+    let mut block_end = 0;
+
+    for (idx, element) in elements.iter().enumerate() {
+        // A dual dialogue block counts once, as one unsplittable element as tall as its taller column
+        let (left, right) = if idx < block_end { (0, 0) } else { dual_columns(&elements[idx..]) };
+        let entry = if idx < block_end {
+            None
+        } else if right > 0 {
+            block_end = idx + left + right;
+            let height = [idx..idx + left, idx + left..block_end]
+                .map(|range| column_height(&visible[range]))
+                .into_iter()
+                .max()
+                .unwrap_or(0);
+            visible[idx].map(|(space_before, _)| (space_before, height))
+        } else {
+            visible[idx]
+        };
+        let next = if right > 0 { block_end } else { idx + 1 };
+
+        if let Some((space_before, total)) = entry {
+            let style = config.resolved_style(element);
+            let keep_with_next = style.keep_with_next && right == 0;
+            let can_split = style.can_split && right == 0;
+            let mut needed = if used == 0 { total } else { space_before + total };
+
+            // An audio cell sits beside its video cell, adding only the lines it overhangs
+            let after_video = idx > 0 && elements[idx - 1].element_type == ElementType::AvVideo;
+            if element.element_type == ElementType::AvAudio && after_video {
+                let video_total = visible[idx - 1].map_or(0, |(_, lines)| lines);
+                needed = total.saturating_sub(video_total);
+            }
+
+            let following: u32 = if keep_with_next && element.element_type == ElementType::SceneHeading {
+                scene_opening_lines(config, &elements[next..], &visible[next..])
+            } else if keep_with_next {
+                visible[next..]
+                    .iter()
+                    .flatten()
+                    .take(style.keep_with_next_lines as usize)
+                    .enumerate()
+                    .map(|(i, &(space, lines))| if i == 0 { lines } else { space + lines })
+                    .sum()
+            } else {
+                0
+            };
+
+            // A transition can't open a page, so it counts against this element's page
+            let bound = visible[next..]
+                .iter()
+                .zip(&elements[next..])
+                .find_map(|(lines, next)| lines.map(|lines| (lines, next)))
+                .filter(|(_, next)| config.resolved_style(next).keep_with_previous)
+                .map_or(0, |((space, lines), _)| space + lines);
+            let following = following.max(bound);
+
+            let fits = used + needed + following <= lines_per_page;
+            if !fits && used > 0 && !(can_split && used + needed > lines_per_page) {
+                pages += 1;
+                used = total;
+            } else {
+                used += needed;
+            }
+
+            while used > lines_per_page {
+                pages += 1;
+                used -= lines_per_page;
+            }
+        }
+
+        if element.element_type == ElementType::OmittedPage {
+            let omitted = parse_page_range(&element.content).map_or(1, |(first, last)| last.saturating_sub(first) + 1);
+            pages += omitted + u32::from(used > 0);
+            used = 0;
+            continue;
+        }
+
+        let act_ends = element.element_type == ElementType::ActBreak && config.act_breaks.new_page_after;
+        let forced = element.element_type == ElementType::PageBreak
+            || element.force_page_break_after
+            || pinned.contains(element.id.0.as_str())
+            || act_ends;
+        if forced && used > 0 {
+            pages += 1;
+            used = 0;
+        }
+    }
+
+    if used > 0 {
+        pages += 1;
+    }
+
+    pages
+}
+
+/// Lines a scene heading keeps under it: `scene_heading_min_following`
+/// content lines, splittable elements giving up no more than they must
+fn scene_opening_lines(config: &PageConfig, following: &[Element], visible: &[Option<(u32, u32)>]) -> u32 {
+    let needed = config.orphan_control.scene_heading_min_following as u32;
+    let mut total = 0;
+    let mut content = 0;
+
+    for (element, &(space, lines)) in following.iter().zip(visible).filter_map(|(e, v)| v.as_ref().map(|v| (e, v))) {
+        if content >= needed {
+            break;
+        }
+        let style = config.resolved_style(element);
+        let take = if style.can_split { (needed - content).max(style.min_lines_before_split as u32).min(lines) } else { lines };
+        total += space + take;
+        content += take;
+    }
+
+    total
+}
+
+/// Lines of one dual dialogue column given each element's (space_before, total)
+fn column_height(column: &[Option<(u32, u32)>]) -> u32 {
+    column
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, &(space, lines))| if i == 0 { lines } else { space + lines })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+
+    #[test]
+    fn test_estimate_close_to_exact() {
+        let config = PageConfig::feature_film();
+        let elements: Vec<Element> = (0..80)
+            .flat_map(|i| {
+                vec![
+                    Element::new(format!("h{}", i), ElementType::SceneHeading, format!("INT. ROOM {} - DAY", i)),
+                    Element::new(format!("a{}", i), ElementType::Action, "Something happens here.\n".repeat(4)),
+                    Element::new(format!("c{}", i), ElementType::Character, "SARAH"),
+                    Element::new(format!("d{}", i), ElementType::Dialogue, "A few words of dialogue that wrap.")
+                        .with_character_name("SARAH"),
+                ]
+            })
+            .collect();
+
+        let estimate = estimate_page_count(&elements, &config);
+        let exact = paginate(&elements, &config).page_count();
+
+        assert!(estimate.abs_diff(exact) <= 1, "estimate {} vs exact {}", estimate, exact);
+    }
+
+    #[test]
+    fn test_estimate_forced_breaks_and_empty() {
+        let config = PageConfig::feature_film();
+        assert_eq!(estimate_page_count(&[], &config), 0);
+
+        let elements = vec![
+            Element::new("1", ElementType::Action, "Page one."),
+            Element::new("2", ElementType::PageBreak, ""),
+            Element::new("3", ElementType::Action, "Page two."),
+        ];
+        assert_eq!(estimate_page_count(&elements, &config), 2);
+    }
+}
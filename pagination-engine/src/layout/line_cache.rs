@@ -0,0 +1,264 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::types::{Element, PageConfig};
+use super::{LineCalculation, LineCalculator};
+
+/// Memoized line calculations keyed by content, element type and effective style
+///
+/// Entries not used during a run are dropped when the run ends, so the cache
+/// tracks the current document instead of growing with every edit.
+#[derive(Debug, Default)]
+pub struct LineCache {
+    entries: HashMap<u64, (Arc<LineCalculation>, u64)>,
+    generation: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl LineCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache key covering everything `LineCalculator::calculate` depends on
+    pub fn key(config: &PageConfig, element: &Element) -> u64 {
+        let style = config.resolved_style(element);
+        let mut hasher = DefaultHasher::new();
+
+        element.content.hash(&mut hasher);
+        element.element_type.hash(&mut hasher);
+        element.dual_column().hash(&mut hasher);
+        style.max_chars_per_line.hash(&mut hasher);
+        style.force_uppercase.hash(&mut hasher);
+        style.space_before.hash(&mut hasher);
+        style.space_after.hash(&mut hasher);
+        style.line_spacing.to_bits().hash(&mut hasher);
+        config.is_visible(element.element_type).hash(&mut hasher);
+        config.hyphenation.enabled.hash(&mut hasher);
+        config.hyphenation.language.hash(&mut hasher);
+        config.hyphenation.dictionary.hash(&mut hasher);
+        config.east_asian_width.hash(&mut hasher);
+        config.dual_dialogue.max_chars_per_line.hash(&mut hasher);
+        config.act_breaks.end_of_act_text.hash(&mut hasher);
+        config.char_width_pt.to_bits().hash(&mut hasher);
+        if let Some(metrics) = &config.font_metrics {
+            metrics.default_width_pt.to_bits().hash(&mut hasher);
+            let mut widths: Vec<(char, u64)> = metrics.widths.iter().map(|(&c, width)| (c, width.to_bits())).collect();
+            widths.sort_unstable();
+            widths.hash(&mut hasher);
+        }
+        element.no_break_ranges.hash(&mut hasher);
+        element.style_runs.hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    /// Return the cached calculation for an element, computing it on a miss
+    pub fn get_or_calculate(&mut self, calc: &LineCalculator, element: &Element) -> Arc<LineCalculation> {
+        let key = Self::key(calc.config(), element);
+        let generation = self.generation;
+
+        if let Some((lines, used)) = self.entries.get_mut(&key) {
+            *used = generation;
+            self.hits += 1;
+            return Arc::clone(lines);
+        }
+
+        self.misses += 1;
+        let lines = Arc::new(calc.calculate(element));
+        self.entries.insert(key, (Arc::clone(&lines), generation));
+        lines
+    }
+
+    /// Look up or compute calculations for a run of elements, in order
+    ///
+    /// With the `rayon` feature, cache misses are wrapped in parallel; the
+    /// returned table is identical either way.
+    #[cfg(not(feature = "rayon"))]
+    pub fn get_or_calculate_all(&mut self, calc: &LineCalculator, elements: &[Element]) -> Vec<Arc<LineCalculation>> {
+        elements.iter().map(|element| self.get_or_calculate(calc, element)).collect()
+    }
+
+    /// Look up or compute calculations for a run of elements, in order
+    ///
+    /// With the `rayon` feature, cache misses are wrapped in parallel; the
+    /// returned table is identical either way.
+    #[cfg(feature = "rayon")]
+    pub fn get_or_calculate_all(&mut self, calc: &LineCalculator, elements: &[Element]) -> Vec<Arc<LineCalculation>> {
+        use rayon::prelude::*;
+        use std::collections::HashSet;
+
+        let generation = self.generation;
+        let keys: Vec<u64> = elements.par_iter().map(|element| Self::key(calc.config(), element)).collect();
+
+        // Resolve hits sequentially so hit/miss counts match the serial path
+        let mut table: Vec<Option<Arc<LineCalculation>>> = Vec::with_capacity(elements.len());
+        let mut missing: Vec<usize> = Vec::new();
+        let mut pending: HashSet<u64> = HashSet::new();
+        for (idx, key) in keys.iter().enumerate() {
+            match self.entries.get_mut(key) {
+                Some((lines, used)) => {
+                    *used = generation;
+                    self.hits += 1;
+                    table.push(Some(Arc::clone(lines)));
+                }
+                None if pending.contains(key) => {
+                    self.hits += 1;
+                    table.push(None);
+                }
+                None => {
+                    self.misses += 1;
+                    pending.insert(*key);
+                    missing.push(idx);
+                    table.push(None);
+                }
+            }
+        }
+
+        let computed: Vec<Arc<LineCalculation>> = missing
+            .par_iter()
+            .map(|&idx| Arc::new(calc.calculate(&elements[idx])))
+            .collect();
+        for (&idx, lines) in missing.iter().zip(computed) {
+            self.entries.insert(keys[idx], (lines, generation));
+        }
+
+        table
+            .into_iter()
+            .zip(&keys)
+            .map(|(lines, key)| lines.unwrap_or_else(|| Arc::clone(&self.entries[key].0)))
+            .collect()
+    }
+
+    /// Start a pagination run
+    pub fn begin_run(&mut self) {
+        self.generation += 1;
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Finish a run, evicting entries the run did not use
+    pub fn end_run(&mut self) {
+        let generation = self.generation;
+        self.entries.retain(|_, (_, used)| *used == generation);
+    }
+
+    /// Drop all entries
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Cache hits since the current run began
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Cache misses since the current run began
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ElementStyleOverride, ElementType};
+
+    #[test]
+    fn test_cache_hit_for_same_content() {
+        let config = PageConfig::feature_film();
+        let calc = LineCalculator::new(&config);
+        let mut cache = LineCache::new();
+
+        cache.begin_run();
+        let a = Element::new("1", ElementType::Action, "A busy office.");
+        let b = Element::new("2", ElementType::Action, "A busy office.");
+        cache.get_or_calculate(&calc, &a);
+        cache.get_or_calculate(&calc, &b);
+
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn test_batch_matches_single_lookups() {
+        let config = PageConfig::feature_film();
+        let calc = LineCalculator::new(&config);
+        let elements = vec![
+            Element::new("1", ElementType::Action, "A busy office."),
+            Element::new("2", ElementType::Dialogue, "Hello?"),
+            Element::new("3", ElementType::Action, "A busy office."),
+        ];
+        let mut cache = LineCache::new();
+
+        cache.begin_run();
+        let table = cache.get_or_calculate_all(&calc, &elements);
+
+        assert_eq!(table.len(), 3);
+        assert!(Arc::ptr_eq(&table[0], &table[2]));
+        assert_eq!(table[1].wrapped_lines, vec!["Hello?".to_string()]);
+        assert_eq!(cache.misses(), 2);
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn test_style_changes_key() {
+        let config = PageConfig::feature_film();
+        let action = Element::new("1", ElementType::Action, "Same text.");
+        let dialogue = Element::new("1", ElementType::Dialogue, "Same text.");
+        let narrow = action.clone().with_style_override(ElementStyleOverride {
+            max_chars_per_line: Some(20),
+            ..Default::default()
+        });
+
+        assert_ne!(LineCache::key(&config, &action), LineCache::key(&config, &dialogue));
+        assert_ne!(LineCache::key(&config, &action), LineCache::key(&config, &narrow));
+    }
+
+    #[test]
+    fn test_force_uppercase_changes_key() {
+        let config = PageConfig::feature_film();
+        let calc = LineCalculator::new(&config);
+        let mut cache = LineCache::new();
+
+        // "ß" uppercases to "SS", so the shouting twin wraps onto two lines
+        let plain = Element::new("1", ElementType::Action, "ß".repeat(40));
+        let shouted = Element::new("2", ElementType::Action, "ß".repeat(40))
+            .with_style_override(ElementStyleOverride { force_uppercase: Some(true), ..Default::default() });
+
+        let table = cache.get_or_calculate_all(&calc, &[plain, shouted.clone()]);
+        assert_eq!(table[0].content_lines, 1);
+        assert_eq!(table[1].content_lines, calc.calculate(&shouted).content_lines);
+        assert_eq!(table[1].content_lines, 2);
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn test_unused_entries_evicted() {
+        let config = PageConfig::feature_film();
+        let calc = LineCalculator::new(&config);
+        let mut cache = LineCache::new();
+
+        cache.begin_run();
+        cache.get_or_calculate(&calc, &Element::new("1", ElementType::Action, "Old text."));
+        cache.end_run();
+        assert_eq!(cache.len(), 1);
+
+        cache.begin_run();
+        cache.get_or_calculate(&calc, &Element::new("1", ElementType::Action, "New text."));
+        cache.end_run();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+}
@@ -1,4 +1,13 @@
-use crate::types::{Element, PageConfig};
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::types::{Element, ElementType, PageConfig, StyleRun};
+use super::normalize_text;
+use crate::utils::{
+    dictionary_points, hyphenation_points, soft_hyphen_points, is_breaking_space, visible_width_by, Markup,
+    NO_BREAK_SPACE, SOFT_HYPHEN,
+};
+use unicode_width::UnicodeWidthChar;
 
 /// Result of calculating lines for an element
 #[derive(Debug, Clone)]
@@ -16,7 +25,249 @@ pub struct LineCalculation {
     pub total_lines: u32,
 
     /// For split calculations: content of each wrapped line
-    pub wrapped_lines: Vec<String>,
+    pub wrapped_lines: WrappedLines,
+
+    /// Element's style runs on each wrapped line (empty when it has none)
+    pub line_style_runs: Vec<Vec<StyleRun>>,
+}
+
+/// Wrapped lines of an element's printed text
+///
+/// Holds one copy of the text wrapping worked on and each line's byte range
+/// in it, so wrapping allocates once per element rather than once per line.
+/// A line is only materialized when read, and only copied when wrapping
+/// added a hyphen at its end.
+#[derive(Debug, Clone, Default)]
+pub struct WrappedLines {
+    text: String,
+    spans: Vec<LineSpan>,
+}
+
+/// One wrapped line: a byte range into `WrappedLines::text`, printed with a
+/// trailing hyphen when wrapping broke a word there
+#[derive(Debug, Clone, Copy)]
+struct LineSpan {
+    start: usize,
+    end: usize,
+    hyphen: bool,
+}
+
+impl WrappedLines {
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Printed text of line `index`, or None past the last line
+    pub fn get(&self, index: usize) -> Option<Cow<'_, str>> {
+        let span = self.spans.get(index)?;
+        let text = &self.text[span.start..span.end];
+        Some(if span.hyphen { Cow::Owned(format!("{}-", text)) } else { Cow::Borrowed(text) })
+    }
+
+    /// Printed text of line `index`
+    ///
+    /// Panics past the last line, like indexing a slice.
+    pub fn line(&self, index: usize) -> Cow<'_, str> {
+        self.get(index).expect("wrapped line index out of range")
+    }
+
+    /// Printed text of each line, in order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Cow<'_, str>> + ExactSizeIterator + '_ {
+        (0..self.len()).map(|i| self.line(i))
+    }
+
+    /// Lines as owned strings
+    pub fn to_strings(&self) -> Vec<String> {
+        self.iter().map(Cow::into_owned).collect()
+    }
+
+    /// Shorten to `len` lines, or pad with blank lines up to it
+    pub fn resize(&mut self, len: usize) {
+        self.spans.resize(len, LineSpan { start: 0, end: 0, hyphen: false });
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for WrappedLines {
+    fn from_iter<I: IntoIterator<Item = S>>(lines: I) -> Self {
+        let mut wrapped = WrappedLines::default();
+        for line in lines {
+            if !wrapped.text.is_empty() {
+                wrapped.text.push('\n');
+            }
+            let start = wrapped.text.len();
+            wrapped.text.push_str(line.as_ref());
+            wrapped.spans.push(LineSpan { start, end: wrapped.text.len(), hyphen: false });
+        }
+        wrapped
+    }
+}
+
+impl PartialEq for WrappedLines {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for WrappedLines {}
+
+impl<S: AsRef<str>> PartialEq<Vec<S>> for WrappedLines {
+    fn eq(&self, other: &Vec<S>) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(line, expected)| line == expected.as_ref())
+    }
+}
+
+/// "END OF ..." text printed for an act break whose content names the act
+pub(crate) fn end_of_act_text(content: &str) -> String {
+    let name = content.trim();
+    if name.is_empty() {
+        "END OF ACT".to_string()
+    } else if name.to_uppercase().starts_with("END OF") {
+        name.to_string()
+    } else {
+        format!("END OF {}", name)
+    }
+}
+
+/// Monospace cells a character takes: two for East Asian wide characters
+fn cell_width(c: char) -> usize {
+    c.width().unwrap_or(1).max(1)
+}
+
+/// Byte ranges of the words in `text`, which breaking spaces separate
+fn word_ranges(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut start = None;
+    text.char_indices().chain(std::iter::once((text.len(), ' '))).filter_map(move |(i, c)| {
+        if is_breaking_space(c) {
+            start.take().map(|start| (start, i))
+        } else {
+            start.get_or_insert(i);
+            None
+        }
+    })
+}
+
+/// Content with the spaces inside an element's no-break ranges made no-break spaces
+fn bind_no_break_ranges<'c>(content: &'c str, ranges: &[(usize, usize)]) -> Cow<'c, str> {
+    if ranges.is_empty() {
+        return Cow::Borrowed(content);
+    }
+
+    content
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let bound = c == ' ' && ranges.iter().any(|&(start, end)| start <= i && i < end);
+            if bound { NO_BREAK_SPACE } else { c }
+        })
+        .collect()
+}
+
+/// Character offset in `content` where each wrapped line begins
+///
+/// Walks the source alongside the wrapped text, skipping whitespace that
+/// wrapping collapsed, source characters it dropped (a lyric's `~`, a soft
+/// hyphen at a break) and characters it inserted (a hyphen at a break).
+/// Offsets never exceed the content's length, so generated text such as
+/// "SCENE 12 OMITTED" maps to positions within the (shorter) source.
+pub(crate) fn line_start_offsets(content: &str, wrapped_lines: &WrappedLines) -> Vec<u32> {
+    line_sources(content, wrapped_lines).into_iter().map(|(start, _)| start).collect()
+}
+
+/// Style runs falling on each wrapped line, with offsets within the line
+///
+/// Characters wrapping inserted take the styling of the character before
+/// them, so a hyphen at a break stays bold with its word. Empty when there
+/// are no runs.
+pub(crate) fn line_style_runs(content: &str, wrapped_lines: &WrappedLines, runs: &[StyleRun]) -> Vec<Vec<StyleRun>> {
+    if runs.is_empty() {
+        return Vec::new();
+    }
+
+    line_sources(content, wrapped_lines)
+        .into_iter()
+        .map(|(_, sources)| {
+            let mut line_runs = Vec::new();
+            for run in runs {
+                let mut inside = false;
+                let mut open = None;
+                for (i, source) in sources.iter().enumerate() {
+                    if let Some(source) = source {
+                        inside = (run.start..run.end).contains(&(*source as usize));
+                    }
+                    match (inside, open) {
+                        (true, None) => open = Some(i),
+                        (false, Some(start)) => {
+                            line_runs.push(StyleRun { start, end: i, ..*run });
+                            open = None;
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(start) = open {
+                    line_runs.push(StyleRun { start, end: sources.len(), ..*run });
+                }
+            }
+            line_runs.sort_by_key(|run| run.start);
+            line_runs
+        })
+        .collect()
+}
+
+/// Start offset of each wrapped line in `content`, and the source offset of
+/// each of its characters (None for characters wrapping inserted)
+fn line_sources(content: &str, wrapped_lines: &WrappedLines) -> Vec<(u32, Vec<Option<u32>>)> {
+    let source: Vec<char> = content.chars().collect();
+    let mut pos = 0;
+    let mut lines = Vec::with_capacity(wrapped_lines.len());
+
+    for (i, line) in wrapped_lines.iter().enumerate() {
+        // Whitespace at a wrap point, then the paragraph's own newline
+        if i > 0 {
+            while pos < source.len()
+                && source[pos] != '\n'
+                && (source[pos].is_whitespace() || source[pos] == SOFT_HYPHEN)
+            {
+                pos += 1;
+            }
+            if source.get(pos) == Some(&'\n') {
+                pos += 1;
+            }
+        }
+        if !line.is_empty() {
+            while pos < source.len() && source[pos].is_whitespace() {
+                pos += 1;
+            }
+        }
+        let start = pos as u32;
+        let mut sources = Vec::with_capacity(line.len());
+
+        // Forced uppercase means the printed case may differ from the source
+        let same = |s: Option<&char>, c: char| {
+            s.is_some_and(|&s| s == c || s.to_uppercase().eq(c.to_uppercase()))
+        };
+        for c in line.chars() {
+            while source.get(pos).is_some_and(|&s| s != c && s.is_whitespace() && s != '\n') {
+                pos += 1;
+            }
+            if same(source.get(pos), c) {
+                sources.push(Some(pos as u32));
+                pos += 1;
+            } else if same(source.get(pos + 1), c) && source[pos] != '\n' {
+                sources.push(Some(pos as u32 + 1));
+                pos += 2;
+            } else {
+                sources.push(None);
+            }
+        }
+
+        lines.push((start, sources));
+    }
+
+    lines
 }
 
 /// Calculates line counts for screenplay elements
@@ -29,16 +280,64 @@ impl<'a> LineCalculator<'a> {
         Self { config }
     }
 
+    /// The configuration this calculator measures against
+    pub fn config(&self) -> &'a PageConfig {
+        self.config
+    }
+
     /// Calculate how many lines an element requires
     pub fn calculate(&self, element: &Element) -> LineCalculation {
-        let style = self.config.style_for(element.element_type);
+        let style = self.config.resolved_style(element);
+
+        // Non-printing elements are zero-line anchors
+        if !self.config.is_visible(element.element_type) {
+            return LineCalculation {
+                content_lines: 0,
+                space_before: 0,
+                space_after: 0,
+                total_lines: 0,
+                wrapped_lines: WrappedLines::default(),
+                line_style_runs: Vec::new(),
+            };
+        }
 
-        // Get max characters per line for this element type
-        let chars_per_line = style.max_chars_per_line as usize;
+        // Get max characters per line for this element type, narrowed inside a dual dialogue column
+        let mut chars_per_line = style.max_chars_per_line as usize;
+        if element.dual_column().is_some() {
+            chars_per_line = chars_per_line.min(self.config.dual_dialogue.max_chars_per_line as usize);
+        }
+
+        // Measure the text as it prints
+        let bound = bind_no_break_ranges(&element.content, &element.no_break_ranges);
+        let content = normalize_text(&bound, &style);
 
         // Wrap text into lines
-        let wrapped_lines = self.wrap_text(&element.content, chars_per_line);
-        let content_lines = wrapped_lines.len() as u32;
+        let wrapped_lines = match element.element_type {
+            // The Fountain `~` lyric prefix is not printed
+            ElementType::Lyrics => {
+                let content: Vec<&str> = content
+                    .split('\n')
+                    .map(|line| line.strip_prefix('~').unwrap_or(line).trim_start())
+                    .collect();
+                self.wrap_text(content.join("\n"), chars_per_line)
+            }
+            // Content is the scene number of the deleted scene
+            ElementType::OmittedScene => {
+                self.wrap_text(normalize_text(&format!("SCENE {} OMITTED", content), &style).into_owned(), chars_per_line)
+            }
+            // Omitted pages print nothing; they become pages of their own
+            ElementType::OmittedPage => WrappedLines::default(),
+            ElementType::ActBreak if self.config.act_breaks.end_of_act_text => {
+                self.wrap_text(end_of_act_text(&content), chars_per_line)
+            }
+            _ => self.wrap_text(content.into_owned(), chars_per_line),
+        };
+        let mut content_lines = wrapped_lines.len() as u32;
+
+        // Notes in a production draft are boxed: one rule line above and below
+        if element.element_type == ElementType::Note {
+            content_lines += 2;
+        }
 
         // Apply line spacing (for double-spaced formats like multi-cam)
         let spaced_lines = if style.line_spacing > 1.0 {
@@ -50,6 +349,7 @@ impl<'a> LineCalculator<'a> {
         // Calculate space before (only if not at start of page, handled by caller)
         let space_before = style.space_before;
         let space_after = style.space_after;
+        let line_style_runs = line_style_runs(&element.content, &wrapped_lines, &element.style_runs);
 
         LineCalculation {
             content_lines,
@@ -57,6 +357,7 @@ impl<'a> LineCalculator<'a> {
             space_after,
             total_lines: spaced_lines + space_after as u32,
             wrapped_lines,
+            line_style_runs,
         }
     }
 
@@ -71,96 +372,195 @@ impl<'a> LineCalculator<'a> {
         calc
     }
 
+    /// Printed width of text in points
+    pub fn text_width_pt(&self, text: &str) -> f64 {
+        match &self.config.font_metrics {
+            None => self.measure(&Markup::new(text), 0..text.len()) as f64 * self.config.char_width_pt,
+            Some(metrics) => visible_width_by(text, |c| metrics.char_width_cpt(c)) as f64 / 100.0,
+        }
+    }
+
+    /// Width of `range` of the text in wrapping units: characters (or cells
+    /// with `east_asian_width`) for monospace, hundredths of a point with
+    /// font metrics
+    fn measure(&self, markup: &Markup, range: Range<usize>) -> usize {
+        match &self.config.font_metrics {
+            None if self.config.east_asian_width => markup.visible_width_by(range, cell_width),
+            None => markup.visible_width(range),
+            Some(metrics) => markup.visible_width_by(range, |c| metrics.char_width_cpt(c)),
+        }
+    }
+
+    /// Byte index within `range` ending its widest prefix within `width` units
+    fn split_index(&self, markup: &Markup, range: Range<usize>, width: usize) -> usize {
+        match &self.config.font_metrics {
+            None if self.config.east_asian_width => markup.byte_index_at_width_by(range, width, cell_width),
+            None => markup.byte_index_at_width(range, width),
+            Some(metrics) => markup.byte_index_at_width_by(range, width, |c| metrics.char_width_cpt(c)),
+        }
+    }
+
     /// Word wrap text to fit within character limit
-    fn wrap_text(&self, text: &str, chars_per_line: usize) -> Vec<String> {
+    ///
+    /// Widths are measured in printed characters (grapheme clusters), so
+    /// emphasis markup is kept in the wrapped lines without counting against
+    /// the limit and lines only ever break between whole clusters. Words
+    /// joined by no-break spaces wrap as one, and are only broken when
+    /// longer than a whole line. With font
+    /// metrics the limit becomes the width of that many monospace characters.
+    /// The text must be normalized, so each line is one range of it.
+    fn wrap_text(&self, text: String, chars_per_line: usize) -> WrappedLines {
+        if text.is_empty() {
+            return WrappedLines::default();
+        }
+
         if chars_per_line == 0 {
-            return vec![text.to_string()];
+            let end = text.len();
+            return WrappedLines { text, spans: vec![LineSpan { start: 0, end, hyphen: false }] };
         }
 
-        let mut lines = Vec::new();
+        let (chars_per_line, space) = match &self.config.font_metrics {
+            None => (chars_per_line, 1),
+            Some(metrics) => (
+                (chars_per_line as f64 * self.config.char_width_pt * 100.0).round() as usize,
+                metrics.char_width_cpt(' '),
+            ),
+        };
+
+        let markup = Markup::new(&text);
+        let mut spans = Vec::new();
+        let mut offset = 0;
 
         for paragraph in text.split('\n') {
-            if paragraph.is_empty() {
-                lines.push(String::new());
-                continue;
-            }
+            let paragraph_start = offset;
+            offset += paragraph.len() + 1;
 
-            let words: Vec<&str> = paragraph.split_whitespace().collect();
-            if words.is_empty() {
-                lines.push(String::new());
-                continue;
-            }
+            // Range of the line being filled, and its width
+            let mut current: Option<(usize, usize)> = None;
+            let mut current_width = 0;
+            let mut has_words = false;
 
-            let mut current_line = String::new();
+            for (word_start, word_end) in word_ranges(paragraph) {
+                has_words = true;
+                let (mut start, end) = (paragraph_start + word_start, paragraph_start + word_end);
+                let mut word_width = self.measure(&markup, start..end);
 
-            for word in words {
-                if current_line.is_empty() {
+                match current {
                     // First word on line
-                    if word.len() > chars_per_line {
-                        // Word itself is longer than line - force break
-                        lines.extend(self.break_long_word(word, chars_per_line));
-                    } else {
-                        current_line = word.to_string();
+                    None => {
+                        if word_width > chars_per_line {
+                            // Word itself is longer than line - force break
+                            self.break_long_word(&markup, start, end, chars_per_line, &mut spans);
+                        } else {
+                            current = Some((start, end));
+                            current_width = word_width;
+                        }
                     }
-                } else if current_line.len() + 1 + word.len() <= chars_per_line {
                     // Word fits on current line
-                    current_line.push(' ');
-                    current_line.push_str(word);
-                } else {
-                    // Word doesn't fit - start new line
-                    lines.push(current_line);
-
-                    if word.len() > chars_per_line {
-                        lines.extend(self.break_long_word(word, chars_per_line));
-                        current_line = String::new();
-                    } else {
-                        current_line = word.to_string();
+                    Some((line_start, _)) if current_width + space + word_width <= chars_per_line => {
+                        current = Some((line_start, end));
+                        current_width += space + word_width;
+                    }
+                    // Word doesn't fit - hyphenate into the gap if allowed, then start new line
+                    Some((line_start, line_end)) => {
+                        let available = chars_per_line.saturating_sub(current_width + space);
+                        let mut line = LineSpan { start: line_start, end: line_end, hyphen: false };
+                        if let Some((head_end, tail_start, hyphen)) = self.hyphenate(&markup, start..end, available) {
+                            line = LineSpan { start: line_start, end: start + head_end, hyphen };
+                            start += tail_start;
+                            word_width = self.measure(&markup, start..end);
+                        }
+                        spans.push(line);
+
+                        if word_width > chars_per_line {
+                            self.break_long_word(&markup, start, end, chars_per_line, &mut spans);
+                            current = None;
+                            current_width = 0;
+                        } else {
+                            current = Some((start, end));
+                            current_width = word_width;
+                        }
                     }
                 }
             }
 
-            if !current_line.is_empty() {
-                lines.push(current_line);
+            match current {
+                Some((start, end)) => spans.push(LineSpan { start, end, hyphen: false }),
+                // A blank line
+                None if !has_words => spans.push(LineSpan { start: paragraph_start, end: paragraph_start, hyphen: false }),
+                None => {}
             }
         }
 
-        // Ensure at least one line for non-empty content
-        if lines.is_empty() && !text.is_empty() {
-            lines.push(String::new());
-        }
-
-        lines
+        WrappedLines { text, spans }
     }
 
-    /// Break a word that's longer than a line
-    fn break_long_word(&self, word: &str, chars_per_line: usize) -> Vec<String> {
-        let mut lines = Vec::new();
-        let mut remaining = word;
+    /// Break the word at `start..end` of `text`, longer than a line, onto lines of its own
+    fn break_long_word(&self, markup: &Markup, mut start: usize, end: usize, chars_per_line: usize, spans: &mut Vec<LineSpan>) {
+        while self.measure(markup, start..end) > chars_per_line {
+            if let Some((head_end, tail_start, hyphen)) = self.hyphenate(markup, start..end, chars_per_line) {
+                spans.push(LineSpan { start, end: start + head_end, hyphen });
+                start += tail_start;
+                continue;
+            }
+
+            let split = self.split_index(markup, start..end, chars_per_line);
+            spans.push(LineSpan { start, end: start + split, hyphen: false });
+            start += split;
+        }
 
-        while remaining.len() > chars_per_line {
-            lines.push(remaining[..chars_per_line].to_string());
-            remaining = &remaining[chars_per_line..];
+        if start < end {
+            spans.push(LineSpan { start, end, hyphen: false });
         }
+    }
 
-        if !remaining.is_empty() {
-            lines.push(remaining.to_string());
+    /// Split the word at `range` of the text at the last hyphenation point
+    /// whose head fits in `available` width units
+    ///
+    /// Words joined by no-break spaces are never hyphenated. Soft hyphens in
+    /// the word are its only break points and apply even with hyphenation
+    /// disabled; otherwise a dictionary entry for the word wins
+    /// over the language rules. Returns where the head ends, where the
+    /// remainder starts (both within the word) and whether the head prints
+    /// a trailing hyphen (not after an existing one), or None if no point
+    /// fits.
+    fn hyphenate(&self, markup: &Markup, range: Range<usize>, available: usize) -> Option<(usize, usize, bool)> {
+        let word = &markup.text()[range.clone()];
+        // Any whitespace left inside a word is a no-break space
+        if word.contains(char::is_whitespace) {
+            return None;
         }
 
-        lines
+        let hyphenation = &self.config.hyphenation;
+        let points = if word.contains(SOFT_HYPHEN) {
+            soft_hyphen_points(word)
+        } else if hyphenation.enabled {
+            dictionary_points(word, &hyphenation.dictionary)
+                .unwrap_or_else(|| hyphenation_points(word, hyphenation.language))
+        } else {
+            return None;
+        };
+
+        points
+            .into_iter()
+            .rev()
+            .find(|point| {
+                let hyphen = if point.explicit { 0 } else { self.measure(&Markup::new("-"), 0..1) };
+                self.measure(markup, range.start..range.start + point.index) + hyphen <= available
+            })
+            .map(|point| (word[..point.index].trim_end_matches(SOFT_HYPHEN).len(), point.index, !point.explicit))
     }
 
     /// Calculate just the content lines without a full LineCalculation
     pub fn content_lines(&self, element: &Element) -> u32 {
-        let style = self.config.style_for(element.element_type);
-        let chars_per_line = style.max_chars_per_line as usize;
-        self.wrap_text(&element.content, chars_per_line).len() as u32
+        self.calculate(element).content_lines
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::ElementId;
+    use crate::types::{ElementId, ElementStyleOverride, FontMetrics};
 
     fn make_config() -> PageConfig {
         PageConfig::feature_film()
@@ -174,9 +574,56 @@ mod tests {
             character_name: None,
             dual_dialogue_position: None,
             force_page_break_after: false,
+            style_override: None,
+            scene_number: None,
+            no_break_ranges: Vec::new(),
+            style_runs: Vec::new(),
+            custom_type: None,
         }
     }
 
+    #[test]
+    fn test_proportional_font_wrapping() {
+        let mut config = make_config();
+        let text = "word ".repeat(20);
+        let mono = LineCalculator::new(&config).calculate(&make_element(ElementType::Action, &text));
+        assert_eq!(mono.content_lines, 2);
+
+        // Half-width characters fit twice as many per line
+        config.font_metrics = Some(FontMetrics { default_width_pt: 3.6, ..Default::default() });
+        let narrow = LineCalculator::new(&config).calculate(&make_element(ElementType::Action, &text));
+        assert_eq!(narrow.content_lines, 1);
+
+        // A character table entry overrides the default width
+        config.font_metrics.as_mut().unwrap().widths.insert('W', 14.4);
+        let calc = LineCalculator::new(&config);
+        let wide = calc.calculate(&make_element(ElementType::Action, &"W".repeat(40)));
+        assert_eq!(wide.wrapped_lines.iter().map(|l| l.len()).collect::<Vec<_>>(), vec![30, 10]);
+        assert!((calc.text_width_pt("WW") - 28.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_measures_normalized_text() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        let lines = calc.calculate(&make_element(ElementType::SceneHeading, "int.   office - day "));
+        assert_eq!(lines.wrapped_lines, vec!["INT. OFFICE - DAY"]);
+    }
+
+    #[test]
+    fn test_line_start_offsets() {
+        let lines: WrappedLines = ["Hello  world", "again"].into_iter().collect();
+        assert_eq!(line_start_offsets("Hello  world again", &lines), vec![0, 13]);
+
+        let lines: WrappedLines = ["One", "", "Two"].into_iter().collect();
+        assert_eq!(line_start_offsets("One\n\nTwo", &lines), vec![0, 4, 5]);
+
+        // The lyric marker is dropped from the printed line
+        let lines: WrappedLines = ["La la", "Hey"].into_iter().collect();
+        assert_eq!(line_start_offsets("~La la\n~Hey", &lines), vec![0, 7]);
+    }
+
     #[test]
     fn test_scene_heading_single_line() {
         let config = make_config();
@@ -251,6 +698,224 @@ mod tests {
         assert_eq!(result.space_before, 2);
     }
 
+    #[test]
+    fn test_hyphenation_disabled_by_default() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        // 56 + 1 + 14 chars: the long word moves whole to the next line
+        let content = format!("{} extraordinarily", "x".repeat(56));
+        let element = make_element(ElementType::Action, &content);
+        let result = calc.calculate(&element);
+
+        assert_eq!(result.wrapped_lines.line(1), "extraordinarily");
+    }
+
+    #[test]
+    fn test_hyphenation_fills_line() {
+        let mut config = make_config();
+        config.hyphenation.enabled = true;
+        let calc = LineCalculator::new(&config);
+
+        let content = format!("{} extraordinarily", "x".repeat(50));
+        let element = make_element(ElementType::Action, &content);
+        let result = calc.calculate(&element);
+
+        assert!(result.wrapped_lines.line(0).ends_with('-'));
+        assert!(result.wrapped_lines.line(0).len() <= 60);
+        let rejoined = format!(
+            "{}{}",
+            result.wrapped_lines.line(0).trim_end_matches('-'),
+            result.wrapped_lines.line(1)
+        );
+        assert!(rejoined.ends_with("extraordinarily"));
+    }
+
+    #[test]
+    fn test_hyphenation_breaks_compound_word() {
+        let mut config = make_config();
+        config.hyphenation.enabled = true;
+        let calc = LineCalculator::new(&config);
+
+        // Parenthetical has 25 chars per line; break after the existing hyphen
+        let element = make_element(ElementType::Parenthetical, "self-congratulatoriness");
+        let result = calc.calculate(&element);
+        assert_eq!(result.wrapped_lines.len(), 1);
+
+        let element = make_element(ElementType::Parenthetical, "counterrevolutionary-minded");
+        let result = calc.calculate(&element);
+        assert!(result.wrapped_lines.line(0).starts_with("counterrevolutionary-"));
+        assert!(result.wrapped_lines.iter().all(|line| line.len() <= 25));
+    }
+
+    #[test]
+    fn test_soft_hyphen_breaks_with_visible_hyphen() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        // Hyphenation is off, but the author's soft hyphens still apply
+        let content = format!("{} extra\u{ad}ordinarily", "x".repeat(50));
+        let element = make_element(ElementType::Action, &content);
+        let result = calc.calculate(&element);
+
+        assert_eq!(result.wrapped_lines.line(0), format!("{} extra-", "x".repeat(50)));
+        assert_eq!(result.wrapped_lines.line(1), "ordinarily");
+        assert_eq!(line_start_offsets(&content, &result.wrapped_lines), vec![0, 57]);
+    }
+
+    #[test]
+    fn test_hyphenation_dictionary() {
+        let mut config = make_config();
+        config.hyphenation.enabled = true;
+        config.hyphenation.dictionary = vec!["extraordi-narily".to_string()];
+        let calc = LineCalculator::new(&config);
+
+        let content = format!("{} extraordinarily", "x".repeat(49));
+        let element = make_element(ElementType::Action, &content);
+        let result = calc.calculate(&element);
+
+        assert_eq!(result.wrapped_lines.line(1), "narily");
+    }
+
+    #[test]
+    fn test_no_break_spaces_and_ranges() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        // "U.S.S.\u{a0}Enterprise" is one 17-character word: it moves whole
+        let content = format!("{} U.S.S.\u{a0}Enterprise", "x".repeat(50));
+        let result = calc.calculate(&make_element(ElementType::Action, &content));
+        assert_eq!(result.wrapped_lines.line(1), "U.S.S.\u{a0}Enterprise");
+
+        // The same phrase held together by a range over its plain space
+        let content = format!("{} U.S.S. Enterprise", "x".repeat(50));
+        let element = make_element(ElementType::Action, &content).with_no_break_range(51, 68);
+        let result = calc.calculate(&element);
+        assert_eq!(result.wrapped_lines.line(0), "x".repeat(50));
+        assert_eq!(line_start_offsets(&content, &result.wrapped_lines), vec![0, 51]);
+    }
+
+    #[test]
+    fn test_style_runs_follow_wrapping() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        // A run over the end of line one and the start of line two, and one
+        // over the whole word that wrapped
+        let content = format!("{} extraordinarily", "x".repeat(56));
+        let mut element = make_element(ElementType::Action, &content);
+        element.style_runs = vec![
+            StyleRun { start: 50, end: 60, underline: true, ..Default::default() },
+            StyleRun { start: 57, end: 72, bold: true, ..Default::default() },
+        ];
+        let result = calc.calculate(&element);
+
+        let underline = element.style_runs[0];
+        let bold = element.style_runs[1];
+        assert_eq!(result.line_style_runs[0], vec![StyleRun { start: 50, end: 56, ..underline }]);
+        assert_eq!(
+            result.line_style_runs[1],
+            vec![StyleRun { start: 0, end: 3, ..underline }, StyleRun { start: 0, end: 15, ..bold }]
+        );
+    }
+
+    #[test]
+    fn test_emphasis_markup_does_not_count() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        // 60 printed characters, 64 with the bold markers
+        let content = format!("**{}**", "A".repeat(60));
+        let element = make_element(ElementType::Action, &content);
+        let result = calc.calculate(&element);
+
+        assert_eq!(result.content_lines, 1);
+        assert_eq!(result.wrapped_lines.line(0), content);
+    }
+
+    #[test]
+    fn test_unpaired_markers_take_width() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        // A signature line prints every underscore: 8 + 55 characters
+        let signature = format!("Signed: {}", "_".repeat(55));
+        assert_eq!(calc.calculate(&make_element(ElementType::Action, &signature)).content_lines, 2);
+
+        // Markers of a span covering several words stay invisible: 59 printed
+        let italic = format!("*{}*", "word ".repeat(12).trim_end());
+        assert_eq!(calc.calculate(&make_element(ElementType::Action, &italic)).content_lines, 1);
+    }
+
+    #[test]
+    fn test_emphasis_markup_preserved_when_wrapping() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        let content = format!("{} *quietly*", "word ".repeat(11).trim_end());
+        let element = make_element(ElementType::Action, &content);
+        let result = calc.calculate(&element);
+
+        // 54 + 1 + 7 printed characters: the italic word wraps with its markers
+        assert_eq!(result.content_lines, 2);
+        assert_eq!(result.wrapped_lines.line(1), "*quietly*");
+    }
+
+    #[test]
+    fn test_note_takes_no_lines_by_default() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        let element = make_element(ElementType::Note, "Punch this up later.");
+        let result = calc.calculate(&element);
+
+        assert_eq!(result.total_lines, 0);
+        assert_eq!(result.space_before, 0);
+    }
+
+    #[test]
+    fn test_note_boxed_in_production_draft() {
+        let mut config = make_config();
+        config.show_notes = true;
+        let calc = LineCalculator::new(&config);
+
+        let element = make_element(ElementType::Note, "Punch this up later.");
+        let result = calc.calculate(&element);
+
+        assert_eq!(result.content_lines, 3);
+        assert_eq!(result.space_before, 1);
+    }
+
+    #[test]
+    fn test_lyrics_prefix_not_measured() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        // 35 characters of lyric after the prefix fits a dialogue-width line
+        let content = format!("~{}\n~{}", "la ".repeat(12).trim_end(), "la");
+        let element = make_element(ElementType::Lyrics, &content);
+        let result = calc.calculate(&element);
+
+        assert_eq!(result.content_lines, 2);
+        assert!(!result.wrapped_lines.line(0).starts_with('~'));
+    }
+
+    #[test]
+    fn test_style_override_widens_line() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        let content = "A ".repeat(35);
+        let mut element = make_element(ElementType::Dialogue, content.trim_end());
+        assert_eq!(calc.calculate(&element).content_lines, 2);
+
+        element.style_override = Some(ElementStyleOverride {
+            max_chars_per_line: Some(70),
+            ..Default::default()
+        });
+        assert_eq!(calc.calculate(&element).content_lines, 1);
+    }
+
     #[test]
     fn test_long_word_breaking() {
         let config = make_config();
@@ -264,4 +929,38 @@ mod tests {
         // 100 chars / 35 chars per line = 3 lines
         assert!(result.content_lines >= 3);
     }
+
+    #[test]
+    fn test_east_asian_wide_characters() {
+        let mut config = make_config();
+        let element = make_element(ElementType::Dialogue, &"あ".repeat(40));
+
+        // One cell per character by default
+        assert_eq!(LineCalculator::new(&config).calculate(&element).content_lines, 2);
+
+        // Two cells each: 17 to a 35-cell dialogue line
+        config.east_asian_width = true;
+        let calc = LineCalculator::new(&config);
+        let result = calc.calculate(&element);
+        assert_eq!(result.content_lines, 3);
+        assert_eq!(result.wrapped_lines.line(0), "あ".repeat(17));
+        assert!((calc.text_width_pt("あa") - 3.0 * 7.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_wraps_by_grapheme_cluster() {
+        let config = make_config();
+        let calc = LineCalculator::new(&config);
+
+        // Combining diaeresis: 100 visible characters, 35 to a dialogue line
+        let long_word = "E\u{308}".repeat(100);
+        let result = calc.calculate(&make_element(ElementType::Dialogue, &long_word));
+        assert_eq!(result.content_lines, 3);
+        assert_eq!(result.wrapped_lines.line(0), "E\u{308}".repeat(35));
+
+        // Non-Latin words wrap on visible width too
+        let text = "Привет ".repeat(10);
+        let result = calc.calculate(&make_element(ElementType::Dialogue, text.trim_end()));
+        assert_eq!(result.wrapped_lines.line(0), "Привет ".repeat(5).trim_end());
+    }
 }
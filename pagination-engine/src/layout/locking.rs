@@ -0,0 +1,149 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{Element, LockedPageSet, PageConfig, PageIdentifier, PaginationResult, RenderOptions};
+use crate::utils::default_clock;
+use super::{LineCache, PaginationRun};
+
+/// Where the locked pages of a draft start in a revised element list
+#[derive(Debug, Clone)]
+pub(super) struct LockPlan {
+    /// Element opening each locked page that still has one
+    starts: HashMap<String, PageIdentifier>,
+    opened: HashSet<PageIdentifier>,
+}
+
+impl LockPlan {
+    /// A locked page opens at the first element, in the revised order, that
+    /// started on it in the locked draft; a page whose elements were all
+    /// deleted opens nowhere
+    pub(super) fn new(elements: &[Element], locks: &LockedPageSet) -> Self {
+        let started_on: HashMap<&str, &PageIdentifier> = locks
+            .pages
+            .iter()
+            .flat_map(|page| page.elements.iter().map(move |id| (id.0.as_str(), &page.identifier)))
+            .collect();
+
+        let mut starts = HashMap::new();
+        let mut opened = HashSet::new();
+        for element in elements {
+            if let Some(&page) = started_on.get(element.id.0.as_str()) {
+                if opened.insert(page.clone()) {
+                    starts.insert(element.id.0.clone(), page.clone());
+                }
+            }
+        }
+
+        Self { starts, opened }
+    }
+
+    /// Locked page that `element` opens
+    pub(super) fn page_started_by(&self, element: &Element) -> Option<&PageIdentifier> {
+        self.starts.get(&element.id.0)
+    }
+
+    /// Whether a locked page keeps content of its own, so its identifier is
+    /// not free for overflow from the page before
+    pub(super) fn is_taken(&self, page: &PageIdentifier) -> bool {
+        self.opened.contains(page)
+    }
+}
+
+/// Paginate a revised draft without renumbering the pages of a locked one
+///
+/// Every locked page that still has content starts where it did, at the
+/// first surviving element that started on it. Material that no longer fits
+/// before the next locked page flows onto A-pages (47A, 47B...), and locked
+/// page numbers left with nothing on them become omitted pages. Pages
+/// after the end of the locked draft are numbered on from its last page.
+pub fn paginate_locked(elements: &[Element], config: &PageConfig, locks: &LockedPageSet) -> PaginationResult {
+    let clock = default_clock();
+    let started_us = clock.now_us();
+
+    let mut cache = LineCache::new();
+    let mut run = PaginationRun::new(elements, config).with_locked_pages(locks);
+    while !run.advance(&mut cache, usize::MAX) {}
+
+    let mut result = run.finish(&RenderOptions::default());
+    result.stats.timing_us = clock.now_us().saturating_sub(started_us);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::{ElementStyleOverride, ElementType};
+
+    fn draft(scenes: usize) -> Vec<Element> {
+        (0..scenes)
+            .flat_map(|i| {
+                vec![
+                    Element::new(format!("h{}", i), ElementType::SceneHeading, format!("INT. ROOM {} - DAY", i)),
+                    // Whole scenes per page keep the locked breaks easy to predict
+                    Element::new(format!("a{}", i), ElementType::Action, "Things happen.\n".repeat(20).trim_end())
+                        .with_style_override(ElementStyleOverride { can_split: Some(false), ..Default::default() }),
+                ]
+            })
+            .collect()
+    }
+
+    fn identifiers(result: &PaginationResult) -> Vec<String> {
+        result.pages.iter().map(|page| page.identifier.display()).collect()
+    }
+
+    #[test]
+    fn test_unchanged_draft_keeps_pages() {
+        let config = PageConfig::feature_film();
+        let elements = draft(8);
+        let locked = paginate(&elements, &config);
+
+        let result = paginate_locked(&elements, &config, &LockedPageSet::from_result(&locked));
+
+        assert_eq!(identifiers(&result), identifiers(&locked));
+        for (id, position) in &locked.element_positions {
+            assert_eq!(result.element_positions[id].pages, position.pages);
+            assert_eq!(result.element_positions[id].start_line, position.start_line);
+        }
+    }
+
+    #[test]
+    fn test_added_material_goes_to_a_pages() {
+        let config = PageConfig::feature_film();
+        let elements = draft(8);
+        let locked = paginate(&elements, &config);
+        let locks = LockedPageSet::from_result(&locked);
+
+        // A long insertion in the scene on page 2
+        let mut revised = elements.clone();
+        let at = revised.iter().position(|e| e.id.0 == "a2").unwrap();
+        revised.insert(at, Element::new("new", ElementType::Action, "More happens.\n".repeat(40).trim_end()));
+
+        let result = paginate_locked(&revised, &config, &locks);
+        let pages = identifiers(&result);
+
+        assert!(pages.contains(&"2A".to_string()), "{:?}", pages);
+        // Every locked page number survives, in order
+        for page in &locked.pages {
+            assert!(pages.contains(&page.identifier.display()));
+        }
+        assert_eq!(result.element_positions["h4"].pages, locked.element_positions["h4"].pages);
+    }
+
+    #[test]
+    fn test_deleted_page_becomes_omitted() {
+        let config = PageConfig::feature_film();
+        let elements = draft(8);
+        let locked = paginate(&elements, &config);
+        let locks = LockedPageSet::from_result(&locked);
+
+        // Drop everything that started on page 3
+        let on_page_3: HashSet<&str> = locks.pages[2].elements.iter().map(|id| id.0.as_str()).collect();
+        let revised: Vec<Element> =
+            elements.iter().filter(|e| !on_page_3.contains(e.id.0.as_str())).cloned().collect();
+
+        let result = paginate_locked(&revised, &config, &locks);
+
+        assert!(result.pages.iter().any(|page| page.identifier == PageIdentifier::Omitted(3)));
+        assert_eq!(result.pages.last().unwrap().identifier, locked.pages.last().unwrap().identifier);
+    }
+}
@@ -1,7 +1,37 @@
+mod acts;
+mod builder;
+mod cheat;
+mod diff;
+mod estimate;
+mod line_cache;
 mod line_calculator;
+mod locking;
+mod normalize;
 mod page_breaker;
+mod paginator;
 mod continuation;
+mod render;
+mod revisions;
+mod scenes;
+mod simulate;
+mod title_page;
+mod validate;
 
+pub use acts::*;
+pub use builder::*;
+pub use cheat::*;
+pub use diff::*;
+pub use estimate::*;
+pub use line_cache::*;
 pub use line_calculator::*;
+pub use locking::*;
+pub use normalize::*;
 pub use page_breaker::*;
+pub use paginator::*;
 pub use continuation::*;
+pub use render::*;
+pub use revisions::*;
+pub use scenes::*;
+pub use simulate::*;
+pub use title_page::*;
+pub use validate::*;
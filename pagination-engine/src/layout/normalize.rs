@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+use crate::types::ElementStyle;
+use crate::utils::is_breaking_space;
+
+/// Text as it prints for an element with this style
+///
+/// Collapses runs of spaces and tabs within each line to a single space,
+/// trims both ends of every line, and uppercases when the style forces it.
+/// No-break spaces are part of the text and left alone.
+/// Line breaks, blank lines included, are kept. The input is borrowed when
+/// it is already normalized.
+pub fn normalize_text<'a>(text: &'a str, style: &ElementStyle) -> Cow<'a, str> {
+    let needs_case = style.force_uppercase && text.chars().any(|c| c.is_lowercase());
+    let needs_spacing = text.split('\n').any(|line| {
+        line.starts_with(is_breaking_space)
+            || line.ends_with(is_breaking_space)
+            || line.contains("  ")
+            || line.contains(|c: char| is_breaking_space(c) && c != ' ')
+    });
+    if !needs_case && !needs_spacing {
+        return Cow::Borrowed(text);
+    }
+
+    let normalized = text
+        .split('\n')
+        .map(|line| line.split(is_breaking_space).filter(|word| !word.is_empty()).collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Cow::Owned(if style.force_uppercase { normalized.to_uppercase() } else { normalized })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ElementType;
+
+    #[test]
+    fn test_normalize_text() {
+        let action = ElementStyle::default_for(ElementType::Action);
+        assert!(matches!(normalize_text("Clean text.\nSecond line.", &action), Cow::Borrowed(_)));
+        assert_eq!(normalize_text("  Two   spaces\t here.  \n\nNext. ", &action), "Two spaces here.\n\nNext.");
+        assert_eq!(normalize_text("Mr.\u{a0}Smith  waits.", &action), "Mr.\u{a0}Smith waits.");
+
+        let heading = ElementStyle::default_for(ElementType::SceneHeading);
+        assert_eq!(normalize_text("int. straße - day", &heading), "INT. STRASSE - DAY");
+    }
+}
@@ -1,11 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use crate::types::{
-    Element, ElementId, ElementPosition, ElementType, Page,
-    PageBreakReason, PageConfig, PageElement, PageIdentifier, PaginationResult,
-    PaginationStats, PaginationWarning, WarningType, LineRange,
+    BreakExplanation, BreakRule, DualDialoguePosition, Element, ElementId, ElementPosition, ElementType, HeaderFooterConfig, LockedPageSet, Page,
+    PageBreakReason, PageConfig, PageNumberingConfig, PageElement, PageIdentifier, PaginationDelta, PaginationResult,
+    PaginationStats, PaginationWarning, RenderOptions, TitlePage, TypeContinuationStyle, WarningType, LineRange,
+};
+use crate::utils::{default_clock, Clock};
+use super::{
+    apply_render_options, build_scene_index, LockPlan, calculate_act_stats, calculate_scene_lengths, layout_title_page, line_start_offsets, number_scenes,
+    ContinuationManager, LineCache, LineCalculation, LineCalculator,
 };
-use super::{ContinuationManager, LineCalculation, LineCalculator};
 
 /// Decision for how to handle an element at a page boundary
 #[derive(Debug)]
@@ -13,53 +18,222 @@ enum BreakDecision {
     /// Element fits on current page
     Fits,
 
-    /// Break page before this element (push to next page)
-    BreakBefore,
+    /// Break page before this element (push to next page), for the given rule
+    BreakBefore(BreakRule),
 
     /// Split the element at the given line number
-    SplitAt { line: u32 },
+    ///
+    /// `orphaned` marks a split that could not honor the minimum line counts
+    SplitAt { line: u32, orphaned: bool },
+}
+
+/// Point where placement can resume: a fresh page about to receive `element`
+#[derive(Debug, Clone)]
+pub(super) struct Checkpoint {
+    pub(super) element: usize,
+    pages: usize,
+    warnings: usize,
+    explanations: usize,
+    identifier: PageIdentifier,
+    page_number: u32,
+    break_count: usize,
+    continuation_count: usize,
+    speaker: Option<String>,
 }
 
 /// Internal state during pagination
-struct PaginationState {
+#[derive(Debug, Clone)]
+pub(super) struct PaginationState {
     pages: Vec<Page>,
+    numbering: PageNumberingConfig,
+    header_footer: HeaderFooterConfig,
+    lines_per_page: u8,
+    /// Finished pages already handed out by `take_completed_pages`
+    emitted_pages: u32,
     current_page: Page,
     page_number: u32,
     element_positions: HashMap<String, ElementPosition>,
     warnings: Vec<PaginationWarning>,
+    /// Rule behind each page break, kept for `RenderOptions::explain`
+    explanations: Vec<BreakExplanation>,
     break_count: usize,
     continuation_count: usize,
+    /// Speaker whose cue opened the speech block currently being placed
+    speaker: Option<String>,
+    /// Audio cell already placed alongside the preceding video cell
+    row_partner: Option<ElementId>,
+    /// Elements still to come that were placed with their dual dialogue block
+    dual_partners: usize,
+    /// Page starts kept from a locked draft
+    locks: Option<LockPlan>,
+    /// Breaks pinned with `PageConfig::forced_breaks`, by element: the line
+    /// of the element the next page opens with, or None to break after it
+    forced_breaks: HashMap<String, Option<u32>>,
 }
 
 impl PaginationState {
-    fn new() -> Self {
-        Self {
+    pub(super) fn new(config: &PageConfig) -> Self {
+        let numbering = config.page_numbering.clone();
+        let first = numbering.start_number;
+
+        let mut state = Self {
             pages: Vec::new(),
-            current_page: Page::new(PageIdentifier::Sequential(1)),
-            page_number: 1,
+            emitted_pages: 0,
+            current_page: Page::new(PageIdentifier::Sequential(first)),
+            page_number: first,
+            numbering,
+            header_footer: config.header_footer.clone(),
+            lines_per_page: config.lines_per_page,
             element_positions: HashMap::new(),
             warnings: Vec::new(),
+            explanations: Vec::new(),
             break_count: 0,
             continuation_count: 0,
-        }
+            speaker: None,
+            row_partner: None,
+            dual_partners: 0,
+            locks: None,
+            forced_breaks: config
+                .forced_breaks
+                .iter()
+                .map(|pinned| (pinned.after_element.0.clone(), pinned.split_at_line))
+                .collect(),
+        };
+        state.start_page();
+        state
     }
 
     fn lines_remaining(&self, lines_per_page: u8) -> u8 {
         lines_per_page.saturating_sub(self.current_page.lines_used)
     }
 
+    /// Whether the page ends after `element`: its own flag, or a break
+    /// pinned on it (an element placed whole ignores the split line)
+    fn breaks_after(&self, element: &Element) -> bool {
+        element.force_page_break_after || self.forced_breaks.contains_key(&element.id.0)
+    }
+
     fn at_page_start(&self) -> bool {
-        self.current_page.lines_used == 0
+        self.current_page.lines_used == self.running_header_lines()
     }
 
-    fn end_page(&mut self, _reason: PageBreakReason) {
-        let finished_page = std::mem::replace(
-            &mut self.current_page,
-            Page::new(PageIdentifier::Sequential(self.page_number + 1)),
-        );
+    /// Page lines the running header takes on the open page
+    fn running_header_lines(&self) -> u8 {
+        if self.current_page.running_header.is_some() { self.header_footer.header_lines } else { 0 }
+    }
+
+    /// Print the running header on the page just opened, reserving its lines
+    fn start_page(&mut self) {
+        let first = self.pages.is_empty() && self.emitted_pages == 0;
+        if first && self.header_footer.skip_first_page {
+            return;
+        }
+        if let Some(text) = self.header_footer.text() {
+            self.current_page.running_header = Some(text);
+            self.current_page.lines_used = self.header_footer.header_lines;
+        }
+    }
+
+    fn end_page(&mut self, reason: PageBreakReason) {
+        let rule = match reason {
+            PageBreakReason::Forced => BreakRule::Forced,
+            PageBreakReason::ActBreak => BreakRule::ActBreak,
+            PageBreakReason::Locked => BreakRule::Locked,
+            PageBreakReason::DialogueContinuation => BreakRule::Split,
+            _ => BreakRule::PageFull,
+        };
+        self.end_page_for(reason, None, rule);
+    }
+
+    /// End the page, recording `rule` and the element being placed as the
+    /// explanation for the break
+    fn end_page_for(&mut self, reason: PageBreakReason, element: Option<&Element>, rule: BreakRule) {
+        self.explanations.push(BreakExplanation {
+            page: self.current_page.identifier.clone(),
+            element_id: element.map(|e| e.id.clone()),
+            rule,
+            free_lines: self.current_page.lines_remaining(self.lines_per_page),
+        });
+
+        let next = self.next_identifier();
+        let mut finished_page = std::mem::replace(&mut self.current_page, Page::new(next.clone()));
+        finished_page.page_number = self.numbering.label(&finished_page.identifier);
+        finished_page.break_reason = Some(reason);
         self.pages.push(finished_page);
-        self.page_number += 1;
+        if let PageIdentifier::Sequential(number) = next {
+            self.page_number = number;
+        }
         self.break_count += 1;
+        self.start_page();
+    }
+
+    /// Identifier for the page after the current one
+    ///
+    /// When the next number belongs to a locked page that keeps content of
+    /// its own, the new page is inserted before it as an A-page.
+    fn next_identifier(&self) -> PageIdentifier {
+        let next = PageIdentifier::Sequential(self.page_number + 1);
+        let Some(locks) = self.locks.as_ref().filter(|locks| locks.is_taken(&next)) else {
+            return next;
+        };
+
+        let mut inserted = match self.current_page.identifier {
+            PageIdentifier::Inserted { .. } => self.current_page.identifier.next(),
+            _ => PageIdentifier::Inserted { base: self.page_number, suffix: 'A' },
+        };
+        while locks.is_taken(&inserted) && matches!(inserted, PageIdentifier::Inserted { .. }) {
+            inserted = inserted.next();
+        }
+        inserted
+    }
+
+    /// Open the locked page `page` that `element` starts
+    ///
+    /// Locked numbers passed over on the way, with nothing left on them,
+    /// become omitted pages. A locked page already behind the current one
+    /// (its element was moved earlier) is ignored.
+    fn start_locked_page(&mut self, element: &Element) {
+        let Some(page) = self.locks.as_ref().and_then(|locks| locks.page_started_by(element)).cloned() else {
+            return;
+        };
+
+        let current = self.current_page.identifier.sort_key();
+        let behind = if self.at_page_start() { page.sort_key() < current } else { page.sort_key() <= current };
+        if behind {
+            return;
+        }
+        if !self.at_page_start() {
+            self.end_page_for(PageBreakReason::Locked, Some(element), BreakRule::Locked);
+        }
+
+        // Sequential numbers not yet used by a page
+        let first_free = match self.current_page.identifier {
+            PageIdentifier::Inserted { base, .. } => base + 1,
+            PageIdentifier::Sequential(number) | PageIdentifier::Omitted(number) => number,
+        };
+        let (PageIdentifier::Sequential(number) | PageIdentifier::Inserted { base: number, .. }) = page else {
+            return;
+        };
+        let last_omitted = if matches!(page, PageIdentifier::Sequential(_)) { number } else { number + 1 };
+        for omitted in first_free..last_omitted {
+            self.current_page.identifier = PageIdentifier::Omitted(omitted);
+            self.page_number = omitted;
+            self.end_page(PageBreakReason::Locked);
+        }
+
+        self.current_page.identifier = page;
+        self.page_number = number;
+    }
+
+    /// Reserve the continued header on a page just opened after a split
+    ///
+    /// Returns the lines above the content, running header included.
+    fn start_continued_page(&mut self, config: &PageConfig) -> u8 {
+        if let Some(header) = &config.continued_header {
+            self.current_page.header = Some(header.text.clone());
+            self.current_page.lines_used += header.lines;
+        }
+        self.current_page.lines_used
     }
 
     fn add_element(&mut self, element: &Element, line_calc: &LineCalculation, at_page_start: bool) {
@@ -73,6 +247,13 @@ impl PaginationState {
             is_continuation: false,
             line_range: None,
             continuation_prefix: None,
+            line_positions: None,
+            frame: None,
+            text: None,
+            style_runs: None,
+            column: None,
+            revision_marks: None,
+            scene_number: None,
         };
 
         self.current_page.elements.push(page_element);
@@ -86,15 +267,105 @@ impl PaginationState {
                 start_line,
                 end_line: start_line + line_calc.content_lines as u8 - 1,
                 is_split: false,
+                line_offsets: Vec::new(),
+            },
+        );
+    }
+
+    /// Record a non-printing element at the current position without using lines
+    fn add_anchor(&mut self, element: &Element) {
+        let start_line = self.current_page.lines_used + 1;
+
+        self.current_page.elements.push(PageElement {
+            element_id: element.id.clone(),
+            start_line,
+            line_count: 0,
+            is_continuation: false,
+            line_range: None,
+            continuation_prefix: None,
+            line_positions: None,
+            frame: None,
+            text: None,
+            style_runs: None,
+            column: None,
+            revision_marks: None,
+            scene_number: None,
+        });
+
+        self.element_positions.insert(
+            element.id.0.clone(),
+            ElementPosition {
+                pages: vec![self.current_page.identifier.clone()],
+                start_line,
+                end_line: start_line,
+                is_split: false,
+                line_offsets: Vec::new(),
             },
         );
     }
 
+    /// Emit the locked page(s) named by an `OmittedPage` marker
+    ///
+    /// Each becomes an `Omitted(n)` page and numbering resumes at the page
+    /// after the last, keeping later pages aligned with the locked draft.
+    fn add_omitted_pages(&mut self, element: &Element) {
+        if !self.at_page_start() {
+            self.end_page_for(PageBreakReason::Forced, Some(element), BreakRule::Forced);
+        }
+
+        let (first, last) = parse_page_range(&element.content).unwrap_or((self.page_number, self.page_number));
+        let mut pages = Vec::new();
+        for number in first..=last.max(first) {
+            self.current_page.identifier = PageIdentifier::Omitted(number);
+            self.page_number = number;
+            if number == first {
+                self.add_anchor(element);
+            }
+            pages.push(self.current_page.identifier.clone());
+            self.end_page(PageBreakReason::Forced);
+        }
+
+        if let Some(position) = self.element_positions.get_mut(&element.id.0) {
+            position.pages = pages;
+        }
+    }
+
+    /// Close the page inside a speech block and carry the block over
+    ///
+    /// Ends the page with the MORE marker (when there is a line for it) and
+    /// places the whole element under a re-emitted `NAME (CONT'D)` cue.
+    fn continue_speech_on_next_page(
+        &mut self,
+        element: &Element,
+        line_calc: &LineCalculation,
+        config: &PageConfig,
+        markers: &TypeContinuationStyle,
+        contd_prefix: String,
+        rule: BreakRule,
+    ) {
+        let consumes = u8::from(markers.consumes_line);
+        if markers.more_marker.is_some() && self.lines_remaining(config.lines_per_page) >= consumes {
+            self.current_page.bottom_continuation = markers.more_marker.clone();
+            self.current_page.lines_used += consumes;
+        }
+        self.continuation_count += 1;
+        self.end_page_for(PageBreakReason::DialogueContinuation, Some(element), rule);
+
+        // The CONT'D cue takes the first line under any header
+        let header_lines = self.start_continued_page(config);
+        self.current_page.lines_used = header_lines + consumes;
+        self.add_element(element, line_calc, true);
+        if let Some(page_element) = self.current_page.elements.last_mut() {
+            page_element.continuation_prefix = Some(contd_prefix);
+        }
+    }
+
     fn add_split_element_first_part(
         &mut self,
         element: &Element,
         first_lines: u32,
         more_marker: Option<String>,
+        marker_lines: u32,
         at_page_start: bool,
         space_before: u8,
     ) {
@@ -111,6 +382,13 @@ impl PaginationState {
                 end: first_lines,
             }),
             continuation_prefix: None,
+            line_positions: None,
+            frame: None,
+            text: None,
+            style_runs: None,
+            column: None,
+            revision_marks: None,
+            scene_number: None,
         };
 
         self.current_page.elements.push(page_element);
@@ -119,7 +397,7 @@ impl PaginationState {
         // Set the MORE marker
         if more_marker.is_some() {
             self.current_page.bottom_continuation = more_marker;
-            self.current_page.lines_used += 1; // MORE takes a line
+            self.current_page.lines_used += marker_lines as u8;
             self.continuation_count += 1;
         }
     }
@@ -130,13 +408,14 @@ impl PaginationState {
         first_lines: u32,
         second_lines: u32,
         contd_prefix: Option<String>,
+        prefix_lines: u32,
     ) {
         // Continuation character name if dialogue
-        let extra_lines = if contd_prefix.is_some() { 1 } else { 0 };
+        let extra_lines = prefix_lines as u8;
 
         let page_element = PageElement {
             element_id: element.id.clone(),
-            start_line: 1 + extra_lines,
+            start_line: self.current_page.lines_used + extra_lines + 1,
             line_count: second_lines as u8,
             is_continuation: true,
             line_range: Some(LineRange {
@@ -144,20 +423,28 @@ impl PaginationState {
                 end: first_lines + second_lines,
             }),
             continuation_prefix: contd_prefix,
+            line_positions: None,
+            frame: None,
+            text: None,
+            style_runs: None,
+            column: None,
+            revision_marks: None,
+            scene_number: None,
         };
 
         self.current_page.elements.push(page_element);
-        self.current_page.lines_used = extra_lines + second_lines as u8;
+        self.current_page.lines_used += extra_lines + second_lines as u8;
     }
 
-    fn record_split_position(&mut self, element_id: &str, first_page: PageIdentifier, second_page: PageIdentifier, start_line: u8, end_line: u8) {
+    fn record_split_position(&mut self, element_id: &str, pages: Vec<PageIdentifier>, start_line: u8, end_line: u8) {
         self.element_positions.insert(
             element_id.to_string(),
             ElementPosition {
-                pages: vec![first_page, second_page],
+                pages,
                 start_line,
                 end_line,
                 is_split: true,
+                line_offsets: Vec::new(),
             },
         );
     }
@@ -170,140 +457,250 @@ impl PaginationState {
         });
     }
 
-    fn finalize(mut self, timing_us: u64, element_count: usize) -> PaginationResult {
-        // Add the last page if it has content
-        if !self.current_page.elements.is_empty() {
-            self.pages.push(self.current_page);
+    /// Place `upcoming[0]` on the current page, breaking or splitting as needed
+    ///
+    /// `upcoming` and `upcoming_lines` start at the element being placed and
+    /// must extend as far as its keep-with-next lookahead reaches.
+    pub(super) fn place(
+        &mut self,
+        config: &PageConfig,
+        continuation_mgr: &ContinuationManager,
+        upcoming: &[Element],
+        upcoming_lines: &[Arc<LineCalculation>],
+    ) {
+        let element = &upcoming[0];
+
+        // Handle forced page break element
+        if element.element_type == ElementType::PageBreak {
+            if !self.at_page_start() {
+                self.end_page_for(PageBreakReason::Forced, Some(element), BreakRule::Forced);
+            }
+            return;
         }
 
-        let page_count = self.pages.len() as u32;
+        if element.element_type == ElementType::OmittedPage {
+            self.add_omitted_pages(element);
+            return;
+        }
 
-        PaginationResult {
-            pages: self.pages,
-            element_positions: self.element_positions,
-            warnings: self.warnings,
-            stats: PaginationStats {
-                page_count,
-                element_count,
-                break_count: self.break_count,
-                continuation_count: self.continuation_count,
-                timing_us,
-            },
+        // The audio cell of an AV row was placed with its video cell
+        if self.row_partner.as_ref() == Some(&element.id) {
+            self.row_partner = None;
+            if self.breaks_after(element) && !self.at_page_start() {
+                self.end_page_for(PageBreakReason::Forced, Some(element), BreakRule::Forced);
+            }
+            return;
         }
-    }
-}
 
-/// Core pagination function - pure, deterministic, no side effects
-pub fn paginate(elements: &[Element], config: &PageConfig) -> PaginationResult {
-    let line_calc = LineCalculator::new(config);
-    let continuation_mgr = ContinuationManager::new(config);
+        // Later elements of a dual dialogue block were placed with its first
+        if self.dual_partners > 0 {
+            self.dual_partners -= 1;
+            if self.breaks_after(element) && !self.at_page_start() {
+                self.end_page_for(PageBreakReason::Forced, Some(element), BreakRule::Forced);
+            }
+            return;
+        }
 
-    let mut state = PaginationState::new();
-    let element_count = elements.len();
+        self.start_locked_page(element);
 
-    for (idx, element) in elements.iter().enumerate() {
-        // Handle forced page break element
-        if element.element_type == ElementType::PageBreak {
-            if !state.at_page_start() {
-                state.end_page(PageBreakReason::Forced);
+        if let (left, right @ 1..) = dual_columns(upcoming) {
+            self.place_dual_block(config, upcoming, upcoming_lines, left);
+            self.dual_partners = left + right - 1;
+            self.speaker = None;
+
+            if self.breaks_after(element) && !self.at_page_start() {
+                self.end_page_for(PageBreakReason::Forced, Some(element), BreakRule::Forced);
             }
-            continue;
+            return;
+        }
+
+        if matches!(element.element_type, ElementType::AvVideo | ElementType::AvAudio) {
+            let partner = upcoming
+                .get(1)
+                .filter(|next| element.element_type == ElementType::AvVideo && next.element_type == ElementType::AvAudio);
+
+            let mut cells = vec![(element, upcoming_lines[0].as_ref())];
+            if let Some(audio) = partner {
+                cells.push((audio, upcoming_lines[1].as_ref()));
+                self.row_partner = Some(audio.id.clone());
+            }
+            self.place_av_row(config, &cells);
+            self.speaker = None;
+
+            if self.breaks_after(element) && !self.at_page_start() {
+                self.end_page_for(PageBreakReason::Forced, Some(element), BreakRule::Forced);
+            }
+            return;
         }
 
-        // Calculate lines for this element
-        let lines = line_calc.calculate(element);
+        // Non-printing elements (e.g., notes) only record where they fall
+        if !config.is_visible(element.element_type) {
+            self.add_anchor(element);
+            if self.breaks_after(element) && !self.at_page_start() {
+                self.end_page_for(PageBreakReason::Forced, Some(element), BreakRule::Forced);
+            }
+            return;
+        }
+
+        let lines = &upcoming_lines[0];
 
         // Calculate total space needed
-        let space_before = if state.at_page_start() { 0 } else { lines.space_before };
-        let total_needed = space_before as u32 + lines.total_lines;
+        let space_before = if self.at_page_start() { 0 } else { lines.space_before };
+
+        let remaining = self.lines_remaining(config.lines_per_page) as u32;
 
-        let remaining = state.lines_remaining(config.lines_per_page) as u32;
+        // A pinned break inside the element overrides the usual rules when its
+        // first part fits; line 0 pins a break before the element
+        let pinned_split = match self.forced_breaks.get(&element.id.0) {
+            Some(&Some(line)) if line < lines.content_lines => Some(line),
+            _ => None,
+        };
+        let more_lines = continuation_mgr.markers(element.element_type).more_lines();
 
         // Decide what to do
-        let decision = decide_break(
-            element,
-            &lines,
-            total_needed,
-            remaining,
-            config,
-            &elements[idx..],
-        );
+        let decision = match pinned_split {
+            Some(0) if !self.at_page_start() => BreakDecision::BreakBefore(BreakRule::Forced),
+            Some(line @ 1..) if space_before as u32 + line + more_lines <= remaining => {
+                BreakDecision::SplitAt { line, orphaned: false }
+            }
+            _ => {
+                if pinned_split.is_some_and(|line| line > 0) {
+                    self.add_warning(
+                        Some(&element.id),
+                        WarningType::ForcedBreakMoved,
+                        "Pinned break falls below the end of the page".to_string(),
+                    );
+                }
+                decide_break(element, lines, self.at_page_start(), remaining, config, upcoming, upcoming_lines)
+            }
+        };
 
         match decision {
             BreakDecision::Fits => {
-                state.add_element(element, &lines, state.at_page_start());
+                self.add_element(element, lines, self.at_page_start());
             }
 
-            BreakDecision::BreakBefore => {
-                if !state.at_page_start() {
-                    state.end_page(PageBreakReason::OrphanPrevention);
+            BreakDecision::BreakBefore(rule) => self.break_before(element, lines, config, continuation_mgr, rule),
+
+            BreakDecision::SplitAt { line, orphaned } => {
+                if orphaned {
+                    self.add_warning(
+                        Some(&element.id),
+                        WarningType::UnpreventableOrphan,
+                        "Split leaves fewer lines on a page than the minimum allows".to_string(),
+                    );
                 }
-                state.add_element(element, &lines, true);
-            }
 
-            BreakDecision::SplitAt { line } => {
-                let at_page_start = state.at_page_start();
+                let at_page_start = self.at_page_start();
 
                 // Split the element
-                let split = if element.element_type == ElementType::Dialogue {
-                    continuation_mgr.split_dialogue(element, &lines, line)
+                let pinned = pinned_split == Some(line);
+                let split = if pinned {
+                    continuation_mgr.split_exact(element, lines, line)
+                } else if matches!(element.element_type, ElementType::Dialogue | ElementType::Lyrics) {
+                    continuation_mgr.split_dialogue(element, lines, line)
                 } else {
-                    continuation_mgr.split_action(&lines, line)
+                    continuation_mgr.split_action(element, lines, line)
                 };
+                let markers = continuation_mgr.markers(element.element_type);
+                let is_speech = matches!(element.element_type, ElementType::Dialogue | ElementType::Lyrics);
 
                 // Check if split is valid (has content on both sides)
                 if split.first_part_lines > 0 && split.second_part_lines > 0 {
-                    let first_page = state.current_page.identifier.clone();
-                    let start_line = state.current_page.lines_used + space_before + 1;
+                    let first_page = self.current_page.identifier.clone();
+                    let start_line = self.current_page.lines_used + space_before + 1;
 
                     // Add first part to current page
-                    state.add_split_element_first_part(
+                    self.add_split_element_first_part(
                         element,
                         split.first_part_lines,
                         split.more_marker.clone(),
+                        markers.more_lines(),
                         at_page_start,
                         lines.space_before,
                     );
 
-                    // End page and start new one
-                    state.end_page(PageBreakReason::DialogueContinuation);
-
-                    let second_page = state.current_page.identifier.clone();
-
-                    // Add second part to new page
-                    state.add_split_element_second_part(
-                        element,
-                        split.first_part_lines,
-                        split.second_part_lines,
-                        split.contd_prefix,
-                    );
+                    // Dialogue without its own character name continues under the block's cue
+                    let contd_prefix = split.contd_prefix.or_else(|| {
+                        let name = self.speaker.as_deref().filter(|_| is_speech)?;
+                        continuation_mgr.contd_prefix_for(element.element_type, Some(name))
+                    });
+
+                    let min_after = if is_speech {
+                        config.orphan_control.dialogue_min_after_split as u32
+                    } else {
+                        config.resolved_style(element).min_lines_after_split as u32
+                    };
+                    let prefix_lines = u32::from(contd_prefix.is_some() && markers.consumes_line);
+
+                    let total = split.first_part_lines + split.second_part_lines;
+                    let mut placed = split.first_part_lines;
+                    let mut pages = vec![first_page];
+                    let mut end_line = 0;
+
+                    // Continue on fresh pages, repeating the header and MORE/CONT'D at
+                    // each boundary until the remainder fits
+                    let natural_rule = if orphaned { BreakRule::SplitBelowMinimum } else { BreakRule::Split };
+                    let mut rule = if pinned { BreakRule::Forced } else { natural_rule };
+                    while placed < total {
+                        self.end_page_for(PageBreakReason::DialogueContinuation, Some(element), rule);
+                        rule = natural_rule;
+                        pages.push(self.current_page.identifier.clone());
+
+                        let header_lines = self.start_continued_page(config) as u32;
+                        let capacity = (config.lines_per_page as u32).saturating_sub(header_lines + prefix_lines);
+
+                        let rest = total - placed;
+                        let chunk = if rest > capacity {
+                            capacity.saturating_sub(markers.more_lines()).min(rest.saturating_sub(min_after)).max(1)
+                        } else {
+                            rest
+                        };
+
+                        self.add_split_element_second_part(element, placed, chunk, contd_prefix.clone(), prefix_lines);
+                        if chunk < rest && split.more_marker.is_some() {
+                            self.current_page.bottom_continuation = split.more_marker.clone();
+                            self.current_page.lines_used += markers.more_lines() as u8;
+                            self.continuation_count += 1;
+                        }
+
+                        end_line = header_lines + prefix_lines + chunk;
+                        placed += chunk;
+                    }
 
                     // Record the split position
-                    state.record_split_position(
-                        &element.id.0,
-                        first_page,
-                        second_page,
-                        start_line,
-                        split.second_part_lines as u8,
-                    );
+                    self.record_split_position(&element.id.0, pages, start_line, end_line as u8);
                 } else {
                     // Can't split meaningfully, push to next page
-                    if !state.at_page_start() {
-                        state.end_page(PageBreakReason::OrphanPrevention);
-                    }
-                    state.add_element(element, &lines, true);
+                    self.break_before(element, lines, config, continuation_mgr, BreakRule::CannotSplit);
                 }
             }
         }
 
-        // Handle forced page break after this element
-        if element.force_page_break_after && !state.at_page_start() {
-            state.end_page(PageBreakReason::Forced);
+        // Track the open speech block for (MORE)/(CONT'D) on later breaks
+        match element.element_type {
+            ElementType::Character => self.speaker = Some(cue_name(element)),
+            ElementType::Parenthetical | ElementType::Dialogue | ElementType::Lyrics => {}
+            _ => self.speaker = None,
+        }
+
+        // Handle forced page break after this element, unless the pinned
+        // break fell inside it
+        let pinned_inside = pinned_split.is_some() && !element.force_page_break_after;
+        if self.breaks_after(element) && !pinned_inside && !self.at_page_start() {
+            self.end_page_for(PageBreakReason::Forced, Some(element), BreakRule::Forced);
+        }
+
+        // The next act starts on a fresh page
+        let act_ends = element.element_type == ElementType::ActBreak && config.act_breaks.new_page_after;
+        if act_ends && !self.at_page_start() {
+            self.end_page_for(PageBreakReason::ActBreak, Some(element), BreakRule::ActBreak);
         }
 
-        // Check for element exceeding page
-        if lines.total_lines > config.lines_per_page as u32 {
-            state.add_warning(
+        // Check for an element that could not be split and overflows its page
+        let was_split = self.element_positions.get(&element.id.0).is_some_and(|p| p.is_split);
+        if lines.total_lines > config.lines_per_page as u32 && !was_split {
+            self.add_warning(
                 Some(&element.id),
                 WarningType::ElementExceedsPage,
                 format!(
@@ -314,154 +711,961 @@ pub fn paginate(elements: &[Element], config: &PageConfig) -> PaginationResult {
         }
     }
 
-    // Timing is measured by the JavaScript worker using performance.now()
-    state.finalize(0, element_count)
-}
+    /// Place an AV row: cells side by side from the same line, the row as
+    /// tall as its tallest cell
+    ///
+    /// A row that doesn't fit flows across pages, each cell continuing at the
+    /// top of the next page, under the same minimum line rules as action.
+    fn place_av_row(&mut self, config: &PageConfig, cells: &[(&Element, &LineCalculation)]) {
+        let (first, first_lines) = cells[0];
+        let style = config.resolved_style(first);
+        let splittable = cells.iter().all(|(e, _)| config.resolved_style(e).can_split);
+        let min_before = (style.min_lines_before_split as u32).max(1);
+        let min_after = style.min_lines_after_split as u32;
+
+        let row_content = cells.iter().map(|(_, l)| l.content_lines).max().unwrap_or(0);
+        let row_total = cells.iter().map(|(_, l)| l.total_lines).max().unwrap_or(0);
+        let space_after = row_total.saturating_sub(row_content);
+
+        // Content lines of the row already placed on earlier pages
+        let mut offset = 0;
+        loop {
+            let rest = row_content - offset;
+            let space = if self.at_page_start() || offset > 0 { 0 } else { first_lines.space_before as u32 };
+            let remaining = self.lines_remaining(config.lines_per_page) as u32;
+
+            if space + rest + space_after <= remaining {
+                self.add_row_part(cells, offset, rest, space);
+                self.current_page.lines_used += (space + rest + space_after) as u8;
+                return;
+            }
 
-/// Decide how to handle an element at a page boundary
-fn decide_break(
-    element: &Element,
-    lines: &LineCalculation,
-    total_needed: u32,
-    remaining: u32,
-    config: &PageConfig,
-    upcoming: &[Element],
-) -> BreakDecision {
-    // If it fits, we're done
-    if total_needed <= remaining {
-        // But check orphan rules for keep_with_next
-        let style = config.style_for(element.element_type);
-        if style.keep_with_next && upcoming.len() > 1 {
-            // Check if we have room for this + required following lines
-            let following_lines = estimate_following_lines(config, &upcoming[1..], style.keep_with_next_lines);
-            if total_needed + following_lines > remaining {
-                return BreakDecision::BreakBefore;
+            let available = remaining.saturating_sub(space);
+            let mut split = available.min(rest.saturating_sub(min_after));
+            let mut rule = BreakRule::Split;
+            if !splittable || split < min_before {
+                if !self.at_page_start() && offset == 0 {
+                    let rule = if splittable { split_rule(available, min_before) } else { BreakRule::CannotSplit };
+                    self.end_page_for(PageBreakReason::OrphanPrevention, Some(first), rule);
+                    continue;
+                }
+                if !splittable || available == 0 {
+                    // Nothing better is possible: let the rest overflow this page
+                    self.add_row_part(cells, offset, rest, space);
+                    self.current_page.lines_used += (space + rest + space_after) as u8;
+                    self.add_warning(
+                        Some(&first.id),
+                        WarningType::ElementExceedsPage,
+                        format!("AV row requires {} lines but page only has {} lines", rest, remaining),
+                    );
+                    return;
+                }
+                split = available;
+                rule = BreakRule::SplitBelowMinimum;
+                self.add_warning(
+                    Some(&first.id),
+                    WarningType::UnpreventableOrphan,
+                    "Split leaves fewer lines on a page than the minimum allows".to_string(),
+                );
             }
+
+            self.add_row_part(cells, offset, split, space);
+            self.current_page.lines_used += (space + split) as u8;
+            self.end_page_for(PageBreakReason::PageFull, Some(first), rule);
+            offset += split;
         }
-        return BreakDecision::Fits;
     }
 
-    let style = config.style_for(element.element_type);
-    let orphan = &config.orphan_control;
-
-    match element.element_type {
-        // Scene heading: never split, push to next page
-        ElementType::SceneHeading => {
-            BreakDecision::BreakBefore
+    /// Place a dual dialogue block: the left column's `left` elements and the
+    /// right column after them, both columns starting on the same line and
+    /// the block as tall as its taller column
+    ///
+    /// The block is kept whole; one taller than a page starts at the top of a
+    /// page and overflows it with a warning.
+    fn place_dual_block(
+        &mut self,
+        config: &PageConfig,
+        upcoming: &[Element],
+        upcoming_lines: &[Arc<LineCalculation>],
+        left: usize,
+    ) {
+        let (_, right) = dual_columns(upcoming);
+        let columns = [
+            (DualDialoguePosition::Left, 0..left),
+            (DualDialoguePosition::Right, left..left + right),
+        ];
+        let height = columns
+            .iter()
+            .map(|(_, range)| column_height(&upcoming_lines[range.clone()]))
+            .max()
+            .unwrap_or(0);
+
+        let mut space = if self.at_page_start() { 0 } else { upcoming_lines[0].space_before as u32 };
+        if space + height > self.lines_remaining(config.lines_per_page) as u32 && !self.at_page_start() {
+            self.end_page_for(PageBreakReason::OrphanPrevention, Some(&upcoming[0]), BreakRule::DualDialogue);
+            space = 0;
         }
 
-        // Character: never split, needs dialogue after it
-        ElementType::Character => {
-            BreakDecision::BreakBefore
+        let top = self.current_page.lines_used + space as u8 + 1;
+        let page = self.current_page.identifier.clone();
+        for (column, range) in columns {
+            let mut line = top;
+            for (i, idx) in range.enumerate() {
+                let (element, lines) = (&upcoming[idx], &upcoming_lines[idx]);
+                if i > 0 {
+                    line += lines.space_before;
+                }
+
+                self.current_page.elements.push(PageElement {
+                    element_id: element.id.clone(),
+                    start_line: line,
+                    line_count: lines.content_lines as u8,
+                    is_continuation: false,
+                    line_range: None,
+                    continuation_prefix: None,
+                    line_positions: None,
+                    frame: None,
+                    text: None,
+                    style_runs: None,
+                    column: Some(column),
+                    revision_marks: None,
+                    scene_number: None,
+                });
+                self.element_positions.insert(
+                    element.id.0.clone(),
+                    ElementPosition {
+                        pages: vec![page.clone()],
+                        start_line: line,
+                        end_line: line + (lines.content_lines as u8).max(1) - 1,
+                        is_split: false,
+                        line_offsets: Vec::new(),
+                    },
+                );
+                line += lines.total_lines as u8;
+            }
         }
+        self.current_page.lines_used += (space + height) as u8;
 
-        // Parenthetical: never split, keep with dialogue
-        ElementType::Parenthetical => {
-            BreakDecision::BreakBefore
+        if height > config.lines_per_page as u32 {
+            self.add_warning(
+                Some(&upcoming[0].id),
+                WarningType::DualDialogueOverflow,
+                format!("Dual dialogue requires {} lines but page only has {} lines", height, config.lines_per_page),
+            );
         }
+    }
 
-        // Dialogue: can split with MORE/CONT'D
-        ElementType::Dialogue => {
-            if !style.can_split {
-                return BreakDecision::BreakBefore;
+    /// Add the row lines `offset..offset + count` of each cell to the current page
+    fn add_row_part(&mut self, cells: &[(&Element, &LineCalculation)], offset: u32, count: u32, space: u32) {
+        let start_line = self.current_page.lines_used + space as u8 + 1;
+        let page = self.current_page.identifier.clone();
+
+        for (element, lines) in cells {
+            let cell_lines = lines.content_lines;
+            let from = cell_lines.min(offset);
+            let to = cell_lines.min(offset + count);
+            if to == from && offset > 0 {
+                continue;
             }
 
-            let min_before = orphan.dialogue_min_before_split as u32;
-            let min_after = orphan.dialogue_min_after_split as u32;
-
-            // Account for space_before in what's available
-            let available_for_content = remaining.saturating_sub(lines.space_before as u32);
-
-            // Need room for at least min_before lines
-            if available_for_content >= min_before {
-                let remaining_after_split = lines.content_lines.saturating_sub(available_for_content);
-
-                // Check if remainder is enough for min_after
-                if remaining_after_split >= min_after {
-                    // We can split at available_for_content lines
-                    // But reserve 1 line for MORE marker
-                    let split_line = available_for_content.saturating_sub(1);
-                    if split_line >= min_before {
-                        return BreakDecision::SplitAt { line: split_line };
-                    }
-                }
+            let is_split = offset > 0 || to < cell_lines;
+            self.current_page.elements.push(PageElement {
+                element_id: element.id.clone(),
+                start_line,
+                line_count: (to - from) as u8,
+                is_continuation: offset > 0,
+                line_range: is_split.then_some(LineRange { start: from, end: to }),
+                continuation_prefix: None,
+                line_positions: None,
+                frame: None,
+                text: None,
+                style_runs: None,
+                column: None,
+                revision_marks: None,
+                scene_number: None,
+            });
+
+            let end_line = start_line + ((to - from) as u8).max(1) - 1;
+            if offset == 0 {
+                self.element_positions.insert(
+                    element.id.0.clone(),
+                    ElementPosition {
+                        pages: vec![page.clone()],
+                        start_line,
+                        end_line,
+                        is_split,
+                        line_offsets: Vec::new(),
+                    },
+                );
+            } else if let Some(position) = self.element_positions.get_mut(&element.id.0) {
+                position.pages.push(page.clone());
+                position.end_line = end_line;
+                position.is_split = true;
             }
+        }
+    }
 
-            // Can't split properly, push to next page
-            BreakDecision::BreakBefore
+    /// Move an element to the top of the next page
+    ///
+    /// Inside a speech block the page ends with (MORE) and the element is
+    /// placed under a `NAME (CONT'D)` cue instead of being cut off silently.
+    fn break_before(
+        &mut self,
+        element: &Element,
+        lines: &LineCalculation,
+        config: &PageConfig,
+        continuation_mgr: &ContinuationManager,
+        rule: BreakRule,
+    ) {
+        if self.at_page_start() {
+            self.add_element(element, lines, true);
+            return;
         }
 
-        // Action: can split without continuation markers
-        ElementType::Action => {
-            if !style.can_split {
-                return BreakDecision::BreakBefore;
+        let in_block = matches!(
+            element.element_type,
+            ElementType::Parenthetical | ElementType::Dialogue | ElementType::Lyrics
+        );
+        let contd_prefix = self
+            .speaker
+            .as_deref()
+            .filter(|_| in_block)
+            .and_then(|name| continuation_mgr.contd_prefix_for(element.element_type, Some(name)));
+
+        match contd_prefix {
+            Some(prefix) => {
+                let markers = continuation_mgr.markers(element.element_type);
+                self.continue_speech_on_next_page(element, lines, config, &markers, prefix, rule);
             }
+            None => {
+                let reason = self.balance_page(config);
+                self.end_page_for(reason, Some(element), rule);
+                self.add_element(element, lines, true);
+            }
+        }
+    }
 
-            let min_before = style.min_lines_before_split as u32;
-            let min_after = style.min_lines_after_split as u32;
-
-            let available_for_content = remaining.saturating_sub(lines.space_before as u32);
-
-            if available_for_content >= min_before {
-                let remaining_after_split = lines.content_lines.saturating_sub(available_for_content);
+    /// Settle a page that is about to end early with free lines at the bottom
+    ///
+    /// With `page_balance` on, up to `max_stretch_lines` free lines are spread
+    /// over the gaps that already separate elements, never more than
+    /// `max_lines_per_gap` in one gap; otherwise the page is left short on
+    /// purpose. Returns the reason to record for the break.
+    fn balance_page(&mut self, config: &PageConfig) -> PageBreakReason {
+        let balance = &config.page_balance;
+        let free = self.lines_remaining(config.lines_per_page) as u32;
+        if !balance.enabled || free == 0 {
+            return PageBreakReason::OrphanPrevention;
+        }
 
-                if remaining_after_split >= min_after {
-                    return BreakDecision::SplitAt { line: available_for_content };
+        // An element opens a gap when a blank line already separates it from what precedes it
+        let mut previous_end = 0u8;
+        let gaps: Vec<bool> = self
+            .current_page
+            .elements
+            .iter()
+            .map(|page_element| {
+                let is_gap = previous_end > 0
+                    && page_element.line_count > 0
+                    && page_element.start_line > previous_end + 1;
+                if page_element.line_count > 0 {
+                    previous_end = previous_end.max(page_element.start_line + page_element.line_count - 1);
                 }
-            }
+                is_gap
+            })
+            .collect();
 
-            BreakDecision::BreakBefore
+        let gap_count = gaps.iter().filter(|&&g| g).count() as u32;
+        let capacity = gap_count * balance.max_lines_per_gap as u32;
+        if free > balance.max_stretch_lines as u32 || capacity < free {
+            return PageBreakReason::ShortPage;
         }
 
-        // Transition: never split
-        ElementType::Transition => {
-            BreakDecision::BreakBefore
-        }
+        // Spread evenly, giving any leftover lines to the lowest gaps
+        let page = self.current_page.identifier.clone();
+        let (each, extra) = (free / gap_count, free % gap_count);
+        let mut seen_gaps = 0;
+        let mut shift = 0u8;
+        for (page_element, is_gap) in self.current_page.elements.iter_mut().zip(gaps) {
+            if is_gap {
+                shift += (each + u32::from(seen_gaps >= gap_count - extra)) as u8;
+                seen_gaps += 1;
+            }
+            page_element.start_line += shift;
 
-        // Act break: always on new page
-        ElementType::ActBreak => {
-            BreakDecision::BreakBefore
+            if let Some(position) = self.element_positions.get_mut(&page_element.element_id.0) {
+                if position.pages.first() == Some(&page) {
+                    position.start_line += shift;
+                }
+                if position.pages.last() == Some(&page) {
+                    position.end_line += shift;
+                }
+            }
         }
+        self.current_page.lines_used = config.lines_per_page;
 
-        // Default: push to next page
-        _ => BreakDecision::BreakBefore,
+        PageBreakReason::StretchedSpacing
     }
-}
 
-/// Estimate lines needed for the next N elements
-fn estimate_following_lines(config: &PageConfig, upcoming: &[Element], count: u8) -> u32 {
-    let calc = LineCalculator::new(config);
-    let mut total = 0u32;
+    /// Record the state before placing `element`, if it opens a fresh page
+    pub(super) fn checkpoint(&self, element: usize) -> Option<Checkpoint> {
+        let fresh = self.current_page.elements.is_empty() && self.at_page_start();
+        (fresh && self.row_partner.is_none() && self.dual_partners == 0).then(|| Checkpoint {
+            element,
+            pages: self.pages.len(),
+            warnings: self.warnings.len(),
+            explanations: self.explanations.len(),
+            identifier: self.current_page.identifier.clone(),
+            page_number: self.page_number,
+            break_count: self.break_count,
+            continuation_count: self.continuation_count,
+            speaker: self.speaker.clone(),
+        })
+    }
 
-    for (i, element) in upcoming.iter().take(count as usize).enumerate() {
-        let lines = calc.calculate(element);
-        // First following element doesn't need space_before (it follows immediately)
-        if i == 0 {
-            total += lines.content_lines;
-        } else {
-            total += lines.space_before as u32 + lines.content_lines;
-        }
+    /// Drop everything placed since `checkpoint` was taken
+    pub(super) fn rewind(&mut self, checkpoint: &Checkpoint) {
+        self.pages.truncate(checkpoint.pages);
+        self.warnings.truncate(checkpoint.warnings);
+        self.explanations.truncate(checkpoint.explanations);
+        self.current_page = Page::new(checkpoint.identifier.clone());
+        self.start_page();
+        self.page_number = checkpoint.page_number;
+        self.break_count = checkpoint.break_count;
+        self.continuation_count = checkpoint.continuation_count;
+        self.speaker = checkpoint.speaker.clone();
+
+        // Elements placed before the checkpoint start on the pages kept
+        let kept: HashSet<&PageIdentifier> = self.pages.iter().map(|page| &page.identifier).collect();
+        self.element_positions
+            .retain(|_, position| position.pages.first().is_some_and(|page| kept.contains(page)));
     }
 
-    total
-}
+    /// Remove and return finished pages
+    ///
+    /// While the open page is still empty the last finished page is held back,
+    /// since trailing anchors may yet be moved onto it.
+    pub(super) fn take_completed_pages(&mut self) -> Vec<Page> {
+        let keep = usize::from(self.at_page_start() && !self.pages.is_empty());
+        let pages: Vec<Page> = self.pages.drain(..self.pages.len() - keep).collect();
+        self.emitted_pages += pages.len() as u32;
+        pages
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub(super) fn finalize(mut self, timing_us: u64, element_count: usize) -> PaginationResult {
+        // Anchors trailing onto an otherwise empty page belong at the end of the previous one
+        if self.at_page_start() && !self.pages.is_empty() {
+            let last_page = self.pages.last_mut().unwrap();
+            for mut page_element in self.current_page.elements.drain(..) {
+                page_element.start_line = last_page.lines_used + 1;
+                if let Some(position) = self.element_positions.get_mut(&page_element.element_id.0) {
+                    position.pages = vec![last_page.identifier.clone()];
+                    position.start_line = page_element.start_line;
+                    position.end_line = page_element.start_line;
+                }
+                last_page.elements.push(page_element);
+            }
+        }
 
-    fn make_element(id: &str, element_type: ElementType, content: &str) -> Element {
-        Element::new(id, element_type, content)
-    }
+        // Add the last page if it has content
+        if !self.current_page.elements.is_empty() {
+            self.current_page.page_number = self.numbering.label(&self.current_page.identifier);
+            self.pages.push(self.current_page);
+        }
 
-    fn make_dialogue(id: &str, content: &str, character: &str) -> Element {
-        Element::new(id, ElementType::Dialogue, content)
-            .with_character_name(character)
-    }
+        let page_count = self.emitted_pages + self.pages.len() as u32;
 
-    #[test]
-    fn test_basic_pagination() {
-        let config = PageConfig::feature_film();
+        PaginationResult {
+            pages: self.pages,
+            element_positions: self.element_positions,
+            warnings: self.warnings,
+            scene_lengths: Vec::new(),
+            scene_index: Vec::new(),
+            stats: PaginationStats {
+                page_count,
+                element_count,
+                break_count: self.break_count,
+                continuation_count: self.continuation_count,
+                timing_us,
+                acts: Vec::new(),
+                fingerprint: String::new(),
+            },
+            title_page: None,
+            break_explanations: self.explanations,
+        }
+    }
+}
+
+/// Core pagination function - pure, deterministic, no side effects
+pub fn paginate(elements: &[Element], config: &PageConfig) -> PaginationResult {
+    paginate_with_options(elements, config, &RenderOptions::default())
+}
+
+/// Paginate and attach the extra rendering data selected in `options`
+pub fn paginate_with_options(
+    elements: &[Element],
+    config: &PageConfig,
+    options: &RenderOptions,
+) -> PaginationResult {
+    paginate_with_clock(elements, config, options, default_clock().as_ref())
+}
+
+/// Paginate and keep only the pages that differ from `previous`, for
+/// returning incremental edits without resending unchanged pages
+pub fn paginate_delta(elements: &[Element], config: &PageConfig, previous: &PaginationResult) -> PaginationDelta {
+    PaginationDelta::between(previous, paginate(elements, config))
+}
+
+/// Paginate, measuring `stats.timing_us` with the supplied clock
+pub fn paginate_with_clock(
+    elements: &[Element],
+    config: &PageConfig,
+    options: &RenderOptions,
+    clock: &dyn Clock,
+) -> PaginationResult {
+    paginate_cached(elements, config, options, clock, &mut LineCache::new())
+}
+
+/// Paginate, reusing line calculations from `cache` where content and style are unchanged
+pub(crate) fn paginate_cached(
+    elements: &[Element],
+    config: &PageConfig,
+    options: &RenderOptions,
+    clock: &dyn Clock,
+    cache: &mut LineCache,
+) -> PaginationResult {
+    let started_us = clock.now_us();
+    cache.begin_run();
+
+    let mut run = PaginationRun::new(elements, config);
+    while !run.advance(cache, usize::MAX) {}
+    cache.end_run();
+
+    // Timing is filled in once the post-pagination passes have run
+    let mut result = run.finish(options);
+    result.stats.timing_us = clock.now_us().saturating_sub(started_us);
+    result
+}
+
+/// A pagination pass that can be driven a chunk of elements at a time
+///
+/// Each element is first wrapped into the line table, then placed on a page;
+/// `advance` performs up to `max_elements` steps of whichever phase is current,
+/// so callers can interleave other work between chunks.
+pub struct PaginationRun<'a> {
+    elements: &'a [Element],
+    config: &'a PageConfig,
+    line_calc: LineCalculator<'a>,
+    continuation_mgr: ContinuationManager<'a>,
+    line_table: Vec<Arc<LineCalculation>>,
+    state: PaginationState,
+    placed: usize,
+    title_page: Option<Page>,
+}
+
+impl<'a> PaginationRun<'a> {
+    pub fn new(elements: &'a [Element], config: &'a PageConfig) -> Self {
+        Self {
+            elements,
+            config,
+            line_calc: LineCalculator::new(config),
+            continuation_mgr: ContinuationManager::new(config),
+            line_table: Vec::with_capacity(elements.len()),
+            state: PaginationState::new(config),
+            placed: 0,
+            title_page: None,
+        }
+    }
+
+    /// Lay out a title page as the result's page 0 (see `layout_title_page`)
+    pub fn with_title_page(mut self, title_page: &TitlePage) -> Self {
+        self.title_page = Some(layout_title_page(title_page, self.config));
+        self
+    }
+
+    /// Keep the page starts of a locked draft (see `paginate_locked`)
+    pub fn with_locked_pages(mut self, locks: &LockedPageSet) -> Self {
+        if !locks.is_empty() {
+            self.state.locks = Some(LockPlan::new(self.elements, locks));
+        }
+        self
+    }
+
+    /// Process up to `max_elements` elements; returns true once every element is placed
+    pub fn advance(&mut self, cache: &mut LineCache, max_elements: usize) -> bool {
+        let elements = self.elements;
+
+        // Wrap every element before placing any; break decisions and
+        // keep-with-next lookahead read from this table instead of re-wrapping
+        if self.line_table.len() < elements.len() {
+            let end = self.line_table.len().saturating_add(max_elements).min(elements.len());
+            let chunk = &elements[self.line_table.len()..end];
+            let lines = cache.get_or_calculate_all(&self.line_calc, chunk);
+            self.line_table.extend(lines);
+            return self.is_done();
+        }
+
+        let end = self.placed.saturating_add(max_elements).min(elements.len());
+        for idx in self.placed..end {
+            self.place(idx);
+        }
+        self.placed = end;
+
+        self.is_done()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.placed == self.elements.len()
+    }
+
+    /// Fraction of the work completed, from 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        if self.elements.is_empty() {
+            return 1.0;
+        }
+        (self.line_table.len() + self.placed) as f64 / (2 * self.elements.len()) as f64
+    }
+
+    /// Close the last page and run the post-pagination passes
+    ///
+    /// `stats.timing_us` is left at zero for the caller to fill in.
+    pub fn finish(self, options: &RenderOptions) -> PaginationResult {
+        let mut result = finish_pagination(self.state, self.elements, self.config, &self.line_table, options);
+        result.title_page = self.title_page;
+        result
+    }
+
+    /// Place one element on the current page, breaking or splitting as needed
+    fn place(&mut self, idx: usize) {
+        self.state.place(
+            self.config,
+            &self.continuation_mgr,
+            &self.elements[idx..],
+            &self.line_table[idx..],
+        );
+    }
+}
+
+/// Close the last page of a finished placement pass and run the
+/// post-pagination passes over the whole document
+pub(super) fn finish_pagination(
+    state: PaginationState,
+    elements: &[Element],
+    config: &PageConfig,
+    line_table: &[Arc<LineCalculation>],
+    options: &RenderOptions,
+) -> PaginationResult {
+    let mut result = state.finalize(0, elements.len());
+    if !options.explain {
+        result.break_explanations.clear();
+    }
+    result.scene_lengths = calculate_scene_lengths(elements, &result.pages, config);
+    result.scene_index = build_scene_index(elements, &result.element_positions);
+    result.stats.acts = calculate_act_stats(elements, &result.pages, &result.element_positions);
+    for (element, lines) in elements.iter().zip(line_table) {
+        if let Some(position) = result.element_positions.get_mut(&element.id.0) {
+            position.line_offsets = line_start_offsets(&element.content, &lines.wrapped_lines);
+        }
+    }
+    let scene_numbers = number_scenes(elements);
+    for page_element in result.pages.iter_mut().flat_map(|page| &mut page.elements) {
+        page_element.scene_number = scene_numbers.get(page_element.element_id.0.as_str()).cloned();
+    }
+    apply_render_options(&mut result.pages, elements, config, options);
+    let by_id: HashMap<&str, &Element> = elements.iter().map(|e| (e.id.0.as_str(), e)).collect();
+    for page in &mut result.pages {
+        page.content_hash = page.compute_content_hash(&by_id);
+    }
+    result.stats.fingerprint = result.fingerprint();
+    result
+}
+
+/// Decide how to handle an element at a page boundary
+fn decide_break(
+    element: &Element,
+    lines: &LineCalculation,
+    at_page_start: bool,
+    remaining: u32,
+    config: &PageConfig,
+    upcoming: &[Element],
+    upcoming_lines: &[Arc<LineCalculation>],
+) -> BreakDecision {
+    let style = config.resolved_style(element);
+    let space_before = if at_page_start { 0 } else { lines.space_before as u32 };
+    let total_needed = space_before + lines.total_lines;
+
+    // A cue or parenthetical decides for the speech it introduces, so the
+    // block never ends a page without the minimum dialogue beneath it
+    if matches!(element.element_type, ElementType::Character | ElementType::Parenthetical) {
+        if total_needed > remaining {
+            return BreakDecision::BreakBefore(BreakRule::CannotSplit);
+        }
+        let (block, minimum) = speech_block_lines(config, &upcoming[1..], &upcoming_lines[1..]);
+        if total_needed + block <= remaining || total_needed + minimum <= remaining {
+            return BreakDecision::Fits;
+        }
+        return BreakDecision::BreakBefore(BreakRule::SpeechBlock);
+    }
+
+    // If it fits, we're done
+
+    if total_needed <= remaining {
+        // A scene heading needs the opening lines of its scene under it
+        if element.element_type == ElementType::SceneHeading && style.keep_with_next {
+            let following = scene_opening_lines(config, &upcoming[1..], &upcoming_lines[1..]);
+            if total_needed + following > remaining {
+                return BreakDecision::BreakBefore(BreakRule::SceneOpening);
+            }
+        } else if style.keep_with_next && upcoming.len() > 1 {
+            // Check if we have room for this + required following lines
+            let following_lines = estimate_following_lines(
+                config,
+                &upcoming[1..],
+                &upcoming_lines[1..],
+                style.keep_with_next_lines,
+            );
+            if total_needed + following_lines > remaining {
+                return BreakDecision::BreakBefore(BreakRule::KeepWithNext);
+            }
+        }
+
+        // A following transition can't open the next page alone, so send this
+        // element's last lines over with it (or the whole element if it can't split)
+        let bound = bound_following_lines(config, &upcoming[1..], &upcoming_lines[1..]);
+        if bound > 0 && total_needed + bound > remaining && !at_page_start {
+            return split_before_bound(element, lines, remaining, config);
+        }
+        return BreakDecision::Fits;
+    }
+
+    let orphan = &config.orphan_control;
+
+    match element.element_type {
+        // Scene heading: never split, push to next page
+        ElementType::SceneHeading => {
+            BreakDecision::BreakBefore(BreakRule::CannotSplit)
+        }
+
+        // Character: never split, needs dialogue after it
+        ElementType::Character => {
+            BreakDecision::BreakBefore(BreakRule::CannotSplit)
+        }
+
+        // Parenthetical: never split, keep with dialogue
+        ElementType::Parenthetical => {
+            BreakDecision::BreakBefore(BreakRule::CannotSplit)
+        }
+
+        // Dialogue and sung lyrics: can split with MORE/CONT'D
+        ElementType::Dialogue | ElementType::Lyrics => {
+            if !style.can_split {
+                return BreakDecision::BreakBefore(BreakRule::CannotSplit);
+            }
+
+            let min_before = orphan.dialogue_min_before_split as u32;
+            let min_after = orphan.dialogue_min_after_split as u32;
+
+            // Account for space_before in what's available
+            let available_for_content = remaining.saturating_sub(lines.space_before as u32);
+
+            // Reserve 1 line for the MORE marker, and pull lines to the next
+            // page when the natural break would leave fewer than min_after
+            let more = config.continuation_style.for_type(element.element_type).more_lines();
+            let available = available_for_content.saturating_sub(more);
+            let split_line = available.min(lines.content_lines.saturating_sub(min_after));
+            if split_line >= min_before.max(1) {
+                return BreakDecision::SplitAt { line: split_line, orphaned: false };
+            }
+
+            // Can't split properly, push to next page
+            BreakDecision::BreakBefore(split_rule(available, min_before))
+        }
+
+        // Action: can split, with continuation markers only if configured
+        ElementType::Action => {
+            if !style.can_split {
+                return BreakDecision::BreakBefore(BreakRule::CannotSplit);
+            }
+
+            let min_before = style.min_lines_before_split as u32;
+            let min_after = style.min_lines_after_split as u32;
+
+            let more = config.continuation_style.for_type(element.element_type).more_lines();
+            let available_for_content = remaining.saturating_sub(space_before + more);
+
+            // Widow control: when the natural break would leave too few lines
+            // for the next page, pull lines from this page to make up the minimum
+            let split_line = available_for_content.min(lines.content_lines.saturating_sub(min_after));
+            if split_line >= min_before.max(1) {
+                return BreakDecision::SplitAt { line: split_line, orphaned: false };
+            }
+
+            // A fresh page can't help an element that fills one by itself;
+            // split where the page ends and report the stranded lines
+            if at_page_start && available_for_content > 0 {
+                return BreakDecision::SplitAt { line: available_for_content, orphaned: true };
+            }
+
+            BreakDecision::BreakBefore(split_rule(available_for_content, min_before))
+        }
+
+        // Transition: never split
+        ElementType::Transition => {
+            BreakDecision::BreakBefore(BreakRule::CannotSplit)
+        }
+
+        // Act break: always on new page
+        ElementType::ActBreak => {
+            BreakDecision::BreakBefore(BreakRule::CannotSplit)
+        }
+
+        // Default: push to next page
+        _ => BreakDecision::BreakBefore(BreakRule::CannotSplit),
+    }
+}
+
+/// Lines of the speech block following a cue or parenthetical
+///
+/// Returns the lines of the whole block and the least of it that must share
+/// the page with the opening element: any parentheticals up to the first
+/// dialogue, `character_min_dialogue_lines` of that dialogue (all of it when
+/// it is too short to split), and a line for (MORE) if the block continues.
+fn speech_block_lines(
+    config: &PageConfig,
+    following: &[Element],
+    following_lines: &[Arc<LineCalculation>],
+) -> (u32, u32) {
+    let orphan = &config.orphan_control;
+    let mut block = 0u32;
+    let mut minimum: Option<u32> = None;
+    let mut leading = 0u32;
+
+    let speech = following
+        .iter()
+        .zip(following_lines)
+        .filter(|(e, _)| config.is_visible(e.element_type))
+        .take_while(|(e, _)| {
+            matches!(
+                e.element_type,
+                ElementType::Parenthetical | ElementType::Dialogue | ElementType::Lyrics
+            )
+        });
+
+    for (element, lines) in speech {
+        let needed = lines.space_before as u32 + lines.total_lines;
+        block += needed;
+
+        if minimum.is_some() {
+            continue;
+        }
+        if element.element_type == ElementType::Parenthetical {
+            leading += needed;
+            continue;
+        }
+
+        let content = lines.content_lines;
+        let floor = (orphan.character_min_dialogue_lines as u32).max(orphan.dialogue_min_before_split as u32);
+        let splittable = content >= floor + orphan.dialogue_min_after_split as u32;
+        let dialogue = if splittable { floor } else { lines.total_lines };
+        minimum = Some(leading + lines.space_before as u32 + dialogue);
+    }
+
+    // A block with no dialogue needs all of its parentheticals
+    let minimum = minimum.unwrap_or(leading);
+    let more = if minimum < block {
+        config.continuation_style.for_type(ElementType::Dialogue).more_lines()
+    } else {
+        0
+    };
+    (block, minimum + more)
+}
+
+/// Lines that must follow a scene heading on its page
+///
+/// Takes `scene_heading_min_following` content lines from what follows, with
+/// the spacing between elements: a splittable element gives up as few as its
+/// `min_lines_before_split` allows, an unsplittable one counts whole, and a
+/// character cue brings the minimum of its speech block.
+fn scene_opening_lines(
+    config: &PageConfig,
+    following: &[Element],
+    following_lines: &[Arc<LineCalculation>],
+) -> u32 {
+    let needed = config.orphan_control.scene_heading_min_following as u32;
+    let mut total = 0u32;
+    let mut content = 0u32;
+
+    for (idx, (element, lines)) in following.iter().zip(following_lines).enumerate() {
+        if content >= needed {
+            break;
+        }
+        if !config.is_visible(element.element_type) {
+            continue;
+        }
+        let space = lines.space_before as u32;
+
+        if element.element_type == ElementType::Character {
+            let (_, minimum) = speech_block_lines(config, &following[idx + 1..], &following_lines[idx + 1..]);
+            return total + space + lines.total_lines + minimum;
+        }
+
+        let style = config.resolved_style(element);
+        let take = if style.can_split {
+            (needed - content).max(style.min_lines_before_split as u32).min(lines.content_lines)
+        } else {
+            lines.content_lines
+        };
+        let whole = take == lines.content_lines;
+        total += space + take + if whole { lines.space_after as u32 } else { 0 };
+        content += take;
+    }
+
+    total
+}
+
+/// Lengths of the left and right columns of a dual dialogue block opening `upcoming`
+///
+/// A block is a run of left-column elements followed directly by a run of
+/// right-column elements; a right run of zero means there is no block.
+pub(crate) fn dual_columns(upcoming: &[Element]) -> (usize, usize) {
+    let run = |elements: &[Element], column| elements.iter().take_while(|e| e.dual_column() == Some(column)).count();
+    let left = run(upcoming, DualDialoguePosition::Left);
+    if left == 0 {
+        return (0, 0);
+    }
+    (left, run(&upcoming[left..], DualDialoguePosition::Right))
+}
+
+/// Lines taken by one column of a dual dialogue block, from its first content line
+fn column_height(lines: &[Arc<LineCalculation>]) -> u32 {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, lines)| if i == 0 { 0 } else { lines.space_before as u32 } + lines.total_lines)
+        .sum()
+}
+
+/// Parse "12" or "12-14" into an inclusive page range
+pub(super) fn parse_page_range(text: &str) -> Option<(u32, u32)> {
+    match text.split_once('-') {
+        Some((first, last)) => Some((first.trim().parse().ok()?, last.trim().parse().ok()?)),
+        None => {
+            let page = text.trim().parse().ok()?;
+            Some((page, page))
+        }
+    }
+}
+
+/// Name for the CONT'D cue of a speech block
+///
+/// Drops extensions such as "(V.O.)" from the cue.
+pub(crate) fn cue_name(cue: &Element) -> String {
+    let name = cue.content.split('(').next().unwrap_or(&cue.content).trim();
+    name.trim_end_matches('^').trim().to_uppercase()
+}
+
+/// Lines of the next visible element when it must stay with this one
+///
+/// Zero unless that element has `keep_with_previous` set.
+fn bound_following_lines(
+    config: &PageConfig,
+    upcoming: &[Element],
+    upcoming_lines: &[Arc<LineCalculation>],
+) -> u32 {
+    upcoming
+        .iter()
+        .zip(upcoming_lines)
+        .find(|(e, _)| config.is_visible(e.element_type))
+        .filter(|(e, _)| config.resolved_style(e).keep_with_previous)
+        .map_or(0, |(_, lines)| lines.space_before as u32 + lines.content_lines)
+}
+
+/// Break an element that fits so its tail moves to the next page
+///
+/// Used when the following element must not open a page by itself. Keeps the
+/// element's split minimums; pushes it whole when they can't be met.
+fn split_before_bound(
+    element: &Element,
+    lines: &LineCalculation,
+    remaining: u32,
+    config: &PageConfig,
+) -> BreakDecision {
+    let style = config.resolved_style(element);
+    if !style.can_split {
+        return BreakDecision::BreakBefore(BreakRule::KeepWithPrevious);
+    }
+
+    let orphan = &config.orphan_control;
+    let (min_before, min_after) = match element.element_type {
+        ElementType::Dialogue | ElementType::Lyrics => {
+            (orphan.dialogue_min_before_split as u32, orphan.dialogue_min_after_split as u32)
+        }
+        ElementType::Action => (style.min_lines_before_split as u32, style.min_lines_after_split as u32),
+        _ => return BreakDecision::BreakBefore(BreakRule::KeepWithPrevious),
+    };
+
+    // Leave room for a MORE marker
+    let more = config.continuation_style.for_type(element.element_type).more_lines();
+    let available = remaining.saturating_sub(lines.space_before as u32 + more);
+
+    let split_line = available.min(lines.content_lines.saturating_sub(min_after.max(1)));
+    if split_line >= min_before.max(1) {
+        BreakDecision::SplitAt { line: split_line, orphaned: false }
+    } else {
+        BreakDecision::BreakBefore(BreakRule::KeepWithPrevious)
+    }
+}
+
+/// Which split minimum an element that couldn't split failed: the lines
+/// before the break when fewer than `min_before` fit, otherwise the lines
+/// that would be left after it
+fn split_rule(available: u32, min_before: u32) -> BreakRule {
+    if available < min_before.max(1) { BreakRule::SplitMinBeforeUnmet } else { BreakRule::SplitMinAfterUnmet }
+}
+
+/// Estimate lines needed for the next N elements
+fn estimate_following_lines(
+    config: &PageConfig,
+    upcoming: &[Element],
+    upcoming_lines: &[Arc<LineCalculation>],
+    count: u8,
+) -> u32 {
+    let mut total = 0u32;
+
+    let visible = upcoming
+        .iter()
+        .zip(upcoming_lines)
+        .filter(|(e, _)| config.is_visible(e.element_type))
+        .map(|(_, lines)| lines);
+    for (i, lines) in visible.take(count as usize).enumerate() {
+        // First following element doesn't need space_before (it follows immediately)
+        if i == 0 {
+            total += lines.content_lines;
+        } else {
+            total += lines.space_before as u32 + lines.content_lines;
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ContinuedHeader, ElementStyleOverride, PageBreak, ReusedPage};
+
+    fn make_element(id: &str, element_type: ElementType, content: &str) -> Element {
+        Element::new(id, element_type, content)
+    }
+
+    fn make_dialogue(id: &str, content: &str, character: &str) -> Element {
+        Element::new(id, ElementType::Dialogue, content)
+            .with_character_name(character)
+    }
+
+    #[test]
+    fn test_basic_pagination() {
+        let config = PageConfig::feature_film();
         let elements = vec![
             make_element("1", ElementType::SceneHeading, "INT. OFFICE - DAY"),
             make_element("2", ElementType::Action, "A busy office."),
@@ -512,17 +1716,900 @@ mod tests {
     }
 
     #[test]
-    fn test_determinism() {
+    fn test_notes_take_no_space() {
         let config = PageConfig::feature_film();
-        let elements: Vec<Element> = (0..50)
-            .map(|i| make_element(&i.to_string(), ElementType::Action, "Some action text here."))
-            .collect();
+        let elements = vec![
+            make_element("1", ElementType::Action, "First line."),
+            make_element("2", ElementType::Note, "Rework this beat."),
+            make_element("3", ElementType::Action, "Second line."),
+        ];
 
-        let result1 = paginate(&elements, &config);
-        let result2 = paginate(&elements, &config);
+        let result = paginate(&elements, &config);
 
-        assert_eq!(result1.stats.page_count, result2.stats.page_count);
-        assert_eq!(result1.pages.len(), result2.pages.len());
+        let note_pos = result.element_positions.get("2").unwrap();
+        let action_pos = result.element_positions.get("3").unwrap();
+        assert_eq!(note_pos.start_line, 2);
+        // Action keeps its normal spacing as though the note were absent
+        assert_eq!(action_pos.start_line, 3);
+        assert_eq!(result.pages[0].lines_used, 3);
+    }
+
+    #[test]
+    fn test_notes_consume_space_when_shown() {
+        let mut config = PageConfig::feature_film();
+        config.show_notes = true;
+        let elements = vec![
+            make_element("1", ElementType::Action, "First line."),
+            make_element("2", ElementType::Note, "Rework this beat."),
+            make_element("3", ElementType::Action, "Second line."),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        let action_pos = result.element_positions.get("3").unwrap();
+        assert_eq!(action_pos.start_line, 7);
+    }
+
+    #[test]
+    fn test_trailing_note_stays_on_last_page() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            make_element("1", ElementType::Action, "Content.").with_force_page_break(),
+            make_element("2", ElementType::Note, "Trailing note."),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.stats.page_count, 1);
+        let note_pos = result.element_positions.get("2").unwrap();
+        assert_eq!(note_pos.pages[0], PageIdentifier::Sequential(1));
+    }
+
+    #[test]
+    fn test_section_and_synopsis_not_printed() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            make_element("1", ElementType::Section, "ACT ONE"),
+            make_element("2", ElementType::Synopsis, "Sarah arrives at work."),
+            make_element("3", ElementType::SceneHeading, "INT. OFFICE - DAY"),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.element_positions.get("3").unwrap().start_line, 1);
+        assert_eq!(result.pages[0].lines_used, 1);
+    }
+
+    fn filler(lines: usize) -> Element {
+        make_element("f", ElementType::Action, "Filler line.\n".repeat(lines).trim_end())
+    }
+
+    #[test]
+    fn test_speech_block_splits_under_cue() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            filler(45),
+            make_element("c", ElementType::Character, "SARAH (V.O.)"),
+            make_element("p", ElementType::Parenthetical, "(quietly)"),
+            make_element("d", ElementType::Dialogue, "Speech line.\n".repeat(12).trim_end()),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        let first = &result.pages[0];
+        assert_eq!(first.elements.len(), 4);
+        assert_eq!(first.bottom_continuation.as_deref(), Some("(MORE)"));
+
+        let rest = &result.pages[1].elements[0];
+        assert_eq!(rest.element_id.0, "d");
+        assert_eq!(rest.continuation_prefix.as_deref(), Some("SARAH (CONT'D)"));
+        assert!(rest.line_count >= 2);
+    }
+
+    #[test]
+    fn test_hit_testing_split_dialogue() {
+        let config = PageConfig::feature_film();
+        let speech = "Speech line.\n".repeat(12);
+        let elements = vec![
+            filler(45),
+            make_element("c", ElementType::Character, "SARAH"),
+            make_element("d", ElementType::Dialogue, speech.trim_end()),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        let rest = &result.pages[1].elements[0];
+        let index = rest.line_range.as_ref().unwrap().start;
+        let offset = index * "Speech line.\n".len() as u32;
+        let page = PageIdentifier::Sequential(2);
+
+        assert_eq!(result.element_at(&page, rest.start_line), Some((ElementId::new("d"), offset)));
+        // The CONT'D line above resolves to the same content
+        assert_eq!(result.element_at(&page, rest.start_line - 1), Some((ElementId::new("d"), offset)));
+        assert_eq!(result.line_for_offset("d", offset + 3), Some((page.clone(), rest.start_line)));
+        assert_eq!(result.locate("d", offset + 3), Some((page, rest.start_line, 3)));
+
+        let first_line = result.pages[0].elements[2].start_line;
+        assert_eq!(result.line_for_offset("d", 0), Some((PageIdentifier::Sequential(1), first_line)));
+        assert_eq!(result.element_at(&PageIdentifier::Sequential(1), 0), None);
+    }
+
+    #[test]
+    fn test_action_continuation_markers() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 10;
+        config.continuation_style.by_type.insert(
+            ElementType::Action,
+            TypeContinuationStyle {
+                more_marker: Some("(CONTINUED)".to_string()),
+                contd_marker: Some("CONTINUED:".to_string()),
+                ..Default::default()
+            },
+        );
+        let elements = vec![make_element("1", ElementType::Action, "Line.\n".repeat(14).trim_end())];
+
+        let result = paginate(&elements, &config);
+
+        // The MORE marker takes the tenth line of the first page
+        assert_eq!(result.pages[0].elements[0].line_count, 9);
+        assert_eq!(result.pages[0].bottom_continuation.as_deref(), Some("(CONTINUED)"));
+        let rest = &result.pages[1].elements[0];
+        assert_eq!(rest.continuation_prefix.as_deref(), Some("CONTINUED:"));
+        assert_eq!(rest.start_line, 2);
+    }
+
+    #[test]
+    fn test_margin_markers_keep_content_lines() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 10;
+        config.continuation_style.by_type.insert(
+            ElementType::Dialogue,
+            TypeContinuationStyle {
+                more_marker: Some("(MORE)".to_string()),
+                contd_marker: Some("(CONT'D)".to_string()),
+                indent: Some(40),
+                consumes_line: false,
+            },
+        );
+        let elements = vec![
+            make_element("c", ElementType::Character, "SARAH"),
+            make_element("d", ElementType::Dialogue, "Speech.\n".repeat(14).trim_end()).with_character_name("SARAH"),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.pages[0].elements[1].line_count, 9);
+        assert_eq!(result.pages[0].lines_used, 10);
+        let rest = &result.pages[1].elements[0];
+        assert_eq!(rest.continuation_prefix.as_deref(), Some("SARAH (CONT'D)"));
+        assert_eq!(rest.start_line, 1);
+        assert_eq!(rest.line_count, 5);
+    }
+
+    #[test]
+    fn test_cue_and_parenthetical_not_stranded() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            filler(51),
+            make_element("c", ElementType::Character, "SARAH"),
+            make_element("p", ElementType::Parenthetical, "(quietly)"),
+            make_element("d", ElementType::Dialogue, "Speech line.\n".repeat(6).trim_end()),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.pages[0].elements.len(), 1);
+        let second: Vec<&str> = result.pages[1].elements.iter().map(|e| e.element_id.0.as_str()).collect();
+        assert_eq!(second, vec!["c", "p", "d"]);
+        assert_eq!(result.pages[1].elements[0].start_line, 1);
+    }
+
+    #[test]
+    fn test_block_break_at_parenthetical_continues_cue() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            filler(49),
+            make_element("c", ElementType::Character, "SARAH"),
+            make_element("d1", ElementType::Dialogue, "Short."),
+            make_element("p", ElementType::Parenthetical, "(beat)"),
+            make_element("d2", ElementType::Dialogue, "Speech line.\n".repeat(5).trim_end()),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.pages[0].bottom_continuation.as_deref(), Some("(MORE)"));
+        let paren = &result.pages[1].elements[0];
+        assert_eq!(paren.element_id.0, "p");
+        assert_eq!(paren.continuation_prefix.as_deref(), Some("SARAH (CONT'D)"));
+        assert_eq!(paren.start_line, 2);
+        assert_eq!(result.pages[1].elements[1].start_line, 3);
+    }
+
+    #[test]
+    fn test_av_row_cells_side_by_side() {
+        let config = PageConfig::av_script();
+        let elements = vec![
+            make_element("v1", ElementType::AvVideo, "WIDE SHOT: kitchen.\nPAN to window."),
+            make_element("a1", ElementType::AvAudio, "VO: Mornings.\nSFX: kettle.\nMUSIC: up.\nVO: Fresh."),
+            make_element("v2", ElementType::AvVideo, "CU: mug."),
+            make_element("a2", ElementType::AvAudio, "VO: Start right."),
+        ];
+
+        let result = paginate(&elements, &config);
+        let page = &result.pages[0];
+
+        assert_eq!(page.elements[0].start_line, 1);
+        assert_eq!(page.elements[1].start_line, 1);
+        assert_eq!(page.elements[1].line_count, 4);
+
+        // The next row starts below the taller audio cell
+        assert_eq!(page.elements[2].start_line, 6);
+        assert_eq!(page.elements[3].start_line, 6);
+        assert_eq!(page.lines_used, 6);
+    }
+
+    fn dual_block(left_lines: usize, right_lines: usize) -> Vec<Element> {
+        let speech = |id: &str, name: &str, lines: usize, column| {
+            vec![
+                make_element(&format!("{}c", id), ElementType::Character, name).with_dual_dialogue_position(column),
+                make_dialogue(&format!("{}d", id), "Words.\n".repeat(lines).trim_end(), name)
+                    .with_dual_dialogue_position(column),
+            ]
+        };
+        let mut block = speech("l", "SARAH", left_lines, DualDialoguePosition::Left);
+        block.extend(speech("r", "JOHN", right_lines, DualDialoguePosition::Right));
+        block
+    }
+
+    #[test]
+    fn test_dual_dialogue_columns_side_by_side() {
+        let config = PageConfig::feature_film();
+        let mut elements = vec![make_element("a", ElementType::Action, "They both talk.")];
+        elements.extend(dual_block(2, 4));
+        elements.push(make_element("z", ElementType::Action, "Silence."));
+
+        let result = paginate(&elements, &config);
+        let page = &result.pages[0];
+
+        let placed: Vec<(&str, u8, Option<DualDialoguePosition>)> =
+            page.elements.iter().map(|e| (e.element_id.0.as_str(), e.start_line, e.column)).collect();
+        assert_eq!(
+            placed,
+            vec![
+                ("a", 1, None),
+                ("lc", 3, Some(DualDialoguePosition::Left)),
+                ("ld", 4, Some(DualDialoguePosition::Left)),
+                ("rc", 3, Some(DualDialoguePosition::Right)),
+                ("rd", 4, Some(DualDialoguePosition::Right)),
+                // Below the taller right column
+                ("z", 9, None),
+            ]
+        );
+        assert_eq!(result.element_positions["rd"].end_line, 7);
+    }
+
+    #[test]
+    fn test_dual_dialogue_block_kept_whole() {
+        let config = PageConfig::feature_film();
+        let mut elements = vec![filler(49)];
+        elements.extend(dual_block(1, 5));
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.pages.len(), 2);
+        assert_eq!(result.pages[0].elements.len(), 1);
+        assert_eq!(result.pages[1].elements.len(), 4);
+        assert!(result.pages[1].elements.iter().all(|e| e.column.is_some()));
+        assert_eq!(result.pages[1].lines_used, 6);
+    }
+
+    #[test]
+    fn test_dual_dialogue_overflow_warning() {
+        let config = PageConfig::feature_film();
+        let result = paginate(&dual_block(60, 1), &config);
+
+        assert!(result.warnings.iter().any(|w| w.warning_type == WarningType::DualDialogueOverflow));
+    }
+
+    #[test]
+    fn test_av_row_flows_across_pages() {
+        let config = PageConfig::av_script();
+        let elements = vec![
+            filler(50),
+            make_element("v", ElementType::AvVideo, "SHOT one.\nSHOT two.\nSHOT three."),
+            make_element("a", ElementType::AvAudio, "Audio line.\n".repeat(10).trim_end()),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        let first: Vec<(&str, u8)> = result.pages[0].elements[1..]
+            .iter()
+            .map(|e| (e.element_id.0.as_str(), e.line_count))
+            .collect();
+        assert_eq!(first, vec![("v", 3), ("a", 4)]);
+
+        let second = &result.pages[1].elements;
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].line_count, 6);
+        assert!(second[0].is_continuation);
+
+        let position = &result.element_positions["a"];
+        assert_eq!(position.pages.len(), 2);
+        assert!(position.is_split);
+        assert!(!result.element_positions["v"].is_split);
+    }
+
+    #[test]
+    fn test_dialogue_split_across_three_pages() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            make_element("c", ElementType::Character, "SARAH"),
+            make_element("d", ElementType::Dialogue, "Speech line.\n".repeat(120).trim_end()),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.page_count(), 3);
+        assert_eq!(result.pages[0].bottom_continuation.as_deref(), Some("(MORE)"));
+        assert_eq!(result.pages[1].bottom_continuation.as_deref(), Some("(MORE)"));
+        assert!(result.pages[2].bottom_continuation.is_none());
+        for page in &result.pages[1..] {
+            assert_eq!(page.elements[0].continuation_prefix.as_deref(), Some("SARAH (CONT'D)"));
+            assert!(page.lines_used <= config.lines_per_page);
+        }
+
+        let parts: u32 = result
+            .pages
+            .iter()
+            .flat_map(|p| &p.elements)
+            .filter(|e| e.element_id.0 == "d")
+            .map(|e| e.line_count as u32)
+            .sum();
+        assert_eq!(parts, 120);
+        assert_eq!(result.element_positions["d"].pages.len(), 3);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_dialogue_split_at_sentence_end() {
+        let mut config = PageConfig::feature_film();
+        config.orphan_control.prefer_sentence_breaks = true;
+        let filler = "Filler line.\n".repeat(44);
+        let speech = "Then you went\nright ahead\nanyway.\n".repeat(6);
+        let elements = vec![
+            make_element("1", ElementType::Action, filler.trim_end()),
+            make_element("2", ElementType::Character, "SARAH"),
+            make_element("3", ElementType::Dialogue, speech.trim_end()).with_character_name("SARAH"),
+        ];
+        let options = RenderOptions { include_text: true, ..Default::default() };
+
+        let result = paginate_with_options(&elements, &config, &options);
+
+        let first = result.pages[0].elements.last().unwrap();
+        let text = first.text.as_ref().unwrap();
+        assert_eq!(first.element_id.0, "3");
+        assert!(text[first.line_count as usize - 1].ends_with('.'));
+        assert_eq!(text.last().unwrap(), "(MORE)");
+
+        let parts: u32 = result.pages.iter().flat_map(|p| &p.elements).filter(|e| e.element_id.0 == "3").map(|e| e.line_count as u32).sum();
+        assert_eq!(parts, LineCalculator::new(&config).calculate(&elements[2]).content_lines);
+    }
+
+    #[test]
+    fn test_scene_heading_min_following() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 10;
+        let elements = vec![
+            make_element("1", ElementType::Action, "One.\nTwo.\nThree.\nFour."),
+            make_element("2", ElementType::SceneHeading, "INT. HALL - DAY"),
+            make_element("3", ElementType::Action, "Line.\n".repeat(6).trim_end()),
+        ];
+
+        // Two lines of the action fit under the heading, so the scene opens here
+        let result = paginate(&elements, &config);
+        assert_eq!(result.element_positions["2"].pages, vec![PageIdentifier::Sequential(1)]);
+        assert_eq!(result.element_positions["3"].pages.len(), 2);
+
+        // Asking for three moves the heading along with its scene
+        config.orphan_control.scene_heading_min_following = 3;
+        let result = paginate(&elements, &config);
+        assert_eq!(result.element_positions["2"].pages, vec![PageIdentifier::Sequential(2)]);
+        assert_eq!(result.element_positions["3"].pages, vec![PageIdentifier::Sequential(2)]);
+    }
+
+    #[test]
+    fn test_continued_header_reserves_lines() {
+        let mut config = PageConfig::feature_film();
+        config.continued_header = Some(ContinuedHeader::default());
+        let elements = vec![
+            make_element("c", ElementType::Character, "SARAH"),
+            make_element("d", ElementType::Dialogue, "Speech line.\n".repeat(120).trim_end()),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert!(result.pages[0].header.is_none());
+        for page in &result.pages[1..] {
+            assert_eq!(page.header.as_deref(), Some("CONTINUED:"));
+            // Header rows, then the CONT'D cue, then content
+            assert_eq!(page.elements[0].start_line, 4);
+            assert!(page.lines_used <= config.lines_per_page);
+        }
+
+        let parts: u32 = result.pages.iter().flat_map(|p| &p.elements).map(|e| e.line_count as u32).sum();
+        assert_eq!(parts, 121);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_running_header_reserves_lines() {
+        let mut config = PageConfig::feature_film();
+        config.continued_header = Some(ContinuedHeader::default());
+        config.header_footer = HeaderFooterConfig {
+            draft_name: Some("Blue Revision".to_string()),
+            draft_date: Some("10/16/26".to_string()),
+            skip_first_page: true,
+            header_lines: 2,
+        };
+        let elements = vec![
+            make_element("c", ElementType::Character, "SARAH"),
+            make_element("d", ElementType::Dialogue, "Speech line.\n".repeat(120).trim_end()),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert!(result.pages[0].running_header.is_none());
+        assert_eq!(result.pages[0].elements[0].start_line, 1);
+        for page in &result.pages[1..] {
+            assert_eq!(page.running_header.as_deref(), Some("Blue Revision - 10/16/26"));
+            // Running header rows, continued header rows, the CONT'D cue, then content
+            assert_eq!(page.elements[0].start_line, 6);
+            assert!(page.lines_used <= config.lines_per_page);
+        }
+
+        let parts: u32 = result.pages.iter().flat_map(|p| &p.elements).map(|e| e.line_count as u32).sum();
+        assert_eq!(parts, 121);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_running_header_keeps_page_start() {
+        let mut config = PageConfig::feature_film();
+        config.header_footer.draft_name = Some("Pink".to_string());
+        config.header_footer.header_lines = 2;
+        let elements = vec![
+            make_element("1", ElementType::Action, "Page one.").with_force_page_break(),
+            make_element("2", ElementType::SceneHeading, "INT. HALL - DAY"),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        // Space before the heading is dropped under the header, as at any page top
+        assert_eq!(result.pages.len(), 2);
+        assert_eq!(result.pages[1].elements[0].start_line, 3);
+        assert_eq!(result.pages[0].running_header.as_deref(), Some("Pink"));
+    }
+
+    fn balance_elements() -> Vec<Element> {
+        let unsplittable = ElementStyleOverride { can_split: Some(false), ..Default::default() };
+        vec![
+            make_element("1", ElementType::Action, "One.\nTwo."),
+            make_element("2", ElementType::Action, "One.\nTwo."),
+            make_element("3", ElementType::Action, "One.\nTwo."),
+            make_element("4", ElementType::Action, "One.\nTwo.\nThree.").with_style_override(unsplittable),
+        ]
+    }
+
+    #[test]
+    fn test_page_balance_stretches_gaps() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 10;
+        config.page_balance.enabled = true;
+
+        let result = paginate(&balance_elements(), &config);
+
+        let first = &result.pages[0];
+        let starts: Vec<u8> = first.elements.iter().map(|e| e.start_line).collect();
+        assert_eq!(starts, vec![1, 5, 9]);
+        assert_eq!(first.lines_used, 10);
+        assert_eq!(first.break_reason, Some(PageBreakReason::StretchedSpacing));
+        assert_eq!(result.element_positions["3"].start_line, 9);
+        assert_eq!(result.element_positions["3"].end_line, 10);
+    }
+
+    #[test]
+    fn test_page_balance_short_page() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 10;
+        config.page_balance.enabled = true;
+        config.page_balance.max_stretch_lines = 1;
+
+        let result = paginate(&balance_elements(), &config);
+        assert_eq!(result.pages[0].break_reason, Some(PageBreakReason::ShortPage));
+        assert_eq!(result.pages[0].lines_used, 8);
+
+        config.page_balance.enabled = false;
+        let result = paginate(&balance_elements(), &config);
+        assert_eq!(result.pages[0].break_reason, Some(PageBreakReason::OrphanPrevention));
+        assert!(result.pages[1].break_reason.is_none());
+    }
+
+    #[test]
+    fn test_action_split_across_three_pages() {
+        let config = PageConfig::feature_film();
+        let elements = vec![make_element("a", ElementType::Action, "Action line.\n".repeat(130).trim_end())];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.page_count(), 3);
+        let position = &result.element_positions["a"];
+        assert_eq!(
+            position.pages,
+            vec![PageIdentifier::Sequential(1), PageIdentifier::Sequential(2), PageIdentifier::Sequential(3)]
+        );
+        assert_eq!(position.end_line, 20);
+        assert!(result.pages.iter().all(|p| p.bottom_continuation.is_none()));
+    }
+
+    #[test]
+    fn test_page_numbering() {
+        let mut config = PageConfig::feature_film();
+        let elements = vec![make_element("a", ElementType::Action, "Action line.\n".repeat(70).trim_end())];
+
+        let result = paginate(&elements, &config);
+        assert_eq!(result.pages[0].page_number, None);
+        assert_eq!(result.pages[1].page_number.as_deref(), Some("2."));
+
+        config.page_numbering.start_number = 10;
+        config.page_numbering.suppress_first_page = false;
+        config.page_numbering.format = "{n}".to_string();
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.pages[0].identifier, PageIdentifier::Sequential(10));
+        assert_eq!(result.pages[0].page_number.as_deref(), Some("10"));
+        assert_eq!(result.element_positions["a"].pages[1], PageIdentifier::Sequential(11));
+    }
+
+    #[test]
+    fn test_omitted_pages_keep_numbering() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            make_element("1", ElementType::Action, "Page one."),
+            make_element("2", ElementType::OmittedPage, "2-3"),
+            make_element("3", ElementType::Action, "Page four."),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        let ids: Vec<PageIdentifier> = result.pages.iter().map(|p| p.identifier.clone()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                PageIdentifier::Sequential(1),
+                PageIdentifier::Omitted(2),
+                PageIdentifier::Omitted(3),
+                PageIdentifier::Sequential(4),
+            ]
+        );
+        assert_eq!(result.pages[1].page_number.as_deref(), Some("2."));
+        assert_eq!(result.element_positions["2"].pages.len(), 2);
+        assert_eq!(result.element_positions["3"].pages, vec![PageIdentifier::Sequential(4)]);
+    }
+
+    #[test]
+    fn test_omitted_scene_placeholder() {
+        let config = PageConfig::feature_film();
+        let options = RenderOptions { include_text: true, ..Default::default() };
+        let elements = vec![make_element("1", ElementType::OmittedScene, "24")];
+
+        let result = paginate_with_options(&elements, &config, &options);
+
+        let text = result.pages[0].elements[0].text.as_ref().unwrap();
+        assert_eq!(text, &vec!["SCENE 24 OMITTED".to_string()]);
+
+        // The placeholder is normalized like any printed text
+        let unnumbered = vec![make_element("1", ElementType::OmittedScene, " ")];
+        let result = paginate_with_options(&unnumbered, &config, &options);
+        assert_eq!(result.pages[0].elements[0].text, Some(vec!["SCENE OMITTED".to_string()]));
+    }
+
+    #[test]
+    fn test_widow_line_pulled_to_next_page() {
+        let config = PageConfig::feature_film();
+        let filler = make_element("1", ElementType::Action, "Filler line.\n".repeat(40).trim_end());
+        let used = paginate(std::slice::from_ref(&filler), &config).pages[0].lines_used as u32;
+
+        // One line more than fits after the blank line, so the natural break strands a widow
+        let available = config.lines_per_page as u32 - used - 1;
+        let paragraph = "Paragraph line.\n".repeat(available as usize + 1);
+        let elements = vec![filler, make_element("2", ElementType::Action, paragraph.trim_end())];
+
+        let result = paginate(&elements, &config);
+
+        let first = result.pages[0].elements.last().unwrap();
+        let second = &result.pages[1].elements[0];
+        assert_eq!(first.line_count as u32, available - 1);
+        assert_eq!(second.line_count, 2);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_transition_takes_last_lines_of_action() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 10;
+        let elements = vec![
+            make_element("1", ElementType::Action, "One.\nTwo."),
+            make_element("2", ElementType::Action, "Line.\n".repeat(6).trim_end()),
+            make_element("3", ElementType::Transition, "CUT TO:"),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.page_count(), 2);
+        let second = &result.pages[1].elements;
+        assert_eq!(second[0].element_id.0, "2");
+        assert_eq!(second[0].line_count, 2);
+        assert_eq!(second[1].element_id.0, "3");
+    }
+
+    #[test]
+    fn test_transition_pushes_unsplittable_element() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 10;
+        let unsplittable = ElementStyleOverride { can_split: Some(false), ..Default::default() };
+        let elements = vec![
+            make_element("1", ElementType::Action, "One.\nTwo."),
+            make_element("2", ElementType::Action, "Line.\n".repeat(6).trim_end())
+                .with_style_override(unsplittable),
+            make_element("3", ElementType::Transition, "CUT TO:"),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        let ids: Vec<&str> = result.pages[1].elements.iter().map(|e| e.element_id.0.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+    }
+
+    #[test]
+    fn test_unpreventable_orphan_warning() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 2;
+        let elements = vec![make_element("1", ElementType::Action, "One.\nTwo.\nThree.")];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.page_count(), 2);
+        assert_eq!(result.pages[1].elements[0].line_count, 1);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.warning_type == WarningType::UnpreventableOrphan));
+    }
+
+    #[test]
+    fn test_style_override_prevents_split() {
+        let config = PageConfig::feature_film();
+        let filler = "Filler line.\n".repeat(50);
+        let paragraph = "Paragraph line.\n".repeat(6);
+
+        let splittable = vec![
+            make_element("1", ElementType::Action, filler.trim_end()),
+            make_element("2", ElementType::Action, paragraph.trim_end()),
+        ];
+        let result = paginate(&splittable, &config);
+        assert!(result.element_positions.get("2").unwrap().is_split);
+
+        let mut unsplittable = splittable.clone();
+        unsplittable[1] = unsplittable[1].clone().with_style_override(ElementStyleOverride {
+            can_split: Some(false),
+            ..Default::default()
+        });
+        let result = paginate(&unsplittable, &config);
+        let pos = result.element_positions.get("2").unwrap();
+        assert!(!pos.is_split);
+        assert_eq!(pos.pages[0], PageIdentifier::Sequential(2));
+    }
+
+    #[test]
+    fn test_forced_breaks() {
+        let mut config = PageConfig::feature_film();
+        let pin = |id: &str, split_at_line| PageBreak {
+            after_element: ElementId::new(id),
+            split_at_line,
+            reason: PageBreakReason::Forced,
+        };
+        config.forced_breaks = vec![pin("1", None), pin("2", Some(4))];
+        let elements = vec![
+            make_element("1", ElementType::Action, "Opening."),
+            make_element("2", ElementType::Action, "Paragraph line.\n".repeat(10).trim_end()),
+            make_element("3", ElementType::Action, "After."),
+        ];
+
+        let result = paginate(&elements, &config);
+        assert_eq!(result.page_count(), 3);
+        assert_eq!(result.pages[0].elements.len(), 1);
+        let range = |page: usize| result.pages[page].elements[0].line_range.clone().map(|r| (r.start, r.end));
+        assert_eq!(range(1), Some((0, 4)));
+        assert_eq!(range(2), Some((4, 10)));
+        assert_eq!(result.get_page_for_element("3"), Some(&PageIdentifier::Sequential(3)));
+
+        // A split pinned past the end of the page falls back to the usual break
+        config.forced_breaks = vec![pin("2", Some(70))];
+        let long = vec![make_element("2", ElementType::Action, "Paragraph line.\n".repeat(80).trim_end())];
+        let result = paginate(&long, &config);
+        assert!(result.warnings.iter().any(|w| w.warning_type == WarningType::ForcedBreakMoved));
+        assert_eq!(result.page_count(), 2);
+    }
+
+    #[test]
+    fn test_determinism() {
+        let config = PageConfig::feature_film();
+        let elements: Vec<Element> = (0..50)
+            .map(|i| make_element(&i.to_string(), ElementType::Action, "Some action text here."))
+            .collect();
+
+        let result1 = paginate(&elements, &config);
+        let result2 = paginate(&elements, &config);
+
+        assert_eq!(result1.stats.page_count, result2.stats.page_count);
+        assert_eq!(result1.pages.len(), result2.pages.len());
+    }
+
+    #[test]
+    fn test_page_content_hashes() {
+        let config = PageConfig::feature_film();
+        let mut elements = vec![
+            make_element("1", ElementType::Action, "Page one."),
+            make_element("2", ElementType::Action, "Page two."),
+        ];
+        elements[0].force_page_break_after = true;
+
+        let first = paginate(&elements, &config);
+        elements[1].content = "Page two, revised.".to_string();
+        let second = paginate(&elements, &config);
+
+        assert_eq!(first.pages[0].content_hash.len(), 16);
+        assert_eq!(first.pages[0].content_hash, second.pages[0].content_hash);
+        // Same placement, different words
+        assert_eq!(first.pages[1].elements[0].line_count, second.pages[1].elements[0].line_count);
+        assert_ne!(first.pages[1].content_hash, second.pages[1].content_hash);
+    }
+
+    #[test]
+    fn test_break_explanations() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            make_element("1", ElementType::Action, "Filler.\n".repeat(51).trim_end()),
+            make_element("2", ElementType::SceneHeading, "INT. OFFICE - DAY").with_force_page_break(),
+            make_element("3", ElementType::Action, "Sarah waits."),
+        ];
+
+        assert!(paginate(&elements, &config).break_explanations.is_empty());
+
+        let options = RenderOptions { explain: true, ..Default::default() };
+        let result = paginate_with_options(&elements, &config, &options);
+        let rules: Vec<_> = result
+            .break_explanations
+            .iter()
+            .map(|explanation| (explanation.element_id.as_ref().unwrap().0.as_str(), explanation.rule))
+            .collect();
+        assert_eq!(rules, vec![("2", BreakRule::SceneOpening), ("2", BreakRule::Forced)]);
+        assert_eq!(result.break_explanations[0].page, PageIdentifier::Sequential(1));
+        assert!(result.break_explanations[0].free_lines > 0);
+    }
+
+    #[test]
+    fn test_paginate_delta() {
+        let config = PageConfig::feature_film();
+        let mut elements: Vec<Element> = (0..3)
+            .map(|i| make_element(&i.to_string(), ElementType::Action, &format!("Page {}.", i)).with_force_page_break())
+            .collect();
+        let previous = paginate(&elements, &config);
+
+        elements[1].content = "Page 1, revised.".to_string();
+        let delta = paginate_delta(&elements, &config, &previous);
+
+        assert!(delta.result.pages.is_empty());
+        assert_eq!(delta.changed_pages.len(), 1);
+        assert_eq!(delta.changed_pages[0].index, 1);
+        assert_eq!(
+            delta.reused_pages,
+            vec![ReusedPage { index: 0, previous_index: 0 }, ReusedPage { index: 2, previous_index: 2 }]
+        );
+
+        let rebuilt = delta.apply(&previous).unwrap();
+        assert_eq!(rebuilt.fingerprint(), paginate(&elements, &config).fingerprint());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_timing() {
+        let config = PageConfig::feature_film();
+        let mut elements: Vec<Element> = (0..80)
+            .map(|i| make_element(&i.to_string(), ElementType::Action, "Some action text here."))
+            .collect();
+
+        let first = paginate(&elements, &config);
+        let mut second = paginate(&elements, &config);
+        second.stats.timing_us += 1_000;
+
+        assert_eq!(first.stats.fingerprint.len(), 16);
+        assert_eq!(first.stats.fingerprint, second.fingerprint());
+
+        elements[40].content.push_str(&" And more.".repeat(10));
+        assert_ne!(paginate(&elements, &config).stats.fingerprint, first.stats.fingerprint);
+    }
+
+    #[test]
+    fn test_timing_uses_clock() {
+        struct SteppingClock(std::cell::Cell<u64>);
+
+        impl Clock for SteppingClock {
+            fn now_us(&self) -> u64 {
+                let now = self.0.get();
+                self.0.set(now + 250);
+                now
+            }
+        }
+
+        let config = PageConfig::feature_film();
+        let elements = vec![make_element("1", ElementType::Action, "Some content.")];
+        let clock = SteppingClock(std::cell::Cell::new(1_000));
+
+        let result = paginate_with_clock(&elements, &config, &RenderOptions::default(), &clock);
+
+        assert_eq!(result.stats.timing_us, 250);
+    }
+
+    #[test]
+    fn test_each_element_wrapped_once() {
+        let config = PageConfig::feature_film();
+        let elements: Vec<Element> = (0..20)
+            .flat_map(|i| {
+                vec![
+                    make_element(&format!("h{}", i), ElementType::SceneHeading, &format!("INT. ROOM {} - DAY", i)),
+                    make_element(&format!("a{}", i), ElementType::Action, &format!("Action {}.", i)),
+                ]
+            })
+            .collect();
+        let mut cache = LineCache::new();
+
+        paginate_cached(&elements, &config, &RenderOptions::default(), &crate::utils::NoClock, &mut cache);
+
+        // Keep-with-next lookahead reads the precomputed table, never the cache
+        assert_eq!(cache.misses(), elements.len());
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn test_chunked_run_matches_single_pass() {
+        let config = PageConfig::feature_film();
+        let elements: Vec<Element> = (0..60)
+            .flat_map(|i| {
+                vec![
+                    make_element(&format!("h{}", i), ElementType::SceneHeading, &format!("INT. ROOM {} - DAY", i)),
+                    make_element(&format!("a{}", i), ElementType::Action, &"Action line.\n".repeat(5)),
+                ]
+            })
+            .collect();
+        let mut cache = LineCache::new();
+        let mut run = PaginationRun::new(&elements, &config);
+
+        let mut progress = vec![run.progress()];
+        while !run.advance(&mut cache, 7) {
+            progress.push(run.progress());
+        }
+        progress.push(run.progress());
+
+        assert!(progress.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(progress.last(), Some(&1.0));
+
+        let chunked = run.finish(&RenderOptions::default());
+        let single = paginate(&elements, &config);
+        assert_eq!(chunked.page_count(), single.page_count());
+        assert_eq!(
+            serde_json::to_value(&chunked.pages).unwrap(),
+            serde_json::to_value(&single.pages).unwrap()
+        );
     }
 
     #[test]
@@ -537,6 +2624,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::absurd_extreme_comparisons, unused_comparisons)]
     fn test_timing_recorded() {
         let config = PageConfig::feature_film();
         let elements = vec![
@@ -0,0 +1,331 @@
+use std::sync::Arc;
+
+use crate::types::{Element, ElementEdit, ElementId, PageConfig, PaginationResult, RenderOptions};
+use crate::utils::default_clock;
+use super::page_breaker::{finish_pagination, Checkpoint, PaginationState};
+use super::{ContinuationManager, LineCache, LineCalculation, LineCalculator};
+
+/// Long-lived pagination engine that keeps state between runs
+///
+/// Holds the configuration, a line-calculation cache and the document last
+/// paginated. Repaginating after `insert_element`, `update_element` or
+/// `remove_element` re-wraps only the edited element and places elements
+/// again only from the page before the first edit, since break decisions at
+/// the bottom of a page look ahead into the next.
+#[derive(Debug)]
+pub struct Paginator {
+    config: PageConfig,
+    cache: LineCache,
+    elements: Vec<Element>,
+    line_table: Vec<Arc<LineCalculation>>,
+    state: PaginationState,
+    /// Page starts of the current placement, in element order
+    checkpoints: Vec<Checkpoint>,
+    /// Elements whose placement in `state` is still valid
+    placed: usize,
+}
+
+impl Paginator {
+    pub fn new(config: PageConfig) -> Self {
+        Self {
+            state: PaginationState::new(&config),
+            config,
+            cache: LineCache::new(),
+            elements: Vec::new(),
+            line_table: Vec::new(),
+            checkpoints: Vec::new(),
+            placed: 0,
+        }
+    }
+
+    pub fn config(&self) -> &PageConfig {
+        &self.config
+    }
+
+    /// Replace the configuration, discarding cached line calculations
+    pub fn set_config(&mut self, config: PageConfig) {
+        self.config = config;
+        self.cache.clear();
+        let calc = LineCalculator::new(&self.config);
+        self.line_table = self.cache.get_or_calculate_all(&calc, &self.elements);
+        self.placed = 0;
+    }
+
+    /// Line-calculation cache (for hit/miss inspection)
+    pub fn cache(&self) -> &LineCache {
+        &self.cache
+    }
+
+    /// The document as last loaded and edited
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// Load and paginate a whole document
+    pub fn paginate(&mut self, elements: &[Element]) -> PaginationResult {
+        self.paginate_with_options(elements, &RenderOptions::default())
+    }
+
+    pub fn paginate_with_options(&mut self, elements: &[Element], options: &RenderOptions) -> PaginationResult {
+        self.set_elements(elements.to_vec());
+        self.repaginate_with_options(options)
+    }
+
+    /// Load a whole document without paginating it, reusing cached lines for
+    /// unchanged elements; the next `repaginate` places it from the start
+    pub fn set_elements(&mut self, elements: Vec<Element>) {
+        self.cache.begin_run();
+        let calc = LineCalculator::new(&self.config);
+        self.line_table = self.cache.get_or_calculate_all(&calc, &elements);
+        self.cache.end_run();
+
+        self.elements = elements;
+        self.placed = 0;
+    }
+
+    /// Insert an element before position `index` (clamped to the end)
+    pub fn insert_element(&mut self, index: usize, element: Element) {
+        let index = index.min(self.elements.len());
+        let lines = self.cache.get_or_calculate(&LineCalculator::new(&self.config), &element);
+        self.elements.insert(index, element);
+        self.line_table.insert(index, lines);
+        self.placed = self.placed.min(index);
+    }
+
+    /// Replace the element with the same ID; returns false if there is none
+    pub fn update_element(&mut self, element: Element) -> bool {
+        let Some(index) = self.elements.iter().position(|e| e.id == element.id) else {
+            return false;
+        };
+        self.line_table[index] = self.cache.get_or_calculate(&LineCalculator::new(&self.config), &element);
+        self.elements[index] = element;
+        self.placed = self.placed.min(index);
+        true
+    }
+
+    /// Remove the element with the given ID, returning it
+    pub fn remove_element(&mut self, id: &str) -> Option<Element> {
+        let index = self.elements.iter().position(|e| e.id.0 == id)?;
+        self.line_table.remove(index);
+        self.placed = self.placed.min(index);
+        Some(self.elements.remove(index))
+    }
+
+    /// Apply edits in order, returning the IDs of replaced or removed
+    /// elements that weren't in the document
+    pub fn apply_edits(&mut self, edits: Vec<ElementEdit>) -> Vec<ElementId> {
+        let mut missing = Vec::new();
+        for edit in edits {
+            match edit {
+                ElementEdit::Insert { index, element } => self.insert_element(index, element),
+                ElementEdit::Replace { element } => {
+                    let id = element.id.clone();
+                    if !self.update_element(element) {
+                        missing.push(id);
+                    }
+                }
+                ElementEdit::Remove { id } => {
+                    if self.remove_element(&id.0).is_none() {
+                        missing.push(id);
+                    }
+                }
+            }
+        }
+        missing
+    }
+
+    /// Paginate the current document, placing only what the edits since the
+    /// last run invalidated
+    pub fn repaginate(&mut self) -> PaginationResult {
+        self.repaginate_with_options(&RenderOptions::default())
+    }
+
+    pub fn repaginate_with_options(&mut self, options: &RenderOptions) -> PaginationResult {
+        let clock = default_clock();
+        let started_us = clock.now_us();
+
+        if self.placed < self.elements.len() || self.elements.is_empty() {
+            self.place_from_last_valid_page();
+        }
+        self.evict_stale_lines();
+
+        let mut result = finish_pagination(self.state.clone(), &self.elements, &self.config, &self.line_table, options);
+        result.stats.timing_us = clock.now_us().saturating_sub(started_us);
+        result
+    }
+
+    /// Rewind to the start of the page before the first invalid element and
+    /// place everything from there
+    fn place_from_last_valid_page(&mut self) {
+        let containing = self.checkpoints.iter().rposition(|checkpoint| checkpoint.element <= self.placed);
+        let resume = containing.and_then(|index| index.checked_sub(1));
+
+        let start = match resume {
+            Some(index) => {
+                let checkpoint = self.checkpoints[index].clone();
+                self.state.rewind(&checkpoint);
+                self.checkpoints.truncate(index);
+                checkpoint.element
+            }
+            None => {
+                self.state = PaginationState::new(&self.config);
+                self.checkpoints.clear();
+                0
+            }
+        };
+
+        let continuation_mgr = ContinuationManager::new(&self.config);
+        for idx in start..self.elements.len() {
+            if let Some(checkpoint) = self.state.checkpoint(idx) {
+                self.checkpoints.push(checkpoint);
+            }
+            self.state.place(&self.config, &continuation_mgr, &self.elements[idx..], &self.line_table[idx..]);
+        }
+        self.placed = self.elements.len();
+    }
+
+    /// Drop cached calculations left behind by edits once they outnumber the document
+    fn evict_stale_lines(&mut self) {
+        if self.cache.len() <= 2 * self.elements.len().max(64) {
+            return;
+        }
+        self.cache.begin_run();
+        self.cache.get_or_calculate_all(&LineCalculator::new(&self.config), &self.elements);
+        self.cache.end_run();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::ElementType;
+
+    fn script() -> Vec<Element> {
+        vec![
+            Element::new("1", ElementType::SceneHeading, "INT. OFFICE - DAY"),
+            Element::new("2", ElementType::Action, "A busy office."),
+            Element::new("3", ElementType::Character, "SARAH"),
+            Element::new("4", ElementType::Dialogue, "Hello?").with_character_name("SARAH"),
+        ]
+    }
+
+    #[test]
+    fn test_repagination_reuses_cache() {
+        let mut paginator = Paginator::new(PageConfig::feature_film());
+        let mut elements = script();
+
+        paginator.paginate(&elements);
+        elements[1].content = "A quiet office.".to_string();
+        paginator.paginate(&elements);
+
+        assert_eq!(paginator.cache().misses(), 1);
+        assert_eq!(paginator.cache().len(), 4);
+    }
+
+    fn long_script() -> Vec<Element> {
+        (0..60)
+            .flat_map(|i| {
+                vec![
+                    Element::new(format!("h{}", i), ElementType::SceneHeading, format!("INT. ROOM {} - DAY", i)),
+                    Element::new(format!("a{}", i), ElementType::Action, "Something happens.\n".repeat(1 + i % 7)),
+                    Element::new(format!("c{}", i), ElementType::Character, "SARAH"),
+                    Element::new(format!("d{}", i), ElementType::Dialogue, "Words and more words.\n".repeat(1 + i % 5))
+                        .with_character_name("SARAH"),
+                ]
+            })
+            .collect()
+    }
+
+    fn assert_matches_fresh(paginator: &mut Paginator) {
+        let incremental = paginator.repaginate();
+        let fresh = paginate(paginator.elements(), paginator.config());
+        assert_eq!(incremental.fingerprint(), fresh.fingerprint());
+        assert_eq!(incremental.stats.page_count, fresh.stats.page_count);
+        assert_eq!(incremental.stats.break_count, fresh.stats.break_count);
+    }
+
+    #[test]
+    fn test_edits_match_full_repagination() {
+        let mut paginator = Paginator::new(PageConfig::feature_film());
+        paginator.paginate(&long_script());
+
+        paginator.update_element(Element::new("a30", ElementType::Action, "Much more happens.\n".repeat(20)));
+        assert_matches_fresh(&mut paginator);
+
+        paginator.insert_element(90, Element::new("new", ElementType::Transition, "CUT TO:"));
+        assert_matches_fresh(&mut paginator);
+
+        assert!(paginator.remove_element("d12").is_some());
+        assert_matches_fresh(&mut paginator);
+
+        paginator.insert_element(0, Element::new("open", ElementType::Action, "FADE IN:"));
+        assert_matches_fresh(&mut paginator);
+
+        assert!(paginator.remove_element("missing").is_none());
+        assert!(!paginator.update_element(Element::new("missing", ElementType::Action, "")));
+    }
+
+    #[test]
+    fn test_set_elements_defers_placement() {
+        let mut paginator = Paginator::new(PageConfig::feature_film());
+        paginator.paginate(&long_script());
+
+        let mut elements = long_script();
+        elements[0].content = "A different opening.".to_string();
+        paginator.set_elements(elements);
+
+        assert_eq!(paginator.cache().misses(), 1);
+        assert_matches_fresh(&mut paginator);
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let mut paginator = Paginator::new(PageConfig::feature_film());
+        paginator.paginate(&long_script());
+
+        let edits: Vec<ElementEdit> = serde_json::from_str(
+            r#"[
+                {"op": "replace", "element": {"id": "a3", "element_type": "action", "content": "Nothing happens."}},
+                {"op": "insert", "index": 0, "element": {"id": "open", "element_type": "action", "content": "FADE IN:"}},
+                {"op": "remove", "id": "d7"},
+                {"op": "remove", "id": "nope"}
+            ]"#,
+        )
+        .unwrap();
+        let missing = paginator.apply_edits(edits);
+
+        assert_eq!(missing, vec![ElementId::new("nope")]);
+        assert_eq!(paginator.elements()[0].id.0, "open");
+        assert!(paginator.elements().iter().all(|e| e.id.0 != "d7"));
+        assert_matches_fresh(&mut paginator);
+    }
+
+    #[test]
+    fn test_edit_rewraps_only_edited_element() {
+        let mut paginator = Paginator::new(PageConfig::feature_film());
+        paginator.paginate(&long_script());
+        let misses = paginator.cache().misses();
+
+        paginator.update_element(Element::new("a59", ElementType::Action, "A new ending."));
+        paginator.repaginate();
+
+        assert_eq!(paginator.cache().misses(), misses + 1);
+    }
+
+    #[test]
+    fn test_matches_uncached_pagination() {
+        let mut paginator = Paginator::new(PageConfig::feature_film());
+        let elements = script();
+
+        paginator.paginate(&elements);
+        let cached = paginator.paginate(&elements);
+        let fresh = paginate(&elements, &PageConfig::feature_film());
+
+        assert_eq!(cached.stats.page_count, fresh.stats.page_count);
+        for (a, b) in cached.pages[0].elements.iter().zip(&fresh.pages[0].elements) {
+            assert_eq!(a.start_line, b.start_line);
+            assert_eq!(a.line_count, b.line_count);
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::types::{
+    DualDialoguePosition, Element, ElementFrame, ElementId, ElementType, LinePosition, Page, PageConfig,
+    PaginationResult, RenderOptions, TextDirection,
+};
+use crate::utils::inches_to_points;
+use super::{line_start_offsets, LineCalculator};
+
+/// Attach the rendering data requested in `options` to already-paginated pages
+pub fn apply_render_options(
+    pages: &mut [Page],
+    elements: &[Element],
+    config: &PageConfig,
+    options: &RenderOptions,
+) {
+    if !options.include_geometry && !options.include_text {
+        return;
+    }
+
+    let by_id: HashMap<&str, &Element> = elements.iter().map(|e| (e.id.0.as_str(), e)).collect();
+    let calc = LineCalculator::new(config);
+
+    for page in pages.iter_mut() {
+        let last = page.elements.len().saturating_sub(1);
+        let more_marker = page.bottom_continuation.clone();
+
+        for (idx, page_element) in page.elements.iter_mut().enumerate() {
+            let Some(element) = by_id.get(page_element.element_id.0.as_str()) else {
+                continue;
+            };
+            let first_wrapped_line = page_element.line_range.as_ref().map_or(0, |r| r.start);
+
+            if options.include_geometry {
+                let (frame, positions) = line_positions(
+                    element,
+                    &calc,
+                    config,
+                    page_element.start_line,
+                    page_element.line_count,
+                    first_wrapped_line,
+                    page_element.column,
+                );
+                page_element.frame = Some(frame);
+                page_element.line_positions = Some(positions);
+            }
+
+            if options.include_text {
+                let lines = calc.calculate(element);
+                let on_page = first_wrapped_line as usize..first_wrapped_line as usize + page_element.line_count as usize;
+                let mut text: Vec<String> = Vec::new();
+
+                if let Some(prefix) = &page_element.continuation_prefix {
+                    text.push(prefix.clone());
+                }
+                text.extend(lines.wrapped_lines.iter().take(on_page.end).skip(on_page.start).map(Cow::into_owned));
+                if !lines.line_style_runs.is_empty() {
+                    page_element.style_runs =
+                        Some(lines.line_style_runs.into_iter().take(on_page.end).skip(on_page.start).collect());
+                }
+                if idx == last {
+                    if let Some(marker) = &more_marker {
+                        text.push(marker.clone());
+                    }
+                }
+
+                page_element.text = Some(text);
+            }
+        }
+    }
+}
+
+/// The element and character offset in its content under a point on a page
+///
+/// `page_index` counts `result.pages` from 0 and the point is in points from
+/// the page's top-left corner, as in `RenderOptions::include_geometry`. Lines
+/// are wrapped and positioned the way pagination placed them; a point left or
+/// right of a line's text lands on its first or last character, and points
+/// between lines, on markers or in the margins hit nothing.
+pub fn hit_test(
+    result: &PaginationResult,
+    elements: &[Element],
+    config: &PageConfig,
+    page_index: usize,
+    x_pt: f64,
+    y_pt: f64,
+) -> Option<(ElementId, u32)> {
+    let page = result.pages.get(page_index)?;
+    let by_id: HashMap<&str, &Element> = elements.iter().map(|e| (e.id.0.as_str(), e)).collect();
+    let calc = LineCalculator::new(config);
+
+    // Dual dialogue columns share rows, so prefer the line whose column holds x
+    let mut best: Option<(f64, &Element, u32, LinePosition)> = None;
+    for page_element in &page.elements {
+        let Some(element) = by_id.get(page_element.element_id.0.as_str()) else {
+            continue;
+        };
+        let first_wrapped_line = page_element.line_range.as_ref().map_or(0, |r| r.start);
+        let (frame, positions) = line_positions(
+            element,
+            &calc,
+            config,
+            page_element.start_line,
+            page_element.line_count,
+            first_wrapped_line,
+            page_element.column,
+        );
+        let Some(i) = positions.iter().position(|line| y_pt >= line.y_pt && y_pt < line.y_pt + line.height_pt) else {
+            continue;
+        };
+        let distance = (frame.x_pt - x_pt).max(x_pt - (frame.x_pt + frame.width_pt)).max(0.0);
+        if best.as_ref().is_none_or(|(best_distance, ..)| distance < *best_distance) {
+            best = Some((distance, element, first_wrapped_line + i as u32, positions[i]));
+        }
+    }
+    let (_, element, index, line) = best?;
+
+    let wrapped = calc.calculate(element).wrapped_lines;
+    let text = wrapped.get(index as usize)?;
+    let rtl = config.text_direction_for(element) == TextDirection::Rtl;
+    let into_line = if rtl { line.x_pt + line.width_pt - x_pt } else { x_pt - line.x_pt };
+
+    // Characters whose midpoint the point has passed
+    let mut chars = 0;
+    for (end, c) in text.char_indices().map(|(i, c)| (i + c.len_utf8(), c)) {
+        let width = calc.text_width_pt(&text[..end]);
+        if into_line < width - calc.text_width_pt(c.encode_utf8(&mut [0; 4])) / 2.0 {
+            break;
+        }
+        chars += 1;
+    }
+
+    let offsets = line_start_offsets(&element.content, &wrapped);
+    let start = offsets.get(index as usize).copied().unwrap_or(0);
+    let end = offsets
+        .get(index as usize + 1)
+        .copied()
+        .unwrap_or_else(|| element.content.chars().count() as u32);
+    Some((element.id.clone(), (start + chars).min(end)))
+}
+
+/// Compute the area an element occupies on one page and the box of each of its lines
+fn line_positions(
+    element: &Element,
+    calc: &LineCalculator,
+    config: &PageConfig,
+    start_line: u8,
+    line_count: u8,
+    first_wrapped_line: u32,
+    column: Option<DualDialoguePosition>,
+) -> (ElementFrame, Vec<LinePosition>) {
+    let style = config.resolved_style(element);
+    let mut left_pt = config.margins.left_pt() + inches_to_points(style.margin_left);
+    let mut width_pt = config.printable_width_pt() - inches_to_points(style.margin_left + style.margin_right);
+
+    // A dual dialogue column is a narrower page: its indent shrinks in proportion
+    if let Some(column) = column {
+        let column_pt = inches_to_points(config.dual_dialogue.column_width);
+        let column_left = if column == DualDialoguePosition::Right { column_pt } else { 0.0 };
+        let scale = column_pt / config.printable_width_pt();
+        left_pt = config.margins.left_pt() + column_left + inches_to_points(style.margin_left) * scale;
+        width_pt *= scale;
+    }
+    let width_pt = width_pt.max(0.0);
+
+    let centered = matches!(element.element_type, ElementType::Centered | ElementType::ActBreak);
+    let rtl = config.text_direction_for(element) == TextDirection::Rtl;
+    let wrapped = calc.calculate(element).wrapped_lines;
+
+    // Right-to-left layout is mirrored: the indent is measured from the right
+    // and each line ends against it
+    let right_pt = 2.0 * config.margins.left_pt() + config.printable_width_pt() - left_pt;
+
+    let positions: Vec<LinePosition> = (0..line_count as u32)
+        .map(|i| {
+            let row = start_line as u32 + (i as f64 * style.line_spacing.max(1.0)) as u32;
+            let y_pt = config.margins.top_pt() + (row - 1) as f64 * config.line_height_pt;
+            let text_width = wrapped.get((first_wrapped_line + i) as usize).map_or(0.0, |text| calc.text_width_pt(&text));
+
+            // Centered text is offset by half the unused width of its own line
+            let x_pt = if centered {
+                left_pt + (config.printable_width_pt() - text_width).max(0.0) / 2.0
+            } else if rtl {
+                right_pt - text_width
+            } else {
+                left_pt
+            };
+
+            LinePosition { x_pt, y_pt, width_pt: text_width, height_pt: config.line_height_pt }
+        })
+        .collect();
+
+    let top_pt = config.margins.top_pt() + start_line.saturating_sub(1) as f64 * config.line_height_pt;
+    let bottom_pt = positions.last().map_or(top_pt, |last| last.y_pt + last.height_pt);
+    let frame = ElementFrame {
+        x_pt: if rtl { right_pt - width_pt } else { left_pt },
+        y_pt: top_pt,
+        width_pt,
+        height_pt: bottom_pt - top_pt,
+    };
+
+    (frame, positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate_with_options;
+    use crate::types::ElementStyleOverride;
+
+    #[test]
+    fn test_geometry_off_by_default() {
+        let config = PageConfig::feature_film();
+        let elements = vec![Element::new("1", ElementType::Action, "A busy office.")];
+
+        let result = paginate_with_options(&elements, &config, &RenderOptions::default());
+
+        assert!(result.pages[0].elements[0].line_positions.is_none());
+    }
+
+    #[test]
+    fn test_geometry_positions() {
+        let config = PageConfig::feature_film();
+        let options = RenderOptions { include_geometry: true, ..Default::default() };
+        let elements = vec![
+            Element::new("1", ElementType::Action, "A busy office."),
+            Element::new("2", ElementType::Character, "SARAH"),
+            Element::new("3", ElementType::Dialogue, "Hello?"),
+        ];
+
+        let result = paginate_with_options(&elements, &config, &options);
+        let page = &result.pages[0];
+
+        // Action starts at the 1" top and 1.5" left margins
+        let action = page.elements[0].line_positions.as_ref().unwrap();
+        assert_eq!(action[0], LinePosition { x_pt: 108.0, y_pt: 72.0, width_pt: 14.0 * 7.2, height_pt: 12.0 });
+
+        // Character on line 3 is indented 2.2" from the left margin
+        let character = page.elements[1].line_positions.as_ref().unwrap();
+        assert!((character[0].x_pt - (108.0 + 2.2 * 72.0)).abs() < 0.001);
+        assert_eq!(character[0].y_pt, 72.0 + 2.0 * 12.0);
+    }
+
+    #[test]
+    fn test_geometry_frames() {
+        let config = PageConfig::feature_film();
+        let options = RenderOptions { include_geometry: true, ..Default::default() };
+        let elements = vec![
+            Element::new("1", ElementType::Character, "SARAH"),
+            Element::new("2", ElementType::Dialogue, "Words and more words. ".repeat(4).trim_end()),
+        ];
+
+        let result = paginate_with_options(&elements, &config, &options);
+        let dialogue = &result.pages[0].elements[1];
+        let frame = dialogue.frame.unwrap();
+        let style = config.style_for(ElementType::Dialogue);
+
+        // The column between the dialogue margins, from the first line's top through the last
+        assert!((frame.x_pt - (108.0 + style.margin_left * 72.0)).abs() < 0.001);
+        assert!((frame.width_pt - (432.0 - (style.margin_left + style.margin_right) * 72.0)).abs() < 0.001);
+        assert_eq!(frame.y_pt, 72.0 + 12.0);
+        assert_eq!(frame.height_pt, dialogue.line_count as f64 * 12.0);
+
+        let positions = dialogue.line_positions.as_ref().unwrap();
+        assert!(positions.iter().all(|line| line.width_pt > 0.0 && line.width_pt <= frame.width_pt));
+    }
+
+    #[test]
+    fn test_hit_test() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::Action, "A busy office."),
+            Element::new("2", ElementType::Character, "SARAH"),
+            Element::new("3", ElementType::Dialogue, "Words and more words. ".repeat(4).trim_end()),
+        ];
+        let result = paginate_with_options(&elements, &config, &RenderOptions::default());
+        let style = config.style_for(ElementType::Dialogue);
+        let dialogue_left = 108.0 + style.margin_left * 72.0;
+
+        // Between the fourth and fifth characters of the action line, leaning right
+        assert_eq!(hit_test(&result, &elements, &config, 0, 108.0 + 3.6 * 7.2, 78.0), Some((ElementId::new("1"), 4)));
+        // Left of the text is the line's start; far right is its end
+        assert_eq!(hit_test(&result, &elements, &config, 0, 20.0, 78.0), Some((ElementId::new("1"), 0)));
+        assert_eq!(hit_test(&result, &elements, &config, 0, 600.0, 78.0), Some((ElementId::new("1"), 14)));
+
+        // The second dialogue line starts after the first line's text
+        let offsets = &result.element_positions["3"].line_offsets;
+        let second_line_y = 72.0 + 4.0 * 12.0 + 1.0;
+        assert_eq!(
+            hit_test(&result, &elements, &config, 0, dialogue_left, second_line_y),
+            Some((ElementId::new("3"), offsets[1]))
+        );
+
+        // The blank line before the cue hits nothing
+        assert_eq!(hit_test(&result, &elements, &config, 0, 200.0, 72.0 + 12.0 + 1.0), None);
+        assert_eq!(hit_test(&result, &elements, &config, 5, 200.0, 78.0), None);
+    }
+
+    #[test]
+    fn test_centered_geometry() {
+        let config = PageConfig::feature_film();
+        let options = RenderOptions { include_geometry: true, ..Default::default() };
+        let elements = vec![Element::new("1", ElementType::Centered, "THE END")];
+
+        let result = paginate_with_options(&elements, &config, &options);
+        let positions = result.pages[0].elements[0].line_positions.as_ref().unwrap();
+
+        // 7 characters centered in the 432pt printable width
+        let expected = 108.0 + (432.0 - 7.0 * 7.2) / 2.0;
+        assert!((positions[0].x_pt - expected).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_dual_dialogue_geometry() {
+        let config = PageConfig::feature_film();
+        let options = RenderOptions { include_geometry: true, ..Default::default() };
+        let elements = vec![
+            Element::new("1", ElementType::Character, "SARAH").with_dual_dialogue_position(DualDialoguePosition::Left),
+            Element::new("2", ElementType::Character, "JOHN").with_dual_dialogue_position(DualDialoguePosition::Right),
+        ];
+
+        let result = paginate_with_options(&elements, &config, &options);
+        let left = result.pages[0].elements[0].line_positions.as_ref().unwrap();
+        let right = result.pages[0].elements[1].line_positions.as_ref().unwrap();
+
+        // Both cues on line 1; the 2.2" cue indent halves in a 3" column
+        assert_eq!(left[0].y_pt, right[0].y_pt);
+        assert!((left[0].x_pt - (108.0 + 1.1 * 72.0)).abs() < 0.001);
+        assert!((right[0].x_pt - (108.0 + 3.0 * 72.0 + 1.1 * 72.0)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_right_to_left_geometry() {
+        let mut config = PageConfig::feature_film();
+        config.text_direction = TextDirection::Rtl;
+        let options = RenderOptions { include_geometry: true, ..Default::default() };
+        let ltr = ElementStyleOverride { text_direction: Some(TextDirection::Ltr), ..Default::default() };
+        let elements = vec![
+            Element::new("1", ElementType::Dialogue, "שלום.").with_character_name("DANA"),
+            Element::new("2", ElementType::Action, "FADE IN.").with_style_override(ltr),
+        ];
+
+        let result = paginate_with_options(&elements, &config, &options);
+        let dialogue = result.pages[0].elements[0].line_positions.as_ref().unwrap();
+        let action = result.pages[0].elements[1].line_positions.as_ref().unwrap();
+
+        // The 1" dialogue indent is measured from the right and the line ends against it
+        assert!((dialogue[0].x_pt - (108.0 + 432.0 - 72.0 - 5.0 * 7.2)).abs() < 0.001);
+        assert_eq!(action[0].x_pt, 108.0);
+    }
+
+    #[test]
+    fn test_text_off_by_default() {
+        let config = PageConfig::feature_film();
+        let elements = vec![Element::new("1", ElementType::Action, "A busy office.")];
+
+        let result = paginate_with_options(&elements, &config, &RenderOptions::default());
+
+        assert!(result.pages[0].elements[0].text.is_none());
+    }
+
+    #[test]
+    fn test_text_is_uppercased() {
+        let config = PageConfig::feature_film();
+        let options = RenderOptions { include_text: true, ..Default::default() };
+        let elements = vec![Element::new("1", ElementType::SceneHeading, "int. office - day")];
+
+        let result = paginate_with_options(&elements, &config, &options);
+
+        let text = result.pages[0].elements[0].text.as_ref().unwrap();
+        assert_eq!(text, &vec!["INT. OFFICE - DAY".to_string()]);
+    }
+
+    #[test]
+    fn test_text_includes_continuation_markers() {
+        let config = PageConfig::feature_film();
+        let options = RenderOptions { include_text: true, ..Default::default() };
+        let filler = "Filler line.\n".repeat(44);
+        let speech = "Speech line.\n".repeat(20);
+        let elements = vec![
+            Element::new("1", ElementType::Action, filler.trim_end()),
+            Element::new("2", ElementType::Dialogue, speech.trim_end()).with_character_name("SARAH"),
+        ];
+
+        let result = paginate_with_options(&elements, &config, &options);
+
+        let first = result.pages[0].elements.last().unwrap();
+        let text = first.text.as_ref().unwrap();
+        assert_eq!(text.len(), first.line_count as usize + 1);
+        assert_eq!(text.last().unwrap(), "(MORE)");
+
+        let rest = result.pages[1].elements[0].text.as_ref().unwrap();
+        assert_eq!(rest[0], "SARAH (CONT'D)");
+        assert_eq!(rest[1], "Speech line.");
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{
+    Element, ElementId, ElementType, Page, PageConfig, PageIdentifier, PaginationResult, RevisedPage, RevisionColor,
+    RevisionReport,
+};
+use super::LineCalculator;
+
+/// What a page prints, reduced to what a reader could see change
+///
+/// Each placement contributes its element's text and type alongside where
+/// it sits, so an edit that leaves line counts alone still counts. Render
+/// extras (geometry, text) are left out.
+type PageSignature<'a> = (
+    Vec<(&'a str, u8, u8, Option<(u32, u32)>, Option<&'a str>, Option<(&'a str, ElementType)>)>,
+    Option<&'a str>,
+);
+
+fn page_signature<'a>(page: &'a Page, elements: &HashMap<&str, &'a Element>) -> PageSignature<'a> {
+    let placements = page
+        .elements
+        .iter()
+        .map(|page_element| {
+            let id = page_element.element_id.0.as_str();
+            (
+                id,
+                page_element.start_line,
+                page_element.line_count,
+                page_element.line_range.as_ref().map(|range| (range.start, range.end)),
+                page_element.continuation_prefix.as_deref(),
+                elements.get(id).map(|e| (e.content.as_str(), e.element_type)),
+            )
+        })
+        .collect();
+
+    (placements, page.bottom_continuation.as_deref())
+}
+
+/// Find the pages a new draft changes, for issuing revision pages
+///
+/// A page is revised when the new draft has no page with its identifier in
+/// the old one or when what it prints differs. Old pages missing from the
+/// new draft are listed as removed.
+pub fn compare_drafts(
+    old_elements: &[Element],
+    old_result: &PaginationResult,
+    new_elements: &[Element],
+    new_result: &PaginationResult,
+    color: RevisionColor,
+) -> RevisionReport {
+    let old_by_id: HashMap<&str, &Element> = old_elements.iter().map(|e| (e.id.0.as_str(), e)).collect();
+    let new_by_id: HashMap<&str, &Element> = new_elements.iter().map(|e| (e.id.0.as_str(), e)).collect();
+    let old_pages: HashMap<&PageIdentifier, &Page> =
+        old_result.pages.iter().map(|page| (&page.identifier, page)).collect();
+
+    let revised_pages: Vec<RevisedPage> = new_result
+        .pages
+        .iter()
+        .filter_map(|page| {
+            let old = old_pages.get(&page.identifier);
+            let changed = old.is_none_or(|old| {
+                page_signature(old, &old_by_id) != page_signature(page, &new_by_id)
+            });
+            changed.then(|| RevisedPage { page: page.identifier.clone(), color, is_new: old.is_none() })
+        })
+        .collect();
+
+    let removed_pages: Vec<PageIdentifier> = old_result
+        .pages
+        .iter()
+        .filter(|old| !new_result.pages.iter().any(|page| page.identifier == old.identifier))
+        .map(|old| old.identifier.clone())
+        .collect();
+
+    let summary = revision_summary(color, &revised_pages, &removed_pages);
+
+    RevisionReport { color, revised_pages, removed_pages, summary }
+}
+
+/// Mark the lines of changed elements for a production draft
+///
+/// Every line a changed element prints is flagged for a margin asterisk,
+/// and each page holding one takes `color`. Marks from earlier revisions
+/// are kept, so calling this once per revision accumulates them while the
+/// pages touched last carry the latest color.
+pub fn mark_revisions(result: &mut PaginationResult, changed: &[ElementId], color: RevisionColor) {
+    let changed: HashSet<&str> = changed.iter().map(|id| id.0.as_str()).collect();
+
+    for page in &mut result.pages {
+        let mut revised = false;
+        for page_element in &mut page.elements {
+            if !changed.contains(page_element.element_id.0.as_str()) {
+                continue;
+            }
+            page_element.revision_marks = Some(vec![true; page_element.line_count as usize]);
+            revised = true;
+        }
+
+        if revised {
+            page.revision_color = Some(color);
+            page.content_hash = page.compute_content_hash(&HashMap::new());
+        }
+    }
+}
+
+/// Mark only the wrapped lines that changed since the previous draft
+///
+/// Each element of `new_elements` is wrapped with `config` and compared line
+/// by line with its previous version: lines kept in order are left alone,
+/// the rest are flagged for a margin asterisk, and all of a new element's
+/// lines are. Pages with a flagged line take `color`. Like `mark_revisions`,
+/// marks already on the result are kept.
+pub fn mark_changed_lines(
+    result: &mut PaginationResult,
+    old_elements: &[Element],
+    new_elements: &[Element],
+    config: &PageConfig,
+    color: RevisionColor,
+) {
+    let calc = LineCalculator::new(config);
+    let old_by_id: HashMap<&str, &Element> = old_elements.iter().map(|e| (e.id.0.as_str(), e)).collect();
+
+    let mut changed_lines: HashMap<&str, Vec<bool>> = HashMap::new();
+    for element in new_elements {
+        let old = old_by_id.get(element.id.0.as_str());
+        if old.is_some_and(|old| old.content == element.content && old.element_type == element.element_type) {
+            continue;
+        }
+        let new_lines = calc.calculate(element).wrapped_lines.to_strings();
+        let flags = match old.filter(|old| old.element_type == element.element_type) {
+            Some(old) => changed_line_flags(&calc.calculate(old).wrapped_lines.to_strings(), &new_lines),
+            None => vec![true; new_lines.len()],
+        };
+        if flags.contains(&true) {
+            changed_lines.insert(element.id.0.as_str(), flags);
+        }
+    }
+
+    for page in &mut result.pages {
+        let mut revised = false;
+        for page_element in &mut page.elements {
+            let Some(flags) = changed_lines.get(page_element.element_id.0.as_str()) else {
+                continue;
+            };
+            let start = page_element.line_range.as_ref().map_or(0, |range| range.start as usize);
+            let marks: Vec<bool> = (start..start + page_element.line_count as usize)
+                .map(|line| flags.get(line).copied().unwrap_or(false))
+                .collect();
+            if !marks.contains(&true) {
+                continue;
+            }
+
+            let merged = match page_element.revision_marks.take() {
+                Some(earlier) => {
+                    marks.iter().enumerate().map(|(i, &mark)| mark || earlier.get(i).copied().unwrap_or(false)).collect()
+                }
+                None => marks,
+            };
+            page_element.revision_marks = Some(merged);
+            revised = true;
+        }
+
+        if revised {
+            page.revision_color = Some(color);
+            page.content_hash = page.compute_content_hash(&HashMap::new());
+        }
+    }
+}
+
+/// For each new line, whether it is outside the longest run of lines the
+/// two versions share in order
+fn changed_line_flags(old_lines: &[String], new_lines: &[String]) -> Vec<bool> {
+    // common[i][j]: longest common subsequence of old_lines[i..] and new_lines[j..]
+    let mut common = vec![vec![0u32; new_lines.len() + 1]; old_lines.len() + 1];
+    for i in (0..old_lines.len()).rev() {
+        for j in (0..new_lines.len()).rev() {
+            common[i][j] = if old_lines[i] == new_lines[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut flags = vec![true; new_lines.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < old_lines.len() && j < new_lines.len() {
+        if old_lines[i] == new_lines[j] {
+            flags[j] = false;
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    flags
+}
+
+/// Memo line listing the revised pages, with sequential runs collapsed ("5-7")
+fn revision_summary(color: RevisionColor, revised: &[RevisedPage], removed: &[PageIdentifier]) -> String {
+    if revised.is_empty() && removed.is_empty() {
+        return format!("{} revision: no pages changed.", color.name());
+    }
+
+    let pages: Vec<&PageIdentifier> = revised.iter().map(|r| &r.page).collect();
+    let count = pages.len();
+    let mut summary = format!(
+        "{} revision: {} {} ({} {}).",
+        color.name(),
+        if count == 1 { "page" } else { "pages" },
+        page_list(&pages),
+        count,
+        if count == 1 { "page" } else { "pages" },
+    );
+
+    if !removed.is_empty() {
+        let removed: Vec<&PageIdentifier> = removed.iter().collect();
+        summary.push_str(&format!(" Removed: {}.", page_list(&removed)));
+    }
+
+    summary
+}
+
+fn page_list(pages: &[&PageIdentifier]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut run: Option<(u32, u32)> = None;
+
+    let close = |run: &mut Option<(u32, u32)>, parts: &mut Vec<String>| match run.take() {
+        Some((first, last)) if first == last => parts.push(first.to_string()),
+        Some((first, last)) => parts.push(format!("{}-{}", first, last)),
+        None => {}
+    };
+
+    for page in pages {
+        match page {
+            PageIdentifier::Sequential(n) => match run {
+                Some((first, last)) if *n == last + 1 => run = Some((first, *n)),
+                _ => {
+                    close(&mut run, &mut parts);
+                    run = Some((*n, *n));
+                }
+            },
+            other => {
+                close(&mut run, &mut parts);
+                parts.push(other.display());
+            }
+        }
+    }
+    close(&mut run, &mut parts);
+
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::PageConfig;
+
+    fn draft() -> Vec<Element> {
+        (0..120)
+            .map(|i| Element::new(i.to_string(), ElementType::Action, format!("Action paragraph {}.", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_unchanged_draft_has_no_revised_pages() {
+        let config = PageConfig::feature_film();
+        let elements = draft();
+        let result = paginate(&elements, &config);
+
+        let report = compare_drafts(&elements, &result, &elements, &result, RevisionColor::Blue);
+
+        assert!(report.revised_pages.is_empty());
+        assert_eq!(report.summary, "Blue revision: no pages changed.");
+    }
+
+    #[test]
+    fn test_edit_in_place_revises_one_page() {
+        let config = PageConfig::feature_film();
+        let old_elements = draft();
+        let old_result = paginate(&old_elements, &config);
+
+        let mut new_elements = old_elements.clone();
+        new_elements[60].content = "A rewritten paragraph.".to_string();
+        let new_result = paginate(&new_elements, &config);
+
+        let report = compare_drafts(&old_elements, &old_result, &new_elements, &new_result, RevisionColor::Pink);
+
+        let page = new_result.get_page_for_element("60").unwrap().clone();
+        assert_eq!(
+            report.revised_pages,
+            vec![RevisedPage { page: page.clone(), color: RevisionColor::Pink, is_new: false }]
+        );
+        assert_eq!(report.summary, format!("Pink revision: page {} (1 page).", page.display()));
+    }
+
+    #[test]
+    fn test_mark_revisions() {
+        let config = PageConfig::feature_film();
+        let mut elements = draft();
+        elements[3].content = "A paragraph long enough to wrap onto a second line of the page.".to_string();
+        let mut result = paginate(&elements, &config);
+
+        mark_revisions(&mut result, &[ElementId::new("3")], RevisionColor::Blue);
+
+        let page = &result.pages[0];
+        assert_eq!(page.revision_color, Some(RevisionColor::Blue));
+        assert_eq!(page.elements[3].revision_marks, Some(vec![true, true]));
+        assert_eq!(page.elements[2].revision_marks, None);
+        assert!(result.pages[1..].iter().all(|page| page.revision_color.is_none()));
+
+        // A later revision recolors only the pages it touches
+        mark_revisions(&mut result, &[ElementId::new("100")], RevisionColor::Pink);
+        assert_eq!(result.pages[0].revision_color, Some(RevisionColor::Blue));
+        let pink = result.get_page_for_element("100").unwrap().clone();
+        let pink = result.pages.iter().find(|page| page.identifier == pink).unwrap();
+        assert_eq!(pink.revision_color, Some(RevisionColor::Pink));
+    }
+
+    #[test]
+    fn test_mark_changed_lines() {
+        let config = PageConfig::feature_film();
+        let old_elements = vec![
+            Element::new("1", ElementType::Action, "First line.\nSecond line.\nThird line."),
+            Element::new("2", ElementType::Action, "Untouched."),
+        ];
+        let mut new_elements = old_elements.clone();
+        new_elements[0].content = "First line.\nSecond line, rewritten.\nThird line.".to_string();
+        new_elements.push(Element::new("3", ElementType::Action, "Added."));
+        let mut result = paginate(&new_elements, &config);
+
+        mark_changed_lines(&mut result, &old_elements, &new_elements, &config, RevisionColor::Blue);
+
+        let page = &result.pages[0];
+        assert_eq!(page.revision_color, Some(RevisionColor::Blue));
+        assert_eq!(page.elements[0].revision_marks, Some(vec![false, true, false]));
+        assert_eq!(page.elements[1].revision_marks, None);
+        assert_eq!(page.elements[2].revision_marks, Some(vec![true]));
+
+        let mut unchanged = paginate(&old_elements, &config);
+        mark_changed_lines(&mut unchanged, &old_elements, &old_elements, &config, RevisionColor::Blue);
+        assert_eq!(unchanged.pages[0].revision_color, None);
+    }
+
+    #[test]
+    fn test_page_list_collapses_runs() {
+        let pages = [
+            PageIdentifier::Sequential(3),
+            PageIdentifier::Sequential(5),
+            PageIdentifier::Sequential(6),
+            PageIdentifier::Sequential(7),
+            PageIdentifier::Inserted { base: 7, suffix: 'A' },
+        ];
+        let refs: Vec<&PageIdentifier> = pages.iter().collect();
+        assert_eq!(page_list(&refs), "3, 5-7, 7A");
+    }
+}
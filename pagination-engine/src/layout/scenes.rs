@@ -0,0 +1,287 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{
+    Element, ElementPosition, ElementType, Page, PageConfig, SceneIndexEntry, SceneLength,
+};
+
+/// Map each element ID to the index of the scene it belongs to
+///
+/// Elements before the first scene heading belong to no scene.
+fn scene_membership(elements: &[Element]) -> HashMap<&str, usize> {
+    let mut membership = HashMap::new();
+    let mut scene: Option<usize> = None;
+
+    for element in elements {
+        if element.element_type == ElementType::SceneHeading {
+            scene = Some(scene.map_or(0, |s| s + 1));
+        }
+        if let Some(s) = scene {
+            membership.insert(element.id.0.as_str(), s);
+        }
+    }
+
+    membership
+}
+
+/// Measure every scene's length in lines and eighths of a page
+///
+/// A scene owns the lines from its first placement on a page up to the next
+/// scene's first placement (or the end of the page's content), so a full page
+/// always adds up to eight eighths across its scenes.
+pub fn calculate_scene_lengths(
+    elements: &[Element],
+    pages: &[Page],
+    config: &PageConfig,
+) -> Vec<SceneLength> {
+    let membership = scene_membership(elements);
+
+    let mut lengths: Vec<SceneLength> = elements
+        .iter()
+        .filter(|e| e.element_type == ElementType::SceneHeading)
+        .map(|heading| SceneLength {
+            heading_element: heading.id.clone(),
+            heading: heading.content.clone(),
+            start_page: None,
+            lines: 0,
+            eighths: 0,
+        })
+        .collect();
+
+    for page in pages {
+        // First line of each scene on this page, in page order
+        let mut starts: Vec<(usize, u8)> = Vec::new();
+        for page_element in &page.elements {
+            if let Some(&scene) = membership.get(page_element.element_id.0.as_str()) {
+                if starts.last().map(|&(s, _)| s) != Some(scene) {
+                    starts.push((scene, page_element.start_line));
+                }
+            }
+        }
+
+        for (i, &(scene, start)) in starts.iter().enumerate() {
+            let end = starts
+                .get(i + 1)
+                .map(|&(_, next)| next)
+                .unwrap_or(page.lines_used + 1);
+            let length = &mut lengths[scene];
+            length.lines += end.saturating_sub(start) as u32;
+            if length.start_page.is_none() {
+                length.start_page = Some(page.identifier.clone());
+            }
+        }
+    }
+
+    for length in &mut lengths {
+        length.eighths = lines_to_eighths(length.lines, config.lines_per_page);
+    }
+
+    lengths
+}
+
+/// Scene number of every scene heading, keyed by element ID
+///
+/// Without locked numbers the scenes are numbered 1, 2, 3... Once any
+/// heading carries a `scene_number` the script is locked: those numbers are
+/// kept, and each new scene takes the number of the locked scene before it
+/// plus the next free letter, so scenes added after 12 are 12A, 12B and one
+/// added after 12A is 12B. New scenes ahead of the first locked one are
+/// lettered in front of it (A1, B1). Omitted scene placeholders hold on to
+/// their numbers.
+pub fn number_scenes(elements: &[Element]) -> HashMap<&str, String> {
+    let headings = elements.iter().filter(|e| e.element_type == ElementType::SceneHeading);
+
+    let mut taken: HashSet<String> = elements.iter().filter_map(locked_number).map(str::to_string).collect();
+    if taken.is_empty() {
+        return headings.enumerate().map(|(i, heading)| (heading.id.0.as_str(), (i + 1).to_string())).collect();
+    }
+
+    let mut numbers = HashMap::new();
+    let mut previous: Option<&str> = None;
+    for (idx, element) in elements.iter().enumerate() {
+        if let Some(number) = locked_number(element) {
+            previous = Some(number);
+            if element.element_type == ElementType::SceneHeading {
+                numbers.insert(element.id.0.as_str(), number.to_string());
+            }
+            continue;
+        }
+        if element.element_type != ElementType::SceneHeading {
+            continue;
+        }
+
+        let lettered = |letter: String| match previous {
+            Some(number) => format!("{}{}", number.trim_end_matches(|c: char| c.is_ascii_alphabetic()), letter),
+            None => format!("{}{}", letter, elements[idx..].iter().find_map(locked_number).unwrap_or_default()),
+        };
+        let number = (0..).map(|n| lettered(scene_letters(n))).find(|number| !taken.contains(number)).unwrap_or_default();
+        taken.insert(number.clone());
+        numbers.insert(element.id.0.as_str(), number);
+    }
+
+    numbers
+}
+
+/// Scene number a heading or omitted scene placeholder holds in a locked script
+fn locked_number(element: &Element) -> Option<&str> {
+    match element.element_type {
+        ElementType::SceneHeading => element.scene_number.as_deref().map(str::trim),
+        ElementType::OmittedScene => Some(element.content.trim()),
+        _ => None,
+    }
+}
+
+/// Letter suffix for the nth inserted scene: A..Z, then AA..ZZ, then AAA...
+fn scene_letters(mut n: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push((b'A' + (n % 26) as u8) as char);
+        if n < 26 {
+            break;
+        }
+        n = n / 26 - 1;
+    }
+    letters.iter().rev().collect()
+}
+
+/// List every placed scene heading with its page and starting line
+pub fn build_scene_index(
+    elements: &[Element],
+    element_positions: &HashMap<String, ElementPosition>,
+) -> Vec<SceneIndexEntry> {
+    let numbers = number_scenes(elements);
+
+    elements
+        .iter()
+        .filter(|e| e.element_type == ElementType::SceneHeading)
+        .filter_map(|heading| {
+            let position = element_positions.get(&heading.id.0)?;
+            Some(SceneIndexEntry {
+                element_id: heading.id.clone(),
+                heading: heading.content.clone(),
+                scene_number: numbers.get(heading.id.0.as_str()).cloned().unwrap_or_default(),
+                page: position.pages.first()?.clone(),
+                start_line: position.start_line,
+            })
+        })
+        .collect()
+}
+
+/// Convert a line count to eighths of a page, never reporting less than 1/8
+pub fn lines_to_eighths(lines: u32, lines_per_page: u8) -> u32 {
+    if lines == 0 || lines_per_page == 0 {
+        return 0;
+    }
+
+    let eighths = (lines as f64 * 8.0 / lines_per_page as f64).round() as u32;
+    eighths.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::PageIdentifier;
+
+    #[test]
+    fn test_lines_to_eighths() {
+        assert_eq!(lines_to_eighths(55, 55), 8);
+        assert_eq!(lines_to_eighths(1, 55), 1);
+        assert_eq!(lines_to_eighths(0, 55), 0);
+        assert_eq!(lines_to_eighths(110, 55), 16);
+    }
+
+    #[test]
+    fn test_scene_lengths() {
+        let config = PageConfig::feature_film();
+        let long_action = "Action line.\n".repeat(40);
+        let elements = vec![
+            Element::new("0", ElementType::Action, "Cold open."),
+            Element::new("1", ElementType::SceneHeading, "INT. OFFICE - DAY"),
+            Element::new("2", ElementType::Action, long_action.trim_end()),
+            Element::new("3", ElementType::SceneHeading, "EXT. STREET - NIGHT"),
+            Element::new("4", ElementType::Action, "A car passes."),
+        ];
+
+        let result = paginate(&elements, &config);
+        let lengths = calculate_scene_lengths(&elements, &result.pages, &config);
+
+        assert_eq!(lengths.len(), 2);
+        assert_eq!(lengths[0].heading, "INT. OFFICE - DAY");
+        // Heading + blank + 40 action lines + two blank lines before the next heading
+        assert_eq!(lengths[0].lines, 44);
+        assert_eq!(lengths[0].eighths, 6);
+        assert_eq!(lengths[1].lines, 3);
+        assert_eq!(lengths[1].eighths, 1);
+    }
+
+    #[test]
+    fn test_scene_index() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::SceneHeading, "INT. OFFICE - DAY"),
+            Element::new("2", ElementType::Action, "A busy office.").with_force_page_break(),
+            Element::new("3", ElementType::SceneHeading, "EXT. STREET - NIGHT"),
+        ];
+
+        let result = paginate(&elements, &config);
+
+        assert_eq!(result.scene_index.len(), 2);
+        let second = &result.scene_index[1];
+        assert_eq!(second.heading, "EXT. STREET - NIGHT");
+        assert_eq!(second.scene_number, "2");
+        assert_eq!(second.page, PageIdentifier::Sequential(2));
+        assert_eq!(second.start_line, 1);
+    }
+
+    #[test]
+    fn test_number_scenes_around_locked_numbers() {
+        let heading = |id: &str| Element::new(id, ElementType::SceneHeading, "INT. ROOM - DAY");
+        let elements = vec![
+            heading("new0"),
+            heading("s1").with_scene_number("1"),
+            heading("s12").with_scene_number("12"),
+            heading("s12a").with_scene_number("12A"),
+            heading("new1"),
+            heading("new2"),
+            Element::new("o13", ElementType::OmittedScene, "13"),
+            heading("new3"),
+            heading("s14").with_scene_number("14"),
+        ];
+
+        let numbers = number_scenes(&elements);
+
+        assert_eq!(numbers["new0"], "A1");
+        assert_eq!(numbers["s12"], "12");
+        assert_eq!(numbers["new1"], "12B");
+        assert_eq!(numbers["new2"], "12C");
+        assert_eq!(numbers["new3"], "13A");
+        assert_eq!(numbers["s14"], "14");
+        assert!(!numbers.contains_key("o13"));
+    }
+
+    #[test]
+    fn test_scene_letters() {
+        assert_eq!(scene_letters(0), "A");
+        assert_eq!(scene_letters(25), "Z");
+        assert_eq!(scene_letters(26), "AA");
+        assert_eq!(scene_letters(701), "ZZ");
+        assert_eq!(scene_letters(702), "AAA");
+    }
+
+    #[test]
+    fn test_scene_numbers_on_page_elements() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::SceneHeading, "INT. OFFICE - DAY").with_scene_number("7"),
+            Element::new("2", ElementType::Action, "A busy office."),
+            Element::new("3", ElementType::SceneHeading, "EXT. STREET - NIGHT"),
+        ];
+
+        let result = paginate(&elements, &config);
+        let numbers: Vec<Option<&str>> =
+            result.pages[0].elements.iter().map(|e| e.scene_number.as_deref()).collect();
+
+        assert_eq!(numbers, vec![Some("7"), None, Some("7A")]);
+        assert_eq!(result.scene_index[1].scene_number, "7A");
+    }
+}
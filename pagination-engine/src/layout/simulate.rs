@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use crate::types::{EditImpact, Element, ElementId, Page, PageConfig};
+use super::{ContinuationManager, LineCalculation, LineCalculator, PaginationState};
+
+/// Which pages would move if an element printed `new_line_count` lines
+///
+/// Answers "if this speech grows by three lines, which pages shift?" for
+/// editor hints: the document is wrapped once and placed twice, as it is and
+/// with the element resized, skipping everything a `PaginationResult` adds
+/// after placement. Returns None when no element has `element_id`.
+pub fn simulate_edit(
+    elements: &[Element],
+    config: &PageConfig,
+    element_id: &str,
+    new_line_count: u32,
+) -> Option<EditImpact> {
+    let target = elements.iter().position(|e| e.id.0 == element_id)?;
+
+    let calc = LineCalculator::new(config);
+    let mut line_table: Vec<Arc<LineCalculation>> = elements.iter().map(|e| Arc::new(calc.calculate(e))).collect();
+    let before = place_all(elements, config, &line_table);
+
+    line_table[target] = Arc::new(resized(&line_table[target], new_line_count));
+    let after = place_all(elements, config, &line_table);
+
+    let starts_before: Vec<_> = before.iter().map(page_start).collect();
+    let shifted_pages = after
+        .iter()
+        .enumerate()
+        .filter(|(i, page)| starts_before.get(*i) != Some(&page_start(page)))
+        .map(|(_, page)| page.identifier.clone())
+        .collect::<Vec<_>>();
+
+    Some(EditImpact {
+        page_count_before: before.len() as u32,
+        page_count_after: after.len() as u32,
+        first_shifted_page: shifted_pages.first().cloned(),
+        shifted_pages,
+    })
+}
+
+/// Pages of a placement pass over a prepared line table
+pub(super) fn place_all(elements: &[Element], config: &PageConfig, line_table: &[Arc<LineCalculation>]) -> Vec<Page> {
+    let continuation_mgr = ContinuationManager::new(config);
+    let mut state = PaginationState::new(config);
+    for idx in 0..elements.len() {
+        state.place(config, &continuation_mgr, &elements[idx..], &line_table[idx..]);
+    }
+    state.finalize(0, elements.len()).pages
+}
+
+/// Where a page's content begins: its first element and the line of that
+/// element it starts from
+pub(super) fn page_start(page: &Page) -> Option<(&ElementId, u32)> {
+    page.elements
+        .first()
+        .map(|first| (&first.element_id, first.line_range.as_ref().map_or(0, |range| range.start)))
+}
+
+/// A line calculation with `content_lines` lines, spacing unchanged
+///
+/// Added lines are blank, so split points land where the page ends rather
+/// than at sentence boundaries that don't exist yet.
+pub(super) fn resized(lines: &LineCalculation, content_lines: u32) -> LineCalculation {
+    let mut resized = lines.clone();
+    resized.total_lines = lines.total_lines - lines.content_lines + content_lines;
+    resized.content_lines = content_lines;
+    resized.wrapped_lines.resize(content_lines as usize);
+    if !resized.line_style_runs.is_empty() {
+        resized.line_style_runs.resize(content_lines as usize, Vec::new());
+    }
+    resized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ElementType, PageIdentifier};
+
+    fn script() -> Vec<Element> {
+        (0..6)
+            .flat_map(|i| {
+                vec![
+                    Element::new(format!("h{}", i), ElementType::SceneHeading, format!("INT. ROOM {} - DAY", i)),
+                    Element::new(format!("a{}", i), ElementType::Action, "Something happens.\n".repeat(12).trim_end()),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_simulate_edit_shifts_later_pages() {
+        let config = PageConfig::feature_film();
+        let elements = script();
+
+        let unchanged = simulate_edit(&elements, &config, "a0", 12).unwrap();
+        assert!(unchanged.shifted_pages.is_empty());
+        assert_eq!(unchanged.page_count_before, unchanged.page_count_after);
+
+        let grown = simulate_edit(&elements, &config, "a0", 40).unwrap();
+        assert_eq!(grown.first_shifted_page, Some(PageIdentifier::Sequential(2)));
+        assert!(grown.page_count_after > grown.page_count_before);
+        assert!(!grown.shifted_pages.contains(&PageIdentifier::Sequential(1)));
+
+        assert!(simulate_edit(&elements, &config, "missing", 3).is_none());
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use crate::types::{
+    Element, ElementId, ElementType, LinePosition, Page, PageConfig, PageElement, PageIdentifier, TitlePage,
+    TitlePageCorner,
+};
+use super::LineCalculator;
+
+/// Lay out a title page as page 0
+///
+/// Title, credit, authors and source are centered and stacked from
+/// `config.title_page.title_line`; the contact block and draft date print in
+/// their configured corners, the draft date above the contact when they
+/// share one. Each block is one placement with an element ID of
+/// `title_page:<block>` and its text and line positions filled in. The page
+/// is unnumbered and does not count towards the script's page numbers.
+pub fn layout_title_page(title_page: &TitlePage, config: &PageConfig) -> Page {
+    let calc = LineCalculator::new(config);
+    let settings = &config.title_page;
+    let spacing = settings.block_spacing as u32;
+    let mut page = Page::new(PageIdentifier::Sequential(0));
+
+    let authors = title_page.authors.join("\n");
+    let centered = [
+        ("title", Some(title_page.title.as_str())),
+        ("credit", title_page.credit.as_deref()),
+        ("authors", Some(authors.as_str())),
+        ("source", title_page.source.as_deref()),
+    ];
+
+    let mut line = settings.title_line.max(1) as u32;
+    for (block, text) in centered {
+        let Some(text) = text.filter(|text| !text.trim().is_empty()) else {
+            continue;
+        };
+        let lines = calc.calculate(&Element::new(block, ElementType::Centered, text)).wrapped_lines;
+        let xs = lines
+            .iter()
+            .map(|line| config.margins.left_pt() + (config.printable_width_pt() - calc.text_width_pt(&line)).max(0.0) / 2.0)
+            .collect();
+        page.elements.push(block_placement(block, line, lines.to_strings(), xs, &calc, config));
+        line += page.elements.last().map_or(0, |e| e.line_count as u32) + spacing;
+    }
+
+    let draft_date: Vec<String> = title_page.draft_date.iter().cloned().collect();
+    let corner_blocks = [
+        ("draft_date", draft_date, settings.draft_date_corner),
+        ("contact", title_page.contact.clone(), settings.contact_corner),
+    ];
+    for corner in [TitlePageCorner::TopLeft, TitlePageCorner::TopRight, TitlePageCorner::BottomLeft, TitlePageCorner::BottomRight] {
+        let blocks: Vec<(&str, &Vec<String>)> = corner_blocks
+            .iter()
+            .filter(|(_, lines, at)| *at == corner && !lines.is_empty())
+            .map(|(block, lines, _)| (*block, lines))
+            .collect();
+        if blocks.is_empty() {
+            continue;
+        }
+
+        let height: u32 = blocks.iter().map(|(_, lines)| lines.len() as u32).sum::<u32>() + spacing * (blocks.len() as u32 - 1);
+        let mut line = match corner {
+            TitlePageCorner::TopLeft | TitlePageCorner::TopRight => 1,
+            TitlePageCorner::BottomLeft | TitlePageCorner::BottomRight => {
+                (config.lines_per_page as u32 + 1).saturating_sub(height).max(1)
+            }
+        };
+
+        for (block, lines) in blocks {
+            let xs = lines
+                .iter()
+                .map(|line| match corner {
+                    TitlePageCorner::TopLeft | TitlePageCorner::BottomLeft => config.margins.left_pt(),
+                    TitlePageCorner::TopRight | TitlePageCorner::BottomRight => {
+                        config.margins.left_pt() + (config.printable_width_pt() - calc.text_width_pt(line)).max(0.0)
+                    }
+                })
+                .collect();
+            page.elements.push(block_placement(block, line, lines.clone(), xs, &calc, config));
+            line += lines.len() as u32 + spacing;
+        }
+    }
+
+    page.elements.sort_by_key(|e| e.start_line);
+    page.lines_used = page
+        .elements
+        .iter()
+        .map(|e| e.start_line.saturating_add(e.line_count).saturating_sub(1))
+        .max()
+        .unwrap_or(0);
+    page.content_hash = page.compute_content_hash(&HashMap::new());
+
+    page
+}
+
+/// One title page block starting on `line`, each line at its own x
+fn block_placement(
+    block: &str,
+    line: u32,
+    lines: Vec<String>,
+    xs: Vec<f64>,
+    calc: &LineCalculator,
+    config: &PageConfig,
+) -> PageElement {
+    let line_positions = xs
+        .into_iter()
+        .zip(&lines)
+        .enumerate()
+        .map(|(i, (x_pt, text))| LinePosition {
+            x_pt,
+            y_pt: config.margins.top_pt() + (line - 1 + i as u32) as f64 * config.line_height_pt,
+            width_pt: calc.text_width_pt(text),
+            height_pt: config.line_height_pt,
+        })
+        .collect();
+
+    PageElement {
+        element_id: ElementId::new(format!("title_page:{}", block)),
+        start_line: line.min(u8::MAX as u32) as u8,
+        line_count: lines.len().min(u8::MAX as usize) as u8,
+        is_continuation: false,
+        line_range: None,
+        continuation_prefix: None,
+        line_positions: Some(line_positions),
+        frame: None,
+        text: Some(lines),
+        style_runs: None,
+        column: None,
+        revision_marks: None,
+        scene_number: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placement<'a>(page: &'a Page, block: &str) -> &'a PageElement {
+        let id = format!("title_page:{}", block);
+        page.elements.iter().find(|e| e.element_id.0 == id).unwrap()
+    }
+
+    #[test]
+    fn test_title_page_layout() {
+        let config = PageConfig::feature_film();
+        let title_page = TitlePage {
+            credit: Some("Written by".to_string()),
+            authors: vec!["Jane Doe".to_string(), "John Roe".to_string()],
+            draft_date: Some("October 16, 2026".to_string()),
+            contact: vec!["Jane Doe".to_string(), "555-0100".to_string()],
+            ..TitlePage::new("THE LONG NIGHT")
+        };
+
+        let page = layout_title_page(&title_page, &config);
+
+        assert_eq!(page.identifier, PageIdentifier::Sequential(0));
+        assert!(page.page_number.is_none());
+
+        // Centered blocks stack from line 20 with a blank line between
+        let title = placement(&page, "title");
+        assert_eq!(title.start_line, 20);
+        let x = title.line_positions.as_ref().unwrap()[0].x_pt;
+        assert!((x - (108.0 + (432.0 - 14.0 * 7.2) / 2.0)).abs() < 0.001);
+        assert_eq!(placement(&page, "credit").start_line, 22);
+        assert_eq!(placement(&page, "authors").line_count, 2);
+
+        // Contact bottom-left, draft date bottom-right, both ending on the last line
+        let contact = placement(&page, "contact");
+        assert_eq!(contact.start_line, config.lines_per_page - 1);
+        assert_eq!(contact.line_positions.as_ref().unwrap()[0].x_pt, 108.0);
+        let date = placement(&page, "draft_date");
+        assert_eq!(date.start_line, config.lines_per_page);
+        let x = date.line_positions.as_ref().unwrap()[0].x_pt;
+        assert!((x - (108.0 + 432.0 - 16.0 * 7.2)).abs() < 0.001);
+        assert_eq!(page.lines_used, config.lines_per_page);
+    }
+
+    #[test]
+    fn test_title_page_shared_corner() {
+        let mut config = PageConfig::feature_film();
+        config.title_page.draft_date_corner = TitlePageCorner::BottomLeft;
+        let title_page = TitlePage {
+            draft_date: Some("Draft".to_string()),
+            contact: vec!["Agent".to_string()],
+            ..TitlePage::new("UNTITLED")
+        };
+
+        let page = layout_title_page(&title_page, &config);
+
+        // The contact stays nearest the edge, the date above it past a blank line
+        assert_eq!(placement(&page, "contact").start_line, config.lines_per_page);
+        assert_eq!(placement(&page, "draft_date").start_line, config.lines_per_page - 2);
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use crate::types::{
+    ConfigIssueSeverity, Element, ElementType, PageConfig, PaginationError, PaginationResult, RenderOptions,
+};
+use crate::utils::default_clock;
+use super::{paginate_cached, LineCache, LineCalculator};
+
+/// Paginate after checking that the input can be paginated faithfully
+///
+/// `paginate` accepts anything and may produce misleading positions for bad
+/// input; this rejects it with a typed error instead. Lines wrapped during
+/// the overflow check are reused by the pagination pass.
+pub fn try_paginate(elements: &[Element], config: &PageConfig) -> Result<PaginationResult, PaginationError> {
+    try_paginate_with_options(elements, config, &RenderOptions::default())
+}
+
+/// `try_paginate` attaching the extra rendering data selected in `options`
+pub fn try_paginate_with_options(
+    elements: &[Element],
+    config: &PageConfig,
+    options: &RenderOptions,
+) -> Result<PaginationResult, PaginationError> {
+    validate_config(config)?;
+    validate_elements(elements, config)?;
+
+    let mut cache = LineCache::new();
+    cache.begin_run();
+    let calc = LineCalculator::new(config);
+    for (element, lines) in elements.iter().zip(cache.get_or_calculate_all(&calc, elements)) {
+        let needed = lines.space_before as u32 + lines.total_lines;
+        if needed > u8::MAX as u32 {
+            return Err(PaginationError::LineCountOverflow {
+                id: element.id.clone(),
+                lines: needed,
+            });
+        }
+    }
+
+    Ok(paginate_cached(
+        elements,
+        config,
+        options,
+        default_clock().as_ref(),
+        &mut cache,
+    ))
+}
+
+/// Check the page geometry and element styles
+///
+/// Fails with the first error `PageConfig::validate` reports; warnings
+/// don't stop pagination.
+pub fn validate_config(config: &PageConfig) -> Result<(), PaginationError> {
+    match config.validate().into_iter().find(|issue| issue.severity == ConfigIssueSeverity::Error) {
+        Some(issue) => Err(PaginationError::InvalidConfig { field: issue.field, reason: issue.message }),
+        None => Ok(()),
+    }
+}
+
+/// Check element IDs and dialogue attribution
+pub fn validate_elements(elements: &[Element], config: &PageConfig) -> Result<(), PaginationError> {
+    let mut seen: HashMap<&str, usize> = HashMap::with_capacity(elements.len());
+    let mut previous: Option<ElementType> = None;
+
+    for (index, element) in elements.iter().enumerate() {
+        if element.id.0.is_empty() {
+            return Err(PaginationError::EmptyElementId { index });
+        }
+        if let Some(&first_index) = seen.get(element.id.0.as_str()) {
+            return Err(PaginationError::DuplicateElementId {
+                id: element.id.clone(),
+                first_index,
+                index,
+            });
+        }
+        seen.insert(element.id.0.as_str(), index);
+
+        let is_speech = matches!(element.element_type, ElementType::Dialogue | ElementType::Parenthetical);
+        let follows_cue = matches!(
+            previous,
+            Some(ElementType::Character | ElementType::Parenthetical | ElementType::Dialogue)
+        );
+        if is_speech && element.character_name.is_none() && !follows_cue {
+            return Err(PaginationError::DialogueWithoutCharacter {
+                id: element.id.clone(),
+                index,
+            });
+        }
+
+        if config.is_visible(element.element_type) {
+            previous = Some(element.element_type);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ElementId;
+
+    #[test]
+    fn test_valid_input_paginates() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::SceneHeading, "INT. OFFICE - DAY"),
+            Element::new("2", ElementType::Character, "SARAH"),
+            Element::new("3", ElementType::Dialogue, "Hello?"),
+        ];
+
+        let result = try_paginate(&elements, &config).unwrap();
+        assert_eq!(result.stats.element_count, 3);
+
+        let options = RenderOptions { include_geometry: true, ..Default::default() };
+        let result = try_paginate_with_options(&elements, &config, &options).unwrap();
+        assert!(result.pages[0].elements[0].line_positions.is_some());
+    }
+
+    #[test]
+    fn test_id_errors() {
+        let config = PageConfig::feature_film();
+
+        let empty = vec![Element::new("", ElementType::Action, "A busy office.")];
+        assert_eq!(
+            try_paginate(&empty, &config).unwrap_err(),
+            PaginationError::EmptyElementId { index: 0 }
+        );
+
+        let duplicate = vec![
+            Element::new("1", ElementType::Action, "A busy office."),
+            Element::new("1", ElementType::Action, "Phones ring."),
+        ];
+        assert_eq!(try_paginate(&duplicate, &config).unwrap_err().code(), "duplicate_element_id");
+    }
+
+    #[test]
+    fn test_dialogue_without_character() {
+        let config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("1", ElementType::Action, "A busy office."),
+            Element::new("2", ElementType::Dialogue, "Hello?"),
+        ];
+        assert_eq!(
+            try_paginate(&elements, &config).unwrap_err(),
+            PaginationError::DialogueWithoutCharacter { id: ElementId::new("2"), index: 1 }
+        );
+
+        let named = vec![
+            Element::new("1", ElementType::Action, "A busy office."),
+            Element::new("2", ElementType::Dialogue, "Hello?").with_character_name("SARAH"),
+        ];
+        assert!(try_paginate(&named, &config).is_ok());
+    }
+
+    #[test]
+    fn test_config_and_overflow_errors() {
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 0;
+        assert_eq!(try_paginate(&[], &config).unwrap_err().code(), "invalid_config");
+
+        // One line more than the printable height fits
+        config.lines_per_page = config.derived_lines_per_page() + 1;
+        assert_eq!(
+            try_paginate(&[], &config).unwrap_err(),
+            PaginationError::InvalidConfig {
+                field: "lines_per_page".to_string(),
+                reason: "is 56 but the printable height fits 55".to_string(),
+            }
+        );
+
+        let config = PageConfig::feature_film();
+        let huge = vec![Element::new("1", ElementType::Action, "Line.\n".repeat(300))];
+        assert!(matches!(
+            try_paginate(&huge, &config).unwrap_err(),
+            PaginationError::LineCountOverflow { lines, .. } if lines > 255
+        ));
+    }
+}
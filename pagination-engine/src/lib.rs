@@ -11,6 +11,22 @@
 //! - **Configuration-driven**: All format variations expressed through PageConfig
 //! - **No DOM measurement**: Math-based calculation only
 //! - **Fast**: Designed for <50ms pagination of 120+ page scripts
+//! - **Portable**: JavaScript bindings live behind the default `wasm` feature;
+//!   build with `default-features = false` for a plain Rust dependency
+//! - **Parallel wrapping**: the optional `rayon` feature wraps element text
+//!   across threads; page assembly stays sequential and deterministic.
+//!   `wasm-rayon` does the same in the browser on Web Workers started by
+//!   `initThreadPool`
+//! - **Schemas**: the optional `schema` feature emits JSON Schema for the
+//!   serialized types, for generating TypeScript declarations
+//! - **Fountain input**: the optional `fountain` feature parses Fountain
+//!   markup straight into elements
+//! - **Export**: `export_fdx` writes Final Draft XML with the computed page
+//!   breaks, for productions that need FDX deliverables, and `render_html`
+//!   lays the pages out as positioned HTML
+//! - **Reports**: `scene_report` outlines the scenes of a paginated script
+//!   for production breadcrumbs and outline panels, `estimate_runtime`
+//!   times it, and `extract_sides` picks out the pages for actor sides
 //!
 //! # Example
 //!
@@ -28,112 +44,38 @@
 //! println!("Total pages: {}", result.stats.page_count);
 //! ```
 
-use wasm_bindgen::prelude::*;
-
+pub mod export;
 pub mod layout;
+pub mod reports;
 pub mod types;
 pub mod utils;
 
-pub use layout::paginate;
-pub use types::*;
-
-/// Initialize panic hook for better error messages in WASM
-#[wasm_bindgen(start)]
-pub fn init() {
-    #[cfg(feature = "console_error_panic_hook")]
-    console_error_panic_hook::set_once();
-}
-
-/// Main entry point for pagination from JavaScript
-///
-/// # Arguments
-///
-/// * `elements_json` - JSON string of Element array
-/// * `config_json` - JSON string of PageConfig
-///
-/// # Returns
-///
-/// JSON string of PaginationResult
-#[wasm_bindgen]
-pub fn paginate_document(elements_json: &str, config_json: &str) -> Result<String, JsError> {
-    // Deserialize inputs
-    let elements: Vec<Element> = serde_json::from_str(elements_json)
-        .map_err(|e| JsError::new(&format!("Failed to parse elements: {}", e)))?;
-
-    let config: PageConfig = serde_json::from_str(config_json)
-        .map_err(|e| JsError::new(&format!("Failed to parse config: {}", e)))?;
-
-    // Run pagination
-    let result = paginate(&elements, &config);
-
-    // Serialize output
-    serde_json::to_string(&result)
-        .map_err(|e| JsError::new(&format!("Failed to serialize result: {}", e)))
-}
-
-/// Get the default Feature Film configuration as JSON
-#[wasm_bindgen]
-pub fn get_feature_film_config() -> Result<String, JsError> {
-    let config = PageConfig::feature_film();
-    serde_json::to_string(&config)
-        .map_err(|e| JsError::new(&format!("Failed to serialize config: {}", e)))
-}
+#[cfg(feature = "fountain")]
+pub mod fountain;
 
-/// Calculate lines for a single element (useful for preview)
-#[wasm_bindgen]
-pub fn calculate_element_lines(element_json: &str, config_json: &str) -> Result<u32, JsError> {
-    let element: Element = serde_json::from_str(element_json)
-        .map_err(|e| JsError::new(&format!("Failed to parse element: {}", e)))?;
+#[cfg(feature = "schema")]
+mod schema;
 
-    let config: PageConfig = serde_json::from_str(config_json)
-        .map_err(|e| JsError::new(&format!("Failed to parse config: {}", e)))?;
+#[cfg(feature = "wasm")]
+mod wasm;
 
-    let calculator = layout::LineCalculator::new(&config);
-    let lines = calculator.calculate(&element);
+#[cfg(feature = "wasm-rayon")]
+mod thread_pool;
 
-    Ok(lines.total_lines)
-}
-
-/// Version of the pagination engine
-#[wasm_bindgen]
-pub fn version() -> String {
-    env!("CARGO_PKG_VERSION").to_string()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_paginate_document() {
-        let elements_json = r#"[
-            {"id": "1", "element_type": "scene_heading", "content": "INT. OFFICE - DAY"},
-            {"id": "2", "element_type": "action", "content": "A busy office."}
-        ]"#;
-
-        let config_json = serde_json::to_string(&PageConfig::feature_film()).unwrap();
-
-        let result = paginate_document(elements_json, &config_json).unwrap();
-        let parsed: PaginationResult = serde_json::from_str(&result).unwrap();
-
-        assert_eq!(parsed.stats.page_count, 1);
-        assert_eq!(parsed.stats.element_count, 2);
-    }
-
-    #[test]
-    fn test_get_feature_film_config() {
-        let config_json = get_feature_film_config().unwrap();
-        let config: PageConfig = serde_json::from_str(&config_json).unwrap();
+pub use export::{export_fdx, render_html};
+pub use layout::{
+    estimate_page_count, paginate, paginate_locked, paginate_with_clock, paginate_with_options, LineCache,
+    PaginationBuilder, PaginationRun, Paginator, try_paginate,
+};
+pub use types::*;
+pub use reports::{estimate_runtime, extract_sides, scene_report};
+pub use utils::Clock;
 
-        assert_eq!(config.lines_per_page, 55);
-    }
+#[cfg(feature = "schema")]
+pub use schema::*;
 
-    #[test]
-    fn test_calculate_element_lines() {
-        let element_json = r#"{"id": "1", "element_type": "action", "content": "A short action."}"#;
-        let config_json = serde_json::to_string(&PageConfig::feature_film()).unwrap();
+#[cfg(feature = "wasm")]
+pub use wasm::*;
 
-        let lines = calculate_element_lines(element_json, &config_json).unwrap();
-        assert_eq!(lines, 1);
-    }
-}
+#[cfg(feature = "wasm-rayon")]
+pub use thread_pool::*;
@@ -0,0 +1,9 @@
+//! Summaries of a paginated script for production tools and outline panels
+
+mod runtime;
+mod scenes;
+mod sides;
+
+pub use runtime::*;
+pub use scenes::*;
+pub use sides::*;
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Element, ElementId, ElementType, PageConfig, PaginationResult};
+
+/// Screen time of one scene, from `estimate_runtime`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SceneRuntime {
+    pub heading_element: ElementId,
+
+    /// Scene heading text
+    pub slug: String,
+
+    pub seconds: f64,
+}
+
+/// Estimated screen time of a paginated script
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RuntimeEstimate {
+    /// Whole script, material before the first scene heading included
+    pub total_seconds: f64,
+
+    /// Each scene, in script order
+    pub scenes: Vec<SceneRuntime>,
+}
+
+/// Estimate screen time from the page lines each element takes
+///
+/// An element owns the page lines from its first line to the next
+/// element's, blank lines and (MORE)/(CONT'D) markers included, so a full
+/// page adds up to `lines_per_page` lines. Those are timed at
+/// `config.runtime`'s dialogue or action rate, which default to one page per
+/// minute.
+pub fn estimate_runtime(elements: &[Element], config: &PageConfig, result: &PaginationResult) -> RuntimeEstimate {
+    let per_page = 60.0 / config.lines_per_page.max(1) as f64;
+    let dialogue_rate = config.runtime.dialogue_seconds_per_line.unwrap_or(per_page);
+    let action_rate = config.runtime.action_seconds_per_line.unwrap_or(per_page);
+    let types: HashMap<&str, ElementType> = elements.iter().map(|e| (e.id.0.as_str(), e.element_type)).collect();
+
+    let mut seconds: HashMap<&str, f64> = HashMap::new();
+    for page in &result.pages {
+        let mut placed: Vec<_> = page.elements.iter().filter(|e| e.line_count > 0).collect();
+        placed.sort_by_key(|e| e.start_line - u8::from(e.continuation_prefix.is_some()));
+
+        for (i, page_element) in placed.iter().enumerate() {
+            let start = page_element.start_line - u8::from(page_element.continuation_prefix.is_some());
+            let end = placed
+                .get(i + 1)
+                .map_or(page.lines_used + 1, |next| next.start_line - u8::from(next.continuation_prefix.is_some()));
+            let id = page_element.element_id.0.as_str();
+            let rate = match types.get(id) {
+                Some(
+                    ElementType::Character
+                    | ElementType::Dialogue
+                    | ElementType::Parenthetical
+                    | ElementType::Lyrics
+                    | ElementType::DualDialogueLeft
+                    | ElementType::DualDialogueRight,
+                ) => dialogue_rate,
+                _ => action_rate,
+            };
+            *seconds.entry(id).or_default() += end.saturating_sub(start) as f64 * rate;
+        }
+    }
+
+    // Summed in script order so the total is the same on every run
+    let mut total_seconds = 0.0;
+    let mut scenes: Vec<SceneRuntime> = Vec::new();
+    for element in elements {
+        let element_seconds = seconds.get(element.id.0.as_str()).copied().unwrap_or(0.0);
+        total_seconds += element_seconds;
+        if element.element_type == ElementType::SceneHeading {
+            scenes.push(SceneRuntime {
+                heading_element: element.id.clone(),
+                slug: element.content.clone(),
+                seconds: 0.0,
+            });
+        }
+        if let Some(scene) = scenes.last_mut() {
+            scene.seconds += element_seconds;
+        }
+    }
+
+    RuntimeEstimate { total_seconds, scenes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+
+    #[test]
+    fn test_estimate_runtime() {
+        let mut config = PageConfig::feature_film();
+        let elements = vec![
+            Element::new("h1", ElementType::SceneHeading, "INT. OFFICE - DAY"),
+            Element::new("a1", ElementType::Action, "Typing.\n".repeat(120).trim_end()),
+            Element::new("h2", ElementType::SceneHeading, "EXT. STREET - NIGHT"),
+            Element::new("c2", ElementType::Character, "SARAH"),
+            Element::new("d2", ElementType::Dialogue, "Finally.").with_character_name("SARAH"),
+        ];
+        let result = paginate(&elements, &config);
+
+        // A page's worth of lines runs a minute: 120 lines of action plus
+        // the heading is a little over two pages
+        let estimate = estimate_runtime(&elements, &config, &result);
+        assert_eq!(estimate.scenes.len(), 2);
+        assert!((130.0..140.0).contains(&estimate.scenes[0].seconds), "{}", estimate.scenes[0].seconds);
+        let total: f64 = estimate.scenes.iter().map(|s| s.seconds).sum();
+        assert!((estimate.total_seconds - total).abs() < 1e-9);
+
+        config.runtime.dialogue_seconds_per_line = Some(10.0);
+        let slower = estimate_runtime(&elements, &config, &result);
+        assert!(slower.scenes[1].seconds > estimate.scenes[1].seconds);
+        assert_eq!(slower.scenes[0].seconds, estimate.scenes[0].seconds);
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::layout::number_scenes;
+use crate::types::{Element, ElementId, ElementType, PageIdentifier, PaginationResult};
+
+/// One scene of the outline, from `scene_report`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SceneSummary {
+    pub heading_element: ElementId,
+
+    /// Scene heading text ("INT. OFFICE - DAY")
+    pub slug: String,
+
+    /// Printed scene number ("12A")
+    pub scene_number: String,
+
+    /// Page the heading prints on (None if it wasn't placed)
+    pub start_page: Option<PageIdentifier>,
+
+    /// Number of pages the scene prints on
+    pub page_span: u32,
+
+    /// Length in eighths of a page, as in `PaginationResult::scene_lengths`
+    pub eighths: u32,
+
+    /// Elements in the scene, heading included
+    pub element_count: usize,
+
+    /// Lines of dialogue, parentheticals and lyrics
+    pub dialogue_lines: u32,
+
+    /// Lines of action
+    pub action_lines: u32,
+
+    /// Share of dialogue among dialogue and action lines, from 0.0 to 1.0
+    pub dialogue_ratio: f64,
+}
+
+/// Outline of the script's scenes, in script order
+///
+/// Elements before the first scene heading belong to no scene. Line counts
+/// come from the placed pages, so hidden elements count toward
+/// `element_count` only.
+pub fn scene_report(elements: &[Element], result: &PaginationResult) -> Vec<SceneSummary> {
+    let numbers = number_scenes(elements);
+    let eighths: HashMap<&str, u32> = result
+        .scene_lengths
+        .iter()
+        .map(|length| (length.heading_element.0.as_str(), length.eighths))
+        .collect();
+
+    let mut printed: HashMap<&str, u32> = HashMap::new();
+    for page_element in result.pages.iter().flat_map(|page| &page.elements) {
+        *printed.entry(page_element.element_id.0.as_str()).or_default() += page_element.line_count as u32;
+    }
+
+    let mut scenes: Vec<SceneSummary> = Vec::new();
+    let mut pages: Vec<HashSet<&PageIdentifier>> = Vec::new();
+    for element in elements {
+        if element.element_type == ElementType::SceneHeading {
+            let id = element.id.0.as_str();
+            scenes.push(SceneSummary {
+                heading_element: element.id.clone(),
+                slug: element.content.clone(),
+                scene_number: numbers.get(id).cloned().unwrap_or_default(),
+                start_page: result.get_page_for_element(id).cloned(),
+                page_span: 0,
+                eighths: eighths.get(id).copied().unwrap_or(0),
+                element_count: 0,
+                dialogue_lines: 0,
+                action_lines: 0,
+                dialogue_ratio: 0.0,
+            });
+            pages.push(HashSet::new());
+        }
+
+        let (Some(scene), Some(scene_pages)) = (scenes.last_mut(), pages.last_mut()) else {
+            continue;
+        };
+        scene.element_count += 1;
+        if let Some(position) = result.element_positions.get(&element.id.0) {
+            scene_pages.extend(&position.pages);
+        }
+
+        let lines = printed.get(element.id.0.as_str()).copied().unwrap_or(0);
+        match element.element_type {
+            ElementType::Dialogue
+            | ElementType::Parenthetical
+            | ElementType::Lyrics
+            | ElementType::DualDialogueLeft
+            | ElementType::DualDialogueRight => scene.dialogue_lines += lines,
+            ElementType::Action => scene.action_lines += lines,
+            _ => {}
+        }
+    }
+
+    for (scene, scene_pages) in scenes.iter_mut().zip(pages) {
+        scene.page_span = scene_pages.len() as u32;
+        let spoken = scene.dialogue_lines + scene.action_lines;
+        if spoken > 0 {
+            scene.dialogue_ratio = scene.dialogue_lines as f64 / spoken as f64;
+        }
+    }
+
+    scenes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::PageConfig;
+
+    #[test]
+    fn test_scene_report() {
+        let elements = vec![
+            Element::new("0", ElementType::Action, "FADE IN:"),
+            Element::new("h1", ElementType::SceneHeading, "INT. OFFICE - DAY"),
+            Element::new("a1", ElementType::Action, "Sarah types."),
+            Element::new("c1", ElementType::Character, "SARAH"),
+            Element::new("d1", ElementType::Dialogue, "Done.").with_character_name("SARAH"),
+            Element::new("h2", ElementType::SceneHeading, "EXT. STREET - NIGHT"),
+            Element::new("a2", ElementType::Action, "Rain.\n".repeat(70).trim_end()),
+        ];
+        let result = paginate(&elements, &PageConfig::feature_film());
+
+        let report = scene_report(&elements, &result);
+
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].slug, "INT. OFFICE - DAY");
+        assert_eq!(report[0].scene_number, "1");
+        assert_eq!(report[0].start_page, Some(PageIdentifier::Sequential(1)));
+        assert_eq!(report[0].element_count, 4);
+        assert_eq!((report[0].dialogue_lines, report[0].action_lines), (1, 1));
+        assert_eq!(report[0].dialogue_ratio, 0.5);
+        assert_eq!(report[1].page_span, 2);
+        assert_eq!(report[1].dialogue_ratio, 0.0);
+        assert!(report[1].eighths > 8);
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::layout::{cue_name, number_scenes};
+use crate::types::{Element, ElementType, Page, PageIdentifier, PaginationResult};
+
+/// Scenes to pull into sides, by number or by who speaks in them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct SidesFilter {
+    /// Printed scene numbers ("12", "12A")
+    pub scene_numbers: Vec<String>,
+
+    /// Character names, matched against cues without extensions and
+    /// ignoring case
+    pub characters: Vec<String>,
+}
+
+/// A page of sides: an original page holding selected material
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SidesPage {
+    /// The page as paginated, identifier unchanged
+    pub page: Page,
+
+    /// Line spans (first, last) of scenes that weren't selected, to strike
+    /// through when printing
+    pub struck_lines: Vec<(u8, u8)>,
+
+    /// Pages left out between the previous page of the sides and this one
+    pub omitted_before: Vec<PageIdentifier>,
+}
+
+/// Reduced page set for printing actor sides, from `extract_sides`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Sides {
+    pub pages: Vec<SidesPage>,
+
+    /// Pages left out after the last page of the sides
+    pub omitted_after: Vec<PageIdentifier>,
+}
+
+/// The pages of a paginated script that hold the scenes `filter` selects
+///
+/// A scene is selected when its number is listed or one of the listed
+/// characters has a cue in it. Every page with any of a selected scene is
+/// kept whole, with its original identifier; the other scenes sharing the
+/// page are marked in `struck_lines`, and the pages skipped are recorded so
+/// the printed sides can say what was left out.
+pub fn extract_sides(result: &PaginationResult, elements: &[Element], filter: &SidesFilter) -> Sides {
+    let numbers = number_scenes(elements);
+    let characters: HashSet<String> = filter.characters.iter().map(|name| name.trim().to_uppercase()).collect();
+
+    // Elements of the selected scenes, each scene running from its heading to the next
+    let headings: Vec<usize> = elements
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.element_type == ElementType::SceneHeading)
+        .map(|(i, _)| i)
+        .collect();
+    let mut selected: HashSet<&str> = HashSet::new();
+    for (i, &start) in headings.iter().enumerate() {
+        let scene = &elements[start..headings.get(i + 1).copied().unwrap_or(elements.len())];
+        let by_number = numbers.get(scene[0].id.0.as_str()).is_some_and(|number| filter.scene_numbers.contains(number));
+        let by_character = scene
+            .iter()
+            .any(|e| e.element_type == ElementType::Character && characters.contains(&cue_name(e)));
+        if by_number || by_character {
+            selected.extend(scene.iter().map(|e| e.id.0.as_str()));
+        }
+    }
+
+    let mut pages = Vec::new();
+    let mut omitted = Vec::new();
+    for page in &result.pages {
+        let is_selected = |id: &str| selected.contains(id);
+        if !page.elements.iter().any(|e| is_selected(&e.element_id.0)) {
+            omitted.push(page.identifier.clone());
+            continue;
+        }
+
+        let mut struck_lines: Vec<(u8, u8)> = Vec::new();
+        let mut previous_struck = false;
+        for page_element in page.elements.iter().filter(|e| e.line_count > 0) {
+            if is_selected(&page_element.element_id.0) {
+                previous_struck = false;
+                continue;
+            }
+            let first = page_element.start_line - u8::from(page_element.continuation_prefix.is_some());
+            let last = page_element.start_line + page_element.line_count - 1;
+            match struck_lines.last_mut() {
+                Some(span) if previous_struck => span.1 = last,
+                _ => struck_lines.push((first, last)),
+            }
+            previous_struck = true;
+        }
+
+        pages.push(SidesPage {
+            page: page.clone(),
+            struck_lines,
+            omitted_before: std::mem::take(&mut omitted),
+        });
+    }
+
+    Sides { pages, omitted_after: omitted }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::paginate;
+    use crate::types::PageConfig;
+
+    fn script() -> Vec<Element> {
+        vec![
+            Element::new("h1", ElementType::SceneHeading, "INT. OFFICE - DAY").with_force_page_break(),
+            Element::new("h2", ElementType::SceneHeading, "INT. HALL - DAY"),
+            Element::new("a2", ElementType::Action, "Empty."),
+            Element::new("h3", ElementType::SceneHeading, "EXT. STREET - NIGHT"),
+            Element::new("c3", ElementType::Character, "SARAH (V.O.)"),
+            Element::new("d3", ElementType::Dialogue, "Here.").with_character_name("SARAH").with_force_page_break(),
+            Element::new("h4", ElementType::SceneHeading, "INT. CAR - NIGHT"),
+            Element::new("a4", ElementType::Action, "Driving."),
+        ]
+    }
+
+    #[test]
+    fn test_extract_sides_by_character() {
+        let elements = script();
+        let result = paginate(&elements, &PageConfig::feature_film());
+        let filter = SidesFilter { characters: vec!["sarah".to_string()], ..Default::default() };
+
+        let sides = extract_sides(&result, &elements, &filter);
+
+        assert_eq!(sides.pages.len(), 1);
+        let page = &sides.pages[0];
+        assert_eq!(page.page.identifier, PageIdentifier::Sequential(2));
+        assert_eq!(page.omitted_before, vec![PageIdentifier::Sequential(1)]);
+        assert_eq!(sides.omitted_after, vec![PageIdentifier::Sequential(3)]);
+
+        // Scene 2 shares the page and is struck, heading through action
+        let hall = &page.page.elements[0];
+        let empty = &page.page.elements[1];
+        assert_eq!(page.struck_lines, vec![(hall.start_line, empty.start_line)]);
+    }
+
+    #[test]
+    fn test_extract_sides_by_scene_number() {
+        let elements = script();
+        let result = paginate(&elements, &PageConfig::feature_film());
+        let filter = SidesFilter { scene_numbers: vec!["1".to_string(), "4".to_string()], ..Default::default() };
+
+        let sides = extract_sides(&result, &elements, &filter);
+
+        let kept: Vec<_> = sides.pages.iter().map(|p| p.page.identifier.clone()).collect();
+        assert_eq!(kept, vec![PageIdentifier::Sequential(1), PageIdentifier::Sequential(3)]);
+        assert_eq!(sides.pages[1].omitted_before, vec![PageIdentifier::Sequential(2)]);
+        assert!(sides.pages[0].struck_lines.is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+//! JSON Schema for the serialized model (enabled by the `schema` feature)
+//!
+//! Generated from the same derives serde uses, so tagged enums such as
+//! `PageIdentifier` and the `#[serde(default)]` fields are described exactly
+//! as they appear on the wire. Feed the output to a JSON Schema to TypeScript
+//! generator, or use `typescript_definitions`, which the wasm package ships
+//! as its `.d.ts` declarations.
+
+use std::collections::BTreeMap;
+
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+use serde_json::Value;
+
+use crate::reports::{RuntimeEstimate, SceneSummary, Sides, SidesFilter};
+use crate::types::{
+    CheatConstraints, CheatResult, CheatSuggestion, ConfigIssue, EditImpact, Element, ElementDiff, ElementEdit,
+    EngineError, LockedPageSet, Page, PageConfig, PageDiff, PageSliceElement, PaginationDelta, PaginationError,
+    PaginationResult, PartialPageConfig, RenderOptions, ResultOptions, RevisionReport, RevisionTracker, TitlePage,
+};
+
+/// Schemas for the engine's inputs and outputs, keyed by type name
+///
+/// Covers every type the JavaScript bindings take or return: `Element`,
+/// `ElementEdit`, `ElementDiff`, `PageConfig`, `PartialPageConfig`,
+/// `RenderOptions`, `ResultOptions`, `LockedPageSet`, `TitlePage`, `Page`,
+/// `PaginationResult`, `PageSliceElement`, `PaginationDelta`, `PageDiff`,
+/// `EditImpact`, `CheatConstraints`, `CheatResult`, `CheatSuggestion`,
+/// `SceneSummary`, `RuntimeEstimate`, `SidesFilter`, `Sides`,
+/// `RevisionReport`, `RevisionTracker`, `ConfigIssue`, `PaginationError` and
+/// `EngineError`.
+pub fn schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![
+        ("Element", schema_for!(Element)),
+        ("ElementEdit", schema_for!(ElementEdit)),
+        ("ElementDiff", schema_for!(ElementDiff)),
+        ("PageConfig", schema_for!(PageConfig)),
+        ("PartialPageConfig", schema_for!(PartialPageConfig)),
+        ("RenderOptions", schema_for!(RenderOptions)),
+        ("ResultOptions", schema_for!(ResultOptions)),
+        ("LockedPageSet", schema_for!(LockedPageSet)),
+        ("TitlePage", schema_for!(TitlePage)),
+        ("Page", schema_for!(Page)),
+        ("PaginationResult", schema_for!(PaginationResult)),
+        ("PageSliceElement", schema_for!(PageSliceElement)),
+        ("PaginationDelta", schema_for!(PaginationDelta)),
+        ("PageDiff", schema_for!(PageDiff)),
+        ("EditImpact", schema_for!(EditImpact)),
+        ("CheatConstraints", schema_for!(CheatConstraints)),
+        ("CheatResult", schema_for!(CheatResult)),
+        ("CheatSuggestion", schema_for!(CheatSuggestion)),
+        ("SceneSummary", schema_for!(SceneSummary)),
+        ("RuntimeEstimate", schema_for!(RuntimeEstimate)),
+        ("SidesFilter", schema_for!(SidesFilter)),
+        ("Sides", schema_for!(Sides)),
+        ("RevisionReport", schema_for!(RevisionReport)),
+        ("RevisionTracker", schema_for!(RevisionTracker)),
+        ("ConfigIssue", schema_for!(ConfigIssue)),
+        ("PaginationError", schema_for!(PaginationError)),
+        ("EngineError", schema_for!(EngineError)),
+    ]
+}
+
+/// All schemas as one JSON object, keyed by type name
+pub fn schemas_json() -> serde_json::Value {
+    schemas()
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), serde_json::to_value(schema).unwrap_or_default()))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// TypeScript declarations for the schemas' types and everything they reference
+///
+/// Structs become interfaces, with `#[serde(default)]` fields optional, and
+/// enums unions of their serialized forms. The wasm package embeds the
+/// checked-in copy, `src/verso.d.ts`, which a test keeps identical to this.
+pub fn typescript_definitions() -> String {
+    let mut types = BTreeMap::new();
+    for (name, root) in schemas() {
+        let Ok(Value::Object(mut schema)) = serde_json::to_value(root) else {
+            continue;
+        };
+        if let Some(Value::Object(definitions)) = schema.remove("definitions") {
+            types.extend(definitions);
+        }
+        schema.remove("$schema");
+        schema.remove("title");
+        types.insert(name.to_string(), Value::Object(schema));
+    }
+
+    let mut out = String::from("// Generated from the engine's JSON Schemas by `typescript_definitions`; do not edit.\n");
+    for (name, schema) in &types {
+        out.push('\n');
+        push_doc(&mut out, schema, "");
+        if schema.get("properties").is_some() && schema.get("additionalProperties").is_none() {
+            out.push_str(&format!("export interface {} {}\n", name, ts_object(schema, "")));
+        } else {
+            out.push_str(&format!("export type {} = {};\n", name, ts_type(schema, "")));
+        }
+    }
+    out
+}
+
+fn push_doc(out: &mut String, schema: &Value, indent: &str) {
+    let Some(description) = schema.get("description").and_then(Value::as_str) else {
+        return;
+    };
+    let lines: Vec<&str> = description.lines().collect();
+    if let [line] = lines.as_slice() {
+        out.push_str(&format!("{}/** {} */\n", indent, line));
+        return;
+    }
+    out.push_str(&format!("{}/**\n", indent));
+    for line in lines {
+        out.push_str(&format!("{} *{}{}\n", indent, if line.is_empty() { "" } else { " " }, line));
+    }
+    out.push_str(&format!("{} */\n", indent));
+}
+
+/// Object literal type from `properties`, one documented field per line
+fn ts_object(schema: &Value, indent: &str) -> String {
+    let required: Vec<&str> = schema["required"].as_array().map_or_else(Vec::new, |fields| {
+        fields.iter().filter_map(Value::as_str).collect()
+    });
+    let inner = format!("{}  ", indent);
+    let mut out = String::from("{\n");
+    if let Some(properties) = schema["properties"].as_object() {
+        for (field, property) in properties {
+            push_doc(&mut out, property, &inner);
+            let optional = if required.contains(&field.as_str()) { "" } else { "?" };
+            out.push_str(&format!("{}{}{}: {};\n", inner, field, optional, ts_type(property, &inner)));
+        }
+    }
+    out.push_str(indent);
+    out.push('}');
+    out
+}
+
+fn ts_type(schema: &Value, indent: &str) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return reference.rsplit('/').next().unwrap_or(reference).to_string();
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string));
+    }
+    for key in ["allOf", "anyOf", "oneOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            return union(variants.iter().map(|variant| ts_type(variant, indent)));
+        }
+    }
+    match schema.get("type") {
+        Some(Value::Array(types)) => union(types.iter().map(|t| {
+            let mut single = schema.clone();
+            single["type"] = t.clone();
+            ts_type(&single, indent)
+        })),
+        Some(Value::String(t)) => match t.as_str() {
+            "null" => "null".to_string(),
+            "boolean" => "boolean".to_string(),
+            "integer" | "number" => "number".to_string(),
+            "string" => "string".to_string(),
+            "array" => match schema.get("items") {
+                // Rust tuples
+                Some(Value::Array(items)) => {
+                    let items: Vec<String> = items.iter().map(|item| ts_type(item, indent)).collect();
+                    format!("[{}]", items.join(", "))
+                }
+                Some(item) => {
+                    let item = ts_type(item, indent);
+                    if item.contains(" | ") { format!("({})[]", item) } else { format!("{}[]", item) }
+                }
+                None => "unknown[]".to_string(),
+            },
+            "object" => match schema.get("additionalProperties") {
+                Some(Value::Object(_)) => format!("Record<string, {}>", ts_type(&schema["additionalProperties"], indent)),
+                _ if schema.get("properties").is_some() => ts_object(schema, indent),
+                _ => "Record<string, unknown>".to_string(),
+            },
+            _ => "unknown".to_string(),
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+fn union(types: impl Iterator<Item = String>) -> String {
+    let mut members: Vec<String> = Vec::new();
+    for member in types {
+        if !members.contains(&member) {
+            members.push(member);
+        }
+    }
+    members.join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schemas_describe_wire_format() {
+        let schemas = schemas_json();
+
+        let result = &schemas["PaginationResult"];
+        let identifier = &result["definitions"]["PageIdentifier"];
+        assert!(identifier.to_string().contains("\"type\""));
+        assert!(identifier.to_string().contains("Sequential"));
+
+        let element = &schemas["Element"];
+        let required = element["required"].as_array().unwrap();
+        assert!(required.iter().any(|field| field == "element_type"));
+        assert!(!required.iter().any(|field| field == "character_name"));
+    }
+
+    #[test]
+    fn test_typescript_definitions_match_checked_in_file() {
+        let generated = typescript_definitions();
+
+        assert!(generated.contains("export interface Element {"));
+        assert!(generated.contains("  element_type: ElementType;"));
+        assert!(generated.contains("  character_name?: string | null;"));
+        assert!(generated.contains("export type PageIdentifier = {"));
+
+        // Regenerate with VERSO_UPDATE_TYPES=1 after changing a serialized type
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/verso.d.ts");
+        if std::env::var_os("VERSO_UPDATE_TYPES").is_some() {
+            std::fs::write(path, &generated).unwrap();
+        }
+        assert!(
+            generated == std::fs::read_to_string(path).unwrap_or_default(),
+            "src/verso.d.ts is out of date; rerun with VERSO_UPDATE_TYPES=1"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+// Worker side of `initThreadPool` (see thread_pool.rs)
+//
+// wasm-pack places this file at snippets/<crate>-<hash>/src/, three levels
+// below the generated module the workers load.
+
+function nextMessage(target, type) {
+  return new Promise((resolve) => {
+    target.addEventListener('message', function onMessage({ data }) {
+      if (data?.type !== type) return;
+      target.removeEventListener('message', onMessage);
+      resolve(data);
+    });
+  });
+}
+
+if (typeof WorkerGlobalScope !== 'undefined' && self instanceof WorkerGlobalScope) {
+  nextMessage(self, 'verso_pool_init').then(async ({ module, memory, pool }) => {
+    const engine = await import('../../../verso_pagination_engine.js');
+    await engine.default({ module_or_path: module, memory });
+    postMessage({ type: 'verso_pool_ready' });
+    engine.runPoolWorker(pool);
+  });
+}
+
+// Held for the life of the page: browsers may collect workers that only
+// share memory with us, and late workers still read the pool
+let started = null;
+
+export async function startWorkers(module, memory, pool) {
+  if (pool.numThreads() === 0) {
+    throw new Error('initThreadPool needs at least one thread');
+  }
+
+  const init = { type: 'verso_pool_init', module, memory, pool: pool.address() };
+  const workers = await Promise.all(
+    Array.from({ length: pool.numThreads() }, async () => {
+      const worker = new Worker(new URL('./thread_pool.js', import.meta.url), { type: 'module' });
+      worker.postMessage(init);
+      await nextMessage(worker, 'verso_pool_ready');
+      return worker;
+    })
+  );
+
+  pool.build();
+  started = { pool, workers };
+}
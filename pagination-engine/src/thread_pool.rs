@@ -0,0 +1,96 @@
+//! Web Worker thread pool for WebAssembly builds (enabled by the
+//! `wasm-rayon` feature)
+//!
+//! Browsers give WebAssembly no threads of its own, so each rayon thread
+//! runs in a Web Worker that instantiates this module on the same shared
+//! memory. Once `initThreadPool` resolves, line calculation fans out across
+//! the workers exactly as the native `rayon` feature does across threads.
+//!
+//! Shared memory needs the `atomics` and `bulk-memory` target features,
+//! which means rebuilding std on nightly (`npm run build:wasm:threads`), and
+//! a page served cross-origin isolated (`Cross-Origin-Opener-Policy:
+//! same-origin`, `Cross-Origin-Embedder-Policy: require-corp`).
+
+use std::io;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+
+use rayon::{ThreadBuilder, ThreadPoolBuilder};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(module = "/src/thread_pool.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = startWorkers)]
+    fn start_workers(module: JsValue, memory: JsValue, pool: WorkerPool) -> js_sys::Promise;
+}
+
+/// Rayon threads waiting for a Web Worker to run them
+///
+/// Owned by the JS side, which keeps it for the life of the page; workers
+/// reach it through `address`.
+#[wasm_bindgen]
+pub struct WorkerPool {
+    num_threads: usize,
+    sender: Sender<ThreadBuilder>,
+    receiver: Mutex<Receiver<ThreadBuilder>>,
+}
+
+#[wasm_bindgen]
+impl WorkerPool {
+    #[wasm_bindgen(js_name = numThreads)]
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    /// Where workers find this pool, for `runPoolWorker`
+    pub fn address(&self) -> *const WorkerPool {
+        self
+    }
+
+    /// Install the global rayon pool, queueing each of its threads for a worker
+    ///
+    /// Call once every worker has loaded the module. Threads wait in the
+    /// queue for workers still on their way to `runPoolWorker`.
+    pub fn build(&mut self) -> Result<(), JsError> {
+        let sender = self.sender.clone();
+        ThreadPoolBuilder::new()
+            .num_threads(self.num_threads)
+            .spawn_handler(move |thread| {
+                sender.send(thread).map_err(|_| io::Error::other("worker pool was dropped"))
+            })
+            .build_global()
+            .map_err(|e| JsError::new(&format!("Failed to start thread pool: {}", e)))
+    }
+}
+
+/// Start `num_threads` Web Workers and make them rayon's global pool
+///
+/// Await once before paginating, e.g. with `navigator.hardwareConcurrency`.
+#[wasm_bindgen(js_name = initThreadPool)]
+pub fn init_thread_pool(num_threads: usize) -> js_sys::Promise {
+    let (sender, receiver) = channel();
+    let pool = WorkerPool {
+        num_threads,
+        sender,
+        receiver: Mutex::new(receiver),
+    };
+    start_workers(wasm_bindgen::module(), wasm_bindgen::memory(), pool)
+}
+
+/// Run one rayon thread on the calling worker; returns when the pool shuts down
+///
+/// `pool` must be the `WorkerPool::address` of a pool that is never freed.
+#[wasm_bindgen(js_name = runPoolWorker)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn run_pool_worker(pool: *const WorkerPool) -> Result<(), JsError> {
+    // SAFETY: startWorkers keeps the pool for the life of the page
+    let pool = unsafe { &*pool };
+    let thread = pool
+        .receiver
+        .lock()
+        .map_err(|_| JsError::new("Worker pool lock was poisoned"))?
+        .recv()
+        .map_err(|_| JsError::new("Worker pool closed before handing out a thread"))?;
+    thread.run();
+    Ok(())
+}
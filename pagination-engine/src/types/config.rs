@@ -1,21 +1,20 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
-use super::ElementType;
+use super::{
+    ConfigIssue, ConfigIssueSeverity, Element, ElementType, PageBreak, PageIdentifier, PaginationWarning, WarningType,
+};
 
 /// Paper size definitions
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum PaperSize {
+    #[default]
     UsLetter,  // 8.5" x 11"
     A4,        // 210mm x 297mm
 }
 
-impl Default for PaperSize {
-    fn default() -> Self {
-        Self::UsLetter
-    }
-}
-
 impl PaperSize {
     /// Width in points (1 inch = 72 points)
     pub fn width_pt(&self) -> f64 {
@@ -36,6 +35,7 @@ impl PaperSize {
 
 /// Margin configuration in inches
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MarginConfig {
     pub top: f64,
     pub bottom: f64,
@@ -47,7 +47,8 @@ impl Default for MarginConfig {
     fn default() -> Self {
         Self {
             top: 1.0,
-            bottom: 1.0,
+            // 55 lines at 6 per inch end 5/6" above the bottom edge
+            bottom: 5.0 / 6.0,
             left: 1.5,
             right: 1.0,
         }
@@ -61,8 +62,34 @@ impl MarginConfig {
     pub fn right_pt(&self) -> f64 { self.right * 72.0 }
 }
 
+/// Where a split element prefers to break
+///
+/// Each preference falls back to the next: paragraph break, then sentence
+/// end, then any line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SplitPreference {
+    #[default]
+    AnyLine,
+    SentenceEnd,
+    ParagraphBreak,
+}
+
+/// Direction text runs in, and the side of the page element indents are measured from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TextDirection {
+    #[default]
+    Ltr,
+    /// Hebrew, Arabic: lines align right and the layout is mirrored
+    Rtl,
+}
+
 /// Style configuration for each element type
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ElementStyle {
     /// Left margin from page's printable area in inches
     pub margin_left: f64,
@@ -91,14 +118,34 @@ pub struct ElementStyle {
     /// Minimum lines that must appear on new page after split
     pub min_lines_after_split: u8,
 
+    /// Boundary a split moves back to, within `min_lines_before_split`
+    #[serde(default)]
+    pub split_preference: SplitPreference,
+
     /// Must keep with following element
     pub keep_with_next: bool,
 
     /// Number of following lines required if keep_with_next is true
     pub keep_with_next_lines: u8,
 
+    /// Must not open a page without the end of the preceding element
+    #[serde(default)]
+    pub keep_with_previous: bool,
+
     /// Force uppercase for this element
     pub force_uppercase: bool,
+
+    /// Text direction for this element type (None for the page's `text_direction`)
+    #[serde(default)]
+    pub text_direction: Option<TextDirection>,
+
+    /// Whether this element occupies space on the page (false = zero-line anchor)
+    #[serde(default = "default_true")]
+    pub visible_in_pagination: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for ElementStyle {
@@ -113,9 +160,13 @@ impl Default for ElementStyle {
             can_split: true,
             min_lines_before_split: 2,
             min_lines_after_split: 2,
+            split_preference: SplitPreference::AnyLine,
             keep_with_next: false,
             keep_with_next_lines: 0,
+            keep_with_previous: false,
             force_uppercase: false,
+            text_direction: None,
+            visible_in_pagination: true,
         }
     }
 }
@@ -138,6 +189,7 @@ impl ElementStyle {
                 can_split: true,
                 min_lines_before_split: 2,
                 min_lines_after_split: 2,
+                split_preference: SplitPreference::ParagraphBreak,
                 ..Self::default()
             },
 
@@ -163,6 +215,15 @@ impl ElementStyle {
                 ..Self::default()
             },
 
+            // Speech printed in one column of a dual dialogue block
+            ElementType::DualDialogueLeft | ElementType::DualDialogueRight => Self {
+                margin_left: 0.5,
+                max_chars_per_line: 28,
+                space_before: 0,
+                can_split: false,
+                ..Self::default()
+            },
+
             ElementType::Parenthetical => Self {
                 margin_left: 1.6,   // 3.1" from page left
                 margin_right: 2.3,  // 2.9" from page right
@@ -181,6 +242,7 @@ impl ElementStyle {
                 space_after: 1,
                 force_uppercase: true,
                 can_split: false,
+                keep_with_previous: true,
                 ..Self::default()
             },
 
@@ -199,13 +261,127 @@ impl ElementStyle {
                 ..Self::default()
             },
 
+            ElementType::Note => Self {
+                space_before: 1,
+                can_split: false,
+                visible_in_pagination: false,
+                ..Self::default()
+            },
+
+            ElementType::Lyrics => Self {
+                margin_left: 1.0,   // Dialogue margins
+                margin_right: 1.5,
+                max_chars_per_line: 35,
+                space_before: 0,
+                can_split: true,
+                min_lines_before_split: 2,
+                min_lines_after_split: 2,
+                ..Self::default()
+            },
+
+            ElementType::Centered => Self {
+                space_before: 1,
+                can_split: false,
+                ..Self::default()
+            },
+
+            ElementType::OmittedScene => Self {
+                space_before: 2,
+                force_uppercase: true,
+                can_split: false,
+                ..Self::default()
+            },
+
+            ElementType::OmittedPage => Self {
+                space_before: 0,
+                can_split: false,
+                ..Self::default()
+            },
+
+            // AV scripts: two 2.9" columns with a 0.2" gutter
+            ElementType::AvVideo => Self {
+                max_chars_per_line: 29,
+                space_before: 1,
+                can_split: true,
+                min_lines_before_split: 2,
+                min_lines_after_split: 2,
+                keep_with_next: true,
+                keep_with_next_lines: 1,
+                ..Self::default()
+            },
+
+            ElementType::AvAudio => Self {
+                margin_left: 3.1,
+                max_chars_per_line: 29,
+                space_before: 1,
+                can_split: true,
+                min_lines_before_split: 2,
+                min_lines_after_split: 2,
+                ..Self::default()
+            },
+
+            // Outline structure only - never printed
+            ElementType::Section | ElementType::Synopsis => Self {
+                space_before: 0,
+                can_split: false,
+                visible_in_pagination: false,
+                ..Self::default()
+            },
+
             _ => Self::default(),
         }
     }
 }
 
-/// How dialogue continuation markers are formatted
+/// Partial style applied over an element type's default for a single element
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ElementStyleOverride {
+    pub margin_left: Option<f64>,
+    pub margin_right: Option<f64>,
+    pub max_chars_per_line: Option<u8>,
+    pub space_before: Option<u8>,
+    pub space_after: Option<u8>,
+    pub line_spacing: Option<f64>,
+    pub can_split: Option<bool>,
+    pub min_lines_before_split: Option<u8>,
+    pub min_lines_after_split: Option<u8>,
+    pub split_preference: Option<SplitPreference>,
+    pub keep_with_next: Option<bool>,
+    pub keep_with_next_lines: Option<u8>,
+    pub keep_with_previous: Option<bool>,
+    pub force_uppercase: Option<bool>,
+    pub text_direction: Option<TextDirection>,
+}
+
+impl ElementStyle {
+    /// Apply an override, keeping this style's value wherever the override is unset
+    pub fn merged(&self, o: &ElementStyleOverride) -> Self {
+        Self {
+            margin_left: o.margin_left.unwrap_or(self.margin_left),
+            margin_right: o.margin_right.unwrap_or(self.margin_right),
+            max_chars_per_line: o.max_chars_per_line.unwrap_or(self.max_chars_per_line),
+            space_before: o.space_before.unwrap_or(self.space_before),
+            space_after: o.space_after.unwrap_or(self.space_after),
+            line_spacing: o.line_spacing.unwrap_or(self.line_spacing),
+            can_split: o.can_split.unwrap_or(self.can_split),
+            min_lines_before_split: o.min_lines_before_split.unwrap_or(self.min_lines_before_split),
+            min_lines_after_split: o.min_lines_after_split.unwrap_or(self.min_lines_after_split),
+            split_preference: o.split_preference.unwrap_or(self.split_preference),
+            keep_with_next: o.keep_with_next.unwrap_or(self.keep_with_next),
+            keep_with_next_lines: o.keep_with_next_lines.unwrap_or(self.keep_with_next_lines),
+            keep_with_previous: o.keep_with_previous.unwrap_or(self.keep_with_previous),
+            force_uppercase: o.force_uppercase.unwrap_or(self.force_uppercase),
+            text_direction: o.text_direction.or(self.text_direction),
+            visible_in_pagination: self.visible_in_pagination,
+        }
+    }
+}
+
+/// How continuation markers are formatted
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ContinuationStyle {
     /// Marker at bottom of page (e.g., "(MORE)")
     pub more_marker: String,
@@ -215,6 +391,10 @@ pub struct ContinuationStyle {
 
     /// Whether to use continuation markers at all
     pub enabled: bool,
+
+    /// Markers for specific element types, replacing the ones above
+    #[serde(default)]
+    pub by_type: HashMap<ElementType, TypeContinuationStyle>,
 }
 
 impl Default for ContinuationStyle {
@@ -223,12 +403,77 @@ impl Default for ContinuationStyle {
             more_marker: "(MORE)".to_string(),
             contd_marker: "(CONT'D)".to_string(),
             enabled: true,
+            by_type: HashMap::new(),
         }
     }
 }
 
+impl ContinuationStyle {
+    /// Markers used when an element of this type breaks across pages
+    ///
+    /// A `by_type` entry wins. Otherwise dialogue, lyrics and parentheticals
+    /// use the global markers and other types continue without any.
+    pub fn for_type(&self, element_type: ElementType) -> TypeContinuationStyle {
+        if !self.enabled {
+            return TypeContinuationStyle::default();
+        }
+        if let Some(style) = self.by_type.get(&element_type) {
+            return style.clone();
+        }
+
+        match element_type {
+            ElementType::Dialogue | ElementType::Lyrics | ElementType::Parenthetical => TypeContinuationStyle {
+                more_marker: Some(self.more_marker.clone()),
+                contd_marker: Some(self.contd_marker.clone()),
+                ..TypeContinuationStyle::default()
+            },
+            _ => TypeContinuationStyle::default(),
+        }
+    }
+}
+
+/// Continuation markers for one element type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct TypeContinuationStyle {
+    /// Marker at the bottom of the page before the break (None for no marker)
+    pub more_marker: Option<String>,
+
+    /// Marker at the top of the next page; speech appends it to the
+    /// character name, other types print it on its own
+    pub contd_marker: Option<String>,
+
+    /// Column the markers print at, in characters from the printable left
+    /// edge (None for the character cue's indent)
+    pub indent: Option<u8>,
+
+    /// Whether each marker takes a page line; when false it prints in the
+    /// margin and content keeps the line
+    pub consumes_line: bool,
+}
+
+impl Default for TypeContinuationStyle {
+    fn default() -> Self {
+        Self {
+            more_marker: None,
+            contd_marker: None,
+            indent: None,
+            consumes_line: true,
+        }
+    }
+}
+
+impl TypeContinuationStyle {
+    /// Page lines taken by the MORE marker
+    pub fn more_lines(&self) -> u32 {
+        u32::from(self.more_marker.is_some() && self.consumes_line)
+    }
+}
+
 /// Orphan/widow control settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct OrphanControlConfig {
     /// Scene heading must have at least N lines after it on same page
     pub scene_heading_min_following: u8,
@@ -241,6 +486,11 @@ pub struct OrphanControlConfig {
 
     /// Minimum dialogue lines after a split
     pub dialogue_min_after_split: u8,
+
+    /// Move a dialogue split back to the last line ending a sentence, as
+    /// long as `dialogue_min_before_split` lines stay on the page
+    #[serde(default)]
+    pub prefer_sentence_breaks: bool,
 }
 
 impl Default for OrphanControlConfig {
@@ -250,12 +500,325 @@ impl Default for OrphanControlConfig {
             character_min_dialogue_lines: 2,
             dialogue_min_before_split: 2,
             dialogue_min_after_split: 2,
+            prefer_sentence_breaks: false,
+        }
+    }
+}
+
+/// Languages with built-in hyphenation rules
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum HyphenationLanguage {
+    #[default]
+    EnUs,
+    EnGb,
+    De,
+    Fr,
+    Es,
+}
+
+/// Word hyphenation settings for line wrapping
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HyphenationConfig {
+    /// Break words at hyphenation points instead of wrapping whole words
+    pub enabled: bool,
+
+    /// Language whose hyphenation rules apply
+    pub language: HyphenationLanguage,
+
+    /// Words with their break points marked by hyphens ("tes-ta-ment"),
+    /// used instead of the language rules for those words
+    #[serde(default)]
+    pub dictionary: Vec<String>,
+}
+
+/// Act break handling for TV scripts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ActBreakConfig {
+    /// Print act breaks as "END OF ..." (content "ACT ONE" prints "END OF ACT ONE")
+    pub end_of_act_text: bool,
+
+    /// Start the next act on a fresh page
+    pub new_page_after: bool,
+}
+
+/// Header printed on a page that continues an element split from the page before
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ContinuedHeader {
+    /// Header text
+    pub text: String,
+
+    /// Lines reserved at the top of the page, including blank lines under the text
+    pub lines: u8,
+}
+
+impl Default for ContinuedHeader {
+    fn default() -> Self {
+        Self {
+            text: "CONTINUED:".to_string(),
+            lines: 2,
         }
     }
 }
 
+/// Judgment calls for pages that end early because content was pushed forward
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct PageBalanceConfig {
+    /// Stretch or deliberately shorten such pages (off keeps the plain short page)
+    pub enabled: bool,
+
+    /// Most free lines that are stretched away; larger remainders leave a short page
+    pub max_stretch_lines: u8,
+
+    /// Most blank lines added to any one gap between elements
+    pub max_lines_per_gap: u8,
+}
+
+impl Default for PageBalanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_stretch_lines: 2,
+            max_lines_per_gap: 1,
+        }
+    }
+}
+
+/// Reading pace for `estimate_runtime`
+///
+/// Rates left unset follow the one-page-per-minute convention: a full page
+/// of `lines_per_page` lines takes sixty seconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct RuntimeConfig {
+    /// Seconds per page line of dialogue, parentheticals, cues and lyrics
+    pub dialogue_seconds_per_line: Option<f64>,
+
+    /// Seconds per page line of action and everything else
+    pub action_seconds_per_line: Option<f64>,
+}
+
+/// Side-by-side layout of dual dialogue blocks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct DualDialogueConfig {
+    /// Width of each column in inches; the right column starts this far from the left margin
+    pub column_width: f64,
+
+    /// Widest line in a column, capping the element's own `max_chars_per_line`
+    pub max_chars_per_line: u8,
+}
+
+impl Default for DualDialogueConfig {
+    fn default() -> Self {
+        Self {
+            column_width: 3.0,
+            max_chars_per_line: 28,
+        }
+    }
+}
+
+/// Running header with the draft name and date
+///
+/// Page number placement is `PageConfig::page_numbering`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct HeaderFooterConfig {
+    /// Draft name, e.g. "Blue Revision"
+    pub draft_name: Option<String>,
+
+    pub draft_date: Option<String>,
+
+    /// Leave the first page without the header
+    pub skip_first_page: bool,
+
+    /// Page lines reserved for the header, including blank lines under it
+    /// (0 prints it in the top margin and keeps every line for content)
+    pub header_lines: u8,
+}
+
+impl HeaderFooterConfig {
+    /// Header text, or None without a draft name or date
+    pub fn text(&self) -> Option<String> {
+        let parts: Vec<&str> = [&self.draft_name, &self.draft_date]
+            .into_iter()
+            .flatten()
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" - "))
+    }
+}
+
+/// Corner of the title page a block prints in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TitlePageCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Placement of the title page blocks (see `TitlePage`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct TitlePageConfig {
+    /// Page line the centered title starts on
+    pub title_line: u8,
+
+    /// Blank lines between blocks (title, credit, authors, source; and
+    /// blocks sharing a corner)
+    pub block_spacing: u8,
+
+    pub contact_corner: TitlePageCorner,
+
+    pub draft_date_corner: TitlePageCorner,
+}
+
+impl Default for TitlePageConfig {
+    fn default() -> Self {
+        Self {
+            title_line: 20,
+            block_spacing: 1,
+            contact_corner: TitlePageCorner::BottomLeft,
+            draft_date_corner: TitlePageCorner::BottomRight,
+        }
+    }
+}
+
+/// Where the page number prints on the page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PageNumberPosition {
+    #[default]
+    TopRight,
+    TopCenter,
+    BottomCenter,
+    BottomRight,
+}
+
+/// Page number conventions (shooting vs submission drafts differ)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct PageNumberingConfig {
+    /// Number of the first page
+    pub start_number: u32,
+
+    /// Leave the first page unnumbered
+    pub suppress_first_page: bool,
+
+    /// Label format; `{n}` is replaced by the page number (e.g. "{n}." gives "42.")
+    pub format: String,
+
+    pub position: PageNumberPosition,
+}
+
+impl Default for PageNumberingConfig {
+    fn default() -> Self {
+        Self {
+            start_number: 1,
+            suppress_first_page: true,
+            format: "{n}.".to_string(),
+            position: PageNumberPosition::TopRight,
+        }
+    }
+}
+
+impl PageNumberingConfig {
+    /// Printed label for a page, or None if the page is unnumbered
+    pub fn label(&self, identifier: &PageIdentifier) -> Option<String> {
+        if self.suppress_first_page && *identifier == PageIdentifier::Sequential(self.start_number) {
+            return None;
+        }
+        // An omitted page keeps its plain number; the page itself says OMITTED
+        let number = match identifier {
+            PageIdentifier::Omitted(n) => n.to_string(),
+            other => other.display(),
+        };
+        Some(self.format.replace("{n}", &number))
+    }
+}
+
+/// Optional rendering data attached to the paginated pages
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct RenderOptions {
+    /// Attach absolute x/y positions (in points) for every line of every element
+    pub include_geometry: bool,
+
+    /// Attach the exact wrapped text each element prints on each page
+    pub include_text: bool,
+
+    /// Record the rule behind every page break in
+    /// `PaginationResult::break_explanations`
+    pub explain: bool,
+}
+
+/// How far `paginate_to_target` may "cheat" the layout, either way from the
+/// base configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct CheatConstraints {
+    /// Most lines per page that may be added or removed
+    pub max_lines_per_page_change: u8,
+
+    /// Most characters dialogue lines may gain or lose
+    pub max_dialogue_width_change: u8,
+}
+
+impl Default for CheatConstraints {
+    fn default() -> Self {
+        Self {
+            max_lines_per_page_change: 2,
+            max_dialogue_width_change: 2,
+        }
+    }
+}
+
+/// Character widths for a proportional font
+///
+/// Replaces the fixed `char_width_pt` when wrapping: each line holds as much
+/// text as fits in the width of `max_chars_per_line` monospace characters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct FontMetrics {
+    /// Advance width of characters missing from `widths`, in points
+    pub default_width_pt: f64,
+
+    /// Advance width of individual characters, in points
+    pub widths: HashMap<char, f64>,
+}
+
+impl FontMetrics {
+    /// Advance width of a character in hundredths of a point
+    pub fn char_width_cpt(&self, c: char) -> usize {
+        let width = self.widths.get(&c).copied().unwrap_or(self.default_width_pt);
+        (width * 100.0).round().max(0.0) as usize
+    }
+}
+
 /// Complete page configuration - ALL format variations expressed here
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PageConfig {
     pub paper_size: PaperSize,
 
@@ -274,11 +837,73 @@ pub struct PageConfig {
     /// Styles for each element type
     pub element_styles: HashMap<ElementType, ElementStyle>,
 
+    /// Styles for integrator-defined element types, by `Element::custom_type` name
+    #[serde(default)]
+    pub custom_styles: HashMap<String, ElementStyle>,
+
     /// Dialogue continuation configuration
     pub continuation_style: ContinuationStyle,
 
     /// Orphan/widow control settings
     pub orphan_control: OrphanControlConfig,
+
+    /// Hyphenation settings (off by default)
+    #[serde(default)]
+    pub hyphenation: HyphenationConfig,
+
+    /// Notes production draft: render notes as boxed text that takes up space
+    #[serde(default)]
+    pub show_notes: bool,
+
+    /// Page number start, format and placement
+    #[serde(default)]
+    pub page_numbering: PageNumberingConfig,
+
+    /// END OF ACT text and act page breaks
+    #[serde(default)]
+    pub act_breaks: ActBreakConfig,
+
+    /// Header reserved on pages that continue a split element (None for no header)
+    #[serde(default)]
+    pub continued_header: Option<ContinuedHeader>,
+
+    /// Proportional font widths (None for the monospace `char_width_pt`)
+    #[serde(default)]
+    pub font_metrics: Option<FontMetrics>,
+
+    /// Direction of the script's text (elements may override it in their style)
+    #[serde(default)]
+    pub text_direction: TextDirection,
+
+    /// Count East Asian wide characters (CJK) as two monospace cells when
+    /// wrapping; ignored with `font_metrics`
+    #[serde(default)]
+    pub east_asian_width: bool,
+
+    /// Stretching or shortening pages that end with a few free lines
+    #[serde(default)]
+    pub page_balance: PageBalanceConfig,
+
+    /// Column width for dual dialogue blocks
+    #[serde(default)]
+    pub dual_dialogue: DualDialogueConfig,
+
+    /// Block placement on the title page
+    #[serde(default)]
+    pub title_page: TitlePageConfig,
+
+    /// Draft name and date header on every page
+    #[serde(default)]
+    pub header_footer: HeaderFooterConfig,
+
+    /// Reading pace for runtime estimates
+    #[serde(default)]
+    pub runtime: RuntimeConfig,
+
+    /// Breaks pinned by an external locking workflow, honored wherever the
+    /// element they follow is placed
+    #[serde(default)]
+    pub forced_breaks: Vec<PageBreak>,
 }
 
 impl Default for PageConfig {
@@ -300,8 +925,19 @@ impl PageConfig {
         element_styles.insert(ElementType::Transition, ElementStyle::default_for(ElementType::Transition));
         element_styles.insert(ElementType::ActBreak, ElementStyle::default_for(ElementType::ActBreak));
         element_styles.insert(ElementType::PageBreak, ElementStyle::default_for(ElementType::PageBreak));
+        element_styles.insert(ElementType::DualDialogueLeft, ElementStyle::default_for(ElementType::DualDialogueLeft));
+        element_styles.insert(ElementType::DualDialogueRight, ElementStyle::default_for(ElementType::DualDialogueRight));
         element_styles.insert(ElementType::Shot, ElementStyle::default_for(ElementType::Shot));
         element_styles.insert(ElementType::BlankLine, ElementStyle::default_for(ElementType::BlankLine));
+        element_styles.insert(ElementType::Note, ElementStyle::default_for(ElementType::Note));
+        element_styles.insert(ElementType::Lyrics, ElementStyle::default_for(ElementType::Lyrics));
+        element_styles.insert(ElementType::Centered, ElementStyle::default_for(ElementType::Centered));
+        element_styles.insert(ElementType::Section, ElementStyle::default_for(ElementType::Section));
+        element_styles.insert(ElementType::Synopsis, ElementStyle::default_for(ElementType::Synopsis));
+        element_styles.insert(ElementType::AvVideo, ElementStyle::default_for(ElementType::AvVideo));
+        element_styles.insert(ElementType::AvAudio, ElementStyle::default_for(ElementType::AvAudio));
+        element_styles.insert(ElementType::OmittedScene, ElementStyle::default_for(ElementType::OmittedScene));
+        element_styles.insert(ElementType::OmittedPage, ElementStyle::default_for(ElementType::OmittedPage));
 
         Self {
             paper_size: PaperSize::UsLetter,
@@ -310,8 +946,378 @@ impl PageConfig {
             line_height_pt: 12.0,
             margins: MarginConfig::default(),
             element_styles,
+            custom_styles: HashMap::new(),
             continuation_style: ContinuationStyle::default(),
             orphan_control: OrphanControlConfig::default(),
+            hyphenation: HyphenationConfig::default(),
+            show_notes: false,
+            page_numbering: PageNumberingConfig::default(),
+            act_breaks: ActBreakConfig::default(),
+            continued_header: None,
+            font_metrics: None,
+            text_direction: TextDirection::Ltr,
+            east_asian_width: false,
+            page_balance: PageBalanceConfig::default(),
+            dual_dialogue: DualDialogueConfig::default(),
+            title_page: TitlePageConfig::default(),
+            header_footer: HeaderFooterConfig::default(),
+            runtime: RuntimeConfig::default(),
+            forced_breaks: Vec::new(),
+        }
+    }
+
+    /// AV/commercial script: video and audio columns side by side
+    ///
+    /// One-inch side margins give each column 3.15" (31 characters) with a
+    /// 0.2" gutter. Rows are `AvVideo` elements each followed by their
+    /// `AvAudio` cell.
+    pub fn av_script() -> Self {
+        let mut config = Self::feature_film();
+        config.margins.left = 1.0;
+
+        let video = config.element_styles.entry(ElementType::AvVideo).or_default();
+        video.max_chars_per_line = 31;
+
+        let audio = config.element_styles.entry(ElementType::AvAudio).or_default();
+        audio.margin_left = 3.35;
+        audio.max_chars_per_line = 31;
+
+        config
+    }
+
+    /// Multi-camera sitcom: double-spaced dialogue, action in capitals, and
+    /// each act ending in END OF ACT and starting a new page
+    pub fn multi_cam_sitcom() -> Self {
+        let mut config = Self::feature_film();
+        config.act_breaks = ActBreakConfig { end_of_act_text: true, new_page_after: true };
+
+        let action = config.element_styles.entry(ElementType::Action).or_default();
+        action.force_uppercase = true;
+
+        for element_type in [ElementType::Dialogue, ElementType::DualDialogueLeft, ElementType::DualDialogueRight] {
+            let dialogue = config.element_styles.entry(element_type).or_default();
+            dialogue.line_spacing = 2.0;
+        }
+
+        config
+    }
+
+    /// Single-camera TV drama or comedy: feature film layout with acts that
+    /// end in END OF ACT and start a new page
+    pub fn single_cam_tv() -> Self {
+        let mut config = Self::feature_film();
+        config.act_breaks = ActBreakConfig { end_of_act_text: true, new_page_after: true };
+        config
+    }
+
+    /// BBC screenplay on A4: the feature film layout with the extra lines and
+    /// the narrower page A4 gives
+    pub fn bbc_screenplay() -> Self {
+        let mut config = Self::feature_film();
+        config.paper_size = PaperSize::A4;
+        config.lines_per_page = 58;
+
+        // 595pt less 2.5" of margins leaves 57 characters
+        for element_type in [ElementType::Action, ElementType::SceneHeading, ElementType::Shot, ElementType::Centered] {
+            let style = config.element_styles.entry(element_type).or_default();
+            style.max_chars_per_line = 57;
+        }
+
+        config
+    }
+
+    /// US stage play: dialogue across the full width under a centered cue,
+    /// indented stage directions, and each act starting a new page
+    pub fn stage_play_us() -> Self {
+        let mut config = Self::feature_film();
+        config.act_breaks.new_page_after = true;
+
+        let character = config.element_styles.entry(ElementType::Character).or_default();
+        character.margin_left = 2.5;  // Centered on the page
+
+        let dialogue = config.element_styles.entry(ElementType::Dialogue).or_default();
+        dialogue.margin_left = 0.0;
+        dialogue.margin_right = 0.0;
+        dialogue.max_chars_per_line = 60;
+
+        let parenthetical = config.element_styles.entry(ElementType::Parenthetical).or_default();
+        parenthetical.margin_left = 1.0;
+        parenthetical.max_chars_per_line = 40;
+
+        // Stage directions sit 3.25" from the page's left edge
+        let action = config.element_styles.entry(ElementType::Action).or_default();
+        action.margin_left = 1.75;
+        action.max_chars_per_line = 35;
+
+        let heading = config.element_styles.entry(ElementType::SceneHeading).or_default();
+        heading.margin_left = 2.0;
+
+        config
+    }
+
+    /// Audio drama: cues at the left margin beside double-spaced speech, and
+    /// sound and music cues in capitals
+    pub fn audio_drama() -> Self {
+        let mut config = Self::feature_film();
+
+        let character = config.element_styles.entry(ElementType::Character).or_default();
+        character.margin_left = 0.0;
+
+        let dialogue = config.element_styles.entry(ElementType::Dialogue).or_default();
+        dialogue.margin_left = 1.5;
+        dialogue.margin_right = 0.0;
+        dialogue.max_chars_per_line = 45;
+        dialogue.line_spacing = 2.0;
+
+        let parenthetical = config.element_styles.entry(ElementType::Parenthetical).or_default();
+        parenthetical.margin_left = 1.5;
+
+        let action = config.element_styles.entry(ElementType::Action).or_default();
+        action.force_uppercase = true;
+
+        config
+    }
+
+    /// Feature film format on A4, derived from the US Letter layout
+    ///
+    /// Margins, indents and line lengths shrink with A4's narrower width, and
+    /// the page holds as many more lines as its taller printable area fits,
+    /// so a script paginates to what it would on A4 rather than on Letter.
+    pub fn a4_international() -> Self {
+        let mut config = Self::feature_film();
+        let letter = Self::feature_film();
+        config.paper_size = PaperSize::A4;
+
+        let x = PaperSize::A4.width_pt() / PaperSize::UsLetter.width_pt();
+        let y = PaperSize::A4.height_pt() / PaperSize::UsLetter.height_pt();
+        config.margins.left *= x;
+        config.margins.right *= x;
+        config.margins.top *= y;
+        config.margins.bottom *= y;
+
+        let width = config.printable_width_pt() / letter.printable_width_pt();
+        let height = config.printable_height_pt() / letter.printable_height_pt();
+        let scale_chars = |chars: u8| (chars as f64 * width).floor() as u8;
+        for style in config.element_styles.values_mut() {
+            style.margin_left *= width;
+            style.margin_right *= width;
+            style.max_chars_per_line = scale_chars(style.max_chars_per_line);
+        }
+        config.dual_dialogue.column_width *= width;
+        config.dual_dialogue.max_chars_per_line = scale_chars(config.dual_dialogue.max_chars_per_line);
+        config.lines_per_page = (letter.lines_per_page as f64 * height).floor() as u8;
+
+        config
+    }
+
+    /// Lines that fit the printable height at `line_height_pt`
+    pub fn derived_lines_per_page(&self) -> u8 {
+        (self.printable_height_pt() / self.line_height_pt).floor().clamp(1.0, u8::MAX as f64) as u8
+    }
+
+    /// Characters that fit between a style's margins at `char_width_pt`
+    pub fn derived_chars_per_line(&self, style: &ElementStyle) -> u8 {
+        self.chars_in(self.printable_width_pt() - (style.margin_left + style.margin_right) * 72.0)
+    }
+
+    fn chars_in(&self, width_pt: f64) -> u8 {
+        (width_pt / self.char_width_pt).floor().clamp(1.0, u8::MAX as f64) as u8
+    }
+
+    /// Replace `lines_per_page` and every style's `max_chars_per_line` with
+    /// what the paper size, margins and character size fit
+    ///
+    /// Returns a configuration warning for each value set by hand that
+    /// disagreed with the geometry.
+    pub fn derive_metrics(&mut self) -> Vec<PaginationWarning> {
+        let mut warnings = Vec::new();
+
+        let lines = self.derived_lines_per_page();
+        if self.lines_per_page != lines {
+            warnings.push(config_warning(format!(
+                "lines_per_page was {} but the printable height fits {}",
+                self.lines_per_page, lines
+            )));
+            self.lines_per_page = lines;
+        }
+
+        let mut element_styles = std::mem::take(&mut self.element_styles);
+        let mut custom_styles = std::mem::take(&mut self.custom_styles);
+        let styles = element_styles
+            .iter_mut()
+            .map(|(element_type, style)| (format!("element_styles.{:?}", element_type), style))
+            .chain(custom_styles.iter_mut().map(|(name, style)| (format!("custom_styles.{}", name), style)));
+        for (field, style) in sorted_by_field(styles) {
+            let chars = self.derived_chars_per_line(style);
+            if style.max_chars_per_line != chars {
+                warnings.push(config_warning(format!(
+                    "{}.max_chars_per_line was {} but its margins fit {}",
+                    field, style.max_chars_per_line, chars
+                )));
+                style.max_chars_per_line = chars;
+            }
+        }
+        self.element_styles = element_styles;
+        self.custom_styles = custom_styles;
+
+        warnings
+    }
+
+    /// This configuration with `derive_metrics` applied, for building on a
+    /// preset: `PageConfig::bbc_screenplay().with_derived_metrics()`
+    pub fn with_derived_metrics(mut self) -> Self {
+        self.derive_metrics();
+        self
+    }
+
+    /// Budgets set by hand that the page geometry can't hold, left unchanged
+    ///
+    /// Screenplay conventions round generously, so a line may end inside its
+    /// style's right margin as long as it stays within the page's.
+    pub fn metric_warnings(&self) -> Vec<PaginationWarning> {
+        let mut warnings = Vec::new();
+
+        let lines = self.derived_lines_per_page();
+        if self.lines_per_page > lines {
+            warnings.push(config_warning(format!(
+                "lines_per_page is {} but the printable height fits {}",
+                self.lines_per_page, lines
+            )));
+        }
+
+        let styles = self
+            .element_styles
+            .iter()
+            .map(|(element_type, style)| (format!("element_styles.{:?}", element_type), style))
+            .chain(self.custom_styles.iter().map(|(name, style)| (format!("custom_styles.{}", name), style)));
+        for (field, style) in sorted_by_field(styles) {
+            let fits = self.chars_in(self.printable_width_pt() - style.margin_left * 72.0);
+            if style.visible_in_pagination && style.max_chars_per_line > fits {
+                warnings.push(config_warning(format!(
+                    "{}.max_chars_per_line is {} but only {} fit before the right margin",
+                    field, style.max_chars_per_line, fits
+                )));
+            }
+        }
+
+        warnings
+    }
+
+    /// Settings pagination can't honor, errors first
+    ///
+    /// Errors are values pagination can't work with: zero or non-finite
+    /// sizes, more lines than the printable height fits, margins that leave
+    /// no room for text, and split minimums a page can't hold. Warnings are
+    /// core element types falling back to the generic style.
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        let mut issue = |field: String, severity: ConfigIssueSeverity, message: String| {
+            issues.push(ConfigIssue { field, severity, message })
+        };
+        let error = ConfigIssueSeverity::Error;
+        let warning = ConfigIssueSeverity::Warning;
+
+        if self.lines_per_page == 0 {
+            issue("lines_per_page".to_string(), error, "must be at least 1".to_string());
+        }
+        if !(self.char_width_pt.is_finite() && self.char_width_pt > 0.0) {
+            issue("char_width_pt".to_string(), error, "must be a positive number".to_string());
+        }
+        if !(self.line_height_pt.is_finite() && self.line_height_pt > 0.0) {
+            issue("line_height_pt".to_string(), error, "must be a positive number".to_string());
+        } else if self.lines_per_page > self.derived_lines_per_page() {
+            issue(
+                "lines_per_page".to_string(),
+                error,
+                format!("is {} but the printable height fits {}", self.lines_per_page, self.derived_lines_per_page()),
+            );
+        }
+
+        if let Some(metrics) = &self.font_metrics {
+            let positive = |width: f64| width.is_finite() && width > 0.0;
+            if !positive(metrics.default_width_pt) || !metrics.widths.values().all(|&w| positive(w)) {
+                issue("font_metrics".to_string(), error, "widths must be positive numbers".to_string());
+            }
+        }
+
+        let printable_width_in = self.printable_width_pt() / 72.0;
+        let styles = self
+            .element_styles
+            .iter()
+            .map(|(element_type, style)| (format!("element_styles.{:?}", element_type), style))
+            .chain(self.custom_styles.iter().map(|(name, style)| (format!("custom_styles.{}", name), style)));
+        for (field, style) in sorted_by_field(styles) {
+            if style.max_chars_per_line == 0 {
+                issue(format!("{}.max_chars_per_line", field), error, "must be at least 1".to_string());
+            }
+            if style.visible_in_pagination && style.margin_left + style.margin_right >= printable_width_in {
+                issue(
+                    format!("{}.margin_left", field),
+                    error,
+                    format!(
+                        "margins of {}in and {}in leave no room on a {:.2}in wide printable area",
+                        style.margin_left, style.margin_right, printable_width_in
+                    ),
+                );
+            }
+            let split_minimum = style.min_lines_before_split as u32 + style.min_lines_after_split as u32;
+            if style.can_split && self.lines_per_page > 0 && split_minimum > self.lines_per_page as u32 {
+                issue(
+                    format!("{}.min_lines_before_split", field),
+                    error,
+                    format!("a split keeps {} lines but a page holds {}", split_minimum, self.lines_per_page),
+                );
+            }
+        }
+
+        let orphan = &self.orphan_control;
+        let dialogue_minimum = orphan.dialogue_min_before_split as u32 + orphan.dialogue_min_after_split as u32;
+        if self.lines_per_page > 0 && dialogue_minimum > self.lines_per_page as u32 {
+            issue(
+                "orphan_control.dialogue_min_before_split".to_string(),
+                error,
+                format!("a dialogue split keeps {} lines but a page holds {}", dialogue_minimum, self.lines_per_page),
+            );
+        }
+
+        for element_type in CORE_TYPES {
+            if !self.element_styles.contains_key(&element_type) {
+                issue(
+                    format!("element_styles.{:?}", element_type),
+                    warning,
+                    "is missing, so the generic 60-character style applies".to_string(),
+                );
+            }
+        }
+
+        issues.sort_by_key(|issue| issue.severity != ConfigIssueSeverity::Error);
+        issues
+    }
+
+    /// Names accepted by `preset`
+    pub const PRESETS: &'static [&'static str] = &[
+        "feature_film",
+        "a4_international",
+        "av_script",
+        "multi_cam_sitcom",
+        "single_cam_tv",
+        "bbc_screenplay",
+        "stage_play_us",
+        "audio_drama",
+    ];
+
+    /// Look up a preset by name (snake_case or kebab-case, see `PRESETS`)
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.replace('-', "_").as_str() {
+            "feature_film" => Some(Self::feature_film()),
+            "a4_international" => Some(Self::a4_international()),
+            "av_script" => Some(Self::av_script()),
+            "multi_cam_sitcom" => Some(Self::multi_cam_sitcom()),
+            "single_cam_tv" => Some(Self::single_cam_tv()),
+            "bbc_screenplay" => Some(Self::bbc_screenplay()),
+            "stage_play_us" => Some(Self::stage_play_us()),
+            "audio_drama" => Some(Self::audio_drama()),
+            _ => None,
         }
     }
 
@@ -331,14 +1337,48 @@ impl PageConfig {
                     can_split: true,
                     min_lines_before_split: 2,
                     min_lines_after_split: 2,
+                    split_preference: SplitPreference::AnyLine,
                     keep_with_next: false,
                     keep_with_next_lines: 0,
+                    keep_with_previous: false,
                     force_uppercase: false,
+                    text_direction: None,
+                    visible_in_pagination: true,
                 };
                 &DEFAULT
             })
     }
 
+    /// Get the style for an element: its custom type's if one is configured,
+    /// otherwise its element type's
+    pub fn base_style(&self, element: &Element) -> &ElementStyle {
+        element
+            .custom_type
+            .as_ref()
+            .and_then(|name| self.custom_styles.get(name))
+            .unwrap_or_else(|| self.style_for(element.element_type))
+    }
+
+    /// Get the effective style for an element, applying its override if any
+    pub fn resolved_style(&self, element: &Element) -> Cow<'_, ElementStyle> {
+        let style = self.base_style(element);
+        match &element.style_override {
+            Some(style_override) => Cow::Owned(style.merged(style_override)),
+            None => Cow::Borrowed(style),
+        }
+    }
+
+    /// Direction an element's text runs in, after its style and override
+    pub fn text_direction_for(&self, element: &Element) -> TextDirection {
+        self.resolved_style(element).text_direction.unwrap_or(self.text_direction)
+    }
+
+    /// Whether elements of this type take up lines on the page
+    pub fn is_visible(&self, element_type: ElementType) -> bool {
+        self.style_for(element_type).visible_in_pagination
+            || (element_type == ElementType::Note && self.show_notes)
+    }
+
     /// Calculate printable width in points
     pub fn printable_width_pt(&self) -> f64 {
         self.paper_size.width_pt() - self.margins.left_pt() - self.margins.right_pt()
@@ -350,6 +1390,32 @@ impl PageConfig {
     }
 }
 
+/// A configuration warning, not tied to any element
+fn config_warning(message: String) -> PaginationWarning {
+    PaginationWarning {
+        element_id: None,
+        warning_type: WarningType::ConfigurationWarning,
+        message,
+    }
+}
+
+/// Styles named by their config field, in a stable order for warnings
+/// Element types nearly every script uses, which should have a style of their own
+const CORE_TYPES: [ElementType; 6] = [
+    ElementType::SceneHeading,
+    ElementType::Action,
+    ElementType::Character,
+    ElementType::Dialogue,
+    ElementType::Parenthetical,
+    ElementType::Transition,
+];
+
+fn sorted_by_field<S>(styles: impl Iterator<Item = (String, S)>) -> Vec<(String, S)> {
+    let mut styles: Vec<(String, S)> = styles.collect();
+    styles.sort_by(|a, b| a.0.cmp(&b.0));
+    styles
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,6 +1428,164 @@ mod tests {
         assert!(config.element_styles.contains_key(&ElementType::SceneHeading));
     }
 
+    #[test]
+    fn test_fountain_element_defaults() {
+        let config = PageConfig::feature_film();
+
+        let lyrics = config.style_for(ElementType::Lyrics);
+        let dialogue = config.style_for(ElementType::Dialogue);
+        assert_eq!(lyrics.max_chars_per_line, dialogue.max_chars_per_line);
+        assert!(lyrics.can_split);
+
+        assert!(config.is_visible(ElementType::Centered));
+        assert!(!config.is_visible(ElementType::Section));
+        assert!(!config.is_visible(ElementType::Synopsis));
+    }
+
+    #[test]
+    fn test_resolved_style_override() {
+        let config = PageConfig::feature_film();
+        let mut element = Element::new("1", ElementType::Action, "Email text.");
+        assert!(config.resolved_style(&element).can_split);
+
+        element.style_override = Some(ElementStyleOverride {
+            can_split: Some(false),
+            max_chars_per_line: Some(50),
+            ..Default::default()
+        });
+        let style = config.resolved_style(&element);
+        assert!(!style.can_split);
+        assert_eq!(style.max_chars_per_line, 50);
+        assert_eq!(style.space_before, 1);
+    }
+
+    #[test]
+    fn test_presets() {
+        for name in PageConfig::PRESETS {
+            assert!(PageConfig::preset(name).is_some(), "{}", name);
+        }
+        assert!(PageConfig::preset("multi-cam-sitcom").is_some());
+        assert!(PageConfig::preset("teleplay").is_none());
+
+        let sitcom = PageConfig::multi_cam_sitcom();
+        assert_eq!(sitcom.style_for(ElementType::Dialogue).line_spacing, 2.0);
+        assert!(sitcom.style_for(ElementType::Action).force_uppercase);
+        assert!(sitcom.act_breaks.new_page_after);
+
+        let bbc = PageConfig::bbc_screenplay();
+        assert_eq!(bbc.paper_size, PaperSize::A4);
+        let width_chars = (bbc.printable_width_pt() / bbc.char_width_pt) as u8;
+        assert_eq!(bbc.style_for(ElementType::Action).max_chars_per_line, width_chars);
+    }
+
+    #[test]
+    fn test_a4_international_metrics() {
+        let config = PageConfig::a4_international();
+        assert_eq!(config.paper_size, PaperSize::A4);
+
+        // A4 is narrower and taller than Letter
+        assert_eq!(config.lines_per_page, 58);
+        assert_eq!(config.style_for(ElementType::Action).max_chars_per_line, 58);
+        assert_eq!(config.style_for(ElementType::Dialogue).max_chars_per_line, 34);
+
+        // Lines still fit the printable width
+        let action = config.style_for(ElementType::Action);
+        assert!(action.max_chars_per_line as f64 * config.char_width_pt <= config.printable_width_pt());
+    }
+
+    #[test]
+    fn test_derive_metrics() {
+        let mut config = PageConfig::feature_film();
+        assert!(config.metric_warnings().iter().all(|w| !w.message.starts_with("lines_per_page")));
+
+        config.lines_per_page = 50;
+        let warnings = config.derive_metrics();
+
+        // 660pt of printable height at 12pt, 252pt of dialogue width at 7.2pt
+        assert_eq!(config.lines_per_page, 55);
+        assert_eq!(config.style_for(ElementType::Dialogue).max_chars_per_line, 35);
+        assert_eq!(config.style_for(ElementType::Parenthetical).max_chars_per_line, 20);
+        assert!(warnings.iter().any(|w| w.message == "lines_per_page was 50 but the printable height fits 55"));
+        assert!(warnings.iter().all(|w| w.warning_type == WarningType::ConfigurationWarning));
+        assert!(config.derive_metrics().is_empty());
+
+        // Too many lines for the page is reported without changing anything
+        config.lines_per_page = 60;
+        assert_eq!(config.metric_warnings().len(), 1);
+        assert_eq!(config.lines_per_page, 60);
+    }
+
+    #[test]
+    fn test_validate() {
+        for name in PageConfig::PRESETS {
+            let issues = PageConfig::preset(name).unwrap().validate();
+            assert!(issues.iter().all(|i| i.severity == ConfigIssueSeverity::Warning), "{}: {:?}", name, issues);
+        }
+        assert!(PageConfig::feature_film().validate().is_empty());
+
+        let mut config = PageConfig::feature_film();
+        config.lines_per_page = 56;
+        let mut dialogue = config.style_for(ElementType::Dialogue).clone();
+        dialogue.margin_left = 4.0;
+        dialogue.margin_right = 3.0;
+        config.element_styles.insert(ElementType::Dialogue, dialogue);
+        config.orphan_control.dialogue_min_before_split = 30;
+        config.orphan_control.dialogue_min_after_split = 30;
+        config.element_styles.remove(&ElementType::Transition);
+
+        let fields: Vec<(String, ConfigIssueSeverity)> =
+            config.validate().into_iter().map(|issue| (issue.field, issue.severity)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("lines_per_page".to_string(), ConfigIssueSeverity::Error),
+                ("element_styles.Dialogue.margin_left".to_string(), ConfigIssueSeverity::Error),
+                ("orphan_control.dialogue_min_before_split".to_string(), ConfigIssueSeverity::Error),
+                ("element_styles.Transition".to_string(), ConfigIssueSeverity::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_type_style() {
+        let mut config = PageConfig::feature_film();
+        config.custom_styles.insert(
+            "caption".to_string(),
+            ElementStyle { margin_left: 1.5, max_chars_per_line: 30, ..ElementStyle::default_for(ElementType::Action) },
+        );
+
+        // Elements arriving without the field are unaffected
+        let json = r#"{"id": "1", "element_type": "action", "content": "SUPER: Ten years later."}"#;
+        let mut element: Element = serde_json::from_str(json).unwrap();
+        assert_eq!(config.resolved_style(&element).max_chars_per_line, 60);
+
+        element.custom_type = Some("caption".to_string());
+        assert_eq!(config.resolved_style(&element).max_chars_per_line, 30);
+
+        // Unknown names fall back to the element type's style
+        element.custom_type = Some("sfx_cue".to_string());
+        assert_eq!(config.resolved_style(&element).max_chars_per_line, 60);
+    }
+
+    #[test]
+    fn test_page_number_labels() {
+        let numbering = PageNumberingConfig::default();
+        assert_eq!(numbering.label(&PageIdentifier::Sequential(1)), None);
+        assert_eq!(numbering.label(&PageIdentifier::Sequential(42)).as_deref(), Some("42."));
+
+        let submission = PageNumberingConfig {
+            start_number: 5,
+            suppress_first_page: false,
+            format: "Page {n}".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(submission.label(&PageIdentifier::Sequential(5)).as_deref(), Some("Page 5"));
+        assert_eq!(
+            submission.label(&PageIdentifier::Inserted { base: 47, suffix: 'A' }).as_deref(),
+            Some("Page 47A")
+        );
+    }
+
     #[test]
     fn test_us_letter_dimensions() {
         let paper = PaperSize::UsLetter;
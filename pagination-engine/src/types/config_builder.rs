@@ -0,0 +1,271 @@
+use crate::layout::validate_config;
+use super::{
+    ElementStyle, ElementType, FontMetrics, HyphenationConfig, MarginConfig, PageConfig, PageNumberingConfig,
+    PaginationError, PaperSize, SplitPreference, TextDirection,
+};
+
+/// Fluent construction of a `PageConfig`, checked by `validate_config` at `build`
+///
+/// Starts from a preset (`feature_film` for `PageConfig::builder()`); element
+/// styles are adjusted in place through a closure:
+///
+/// ```ignore
+/// use verso_pagination_engine::PageConfig;
+///
+/// let config = PageConfig::builder()
+///     .lines_per_page(52)
+///     .dialogue(|s| s.max_chars(34))
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.lines_per_page, 52);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PageConfigBuilder {
+    config: PageConfig,
+    derive_metrics: bool,
+}
+
+impl PageConfigBuilder {
+    /// Start from an existing configuration, such as one of the presets
+    pub fn new(config: PageConfig) -> Self {
+        Self { config, derive_metrics: false }
+    }
+
+    pub fn paper_size(mut self, paper_size: PaperSize) -> Self {
+        self.config.paper_size = paper_size;
+        self
+    }
+
+    pub fn lines_per_page(mut self, lines: u8) -> Self {
+        self.config.lines_per_page = lines;
+        self
+    }
+
+    pub fn char_width_pt(mut self, width: f64) -> Self {
+        self.config.char_width_pt = width;
+        self
+    }
+
+    pub fn line_height_pt(mut self, height: f64) -> Self {
+        self.config.line_height_pt = height;
+        self
+    }
+
+    /// Page margins in inches
+    pub fn margins(mut self, top: f64, bottom: f64, left: f64, right: f64) -> Self {
+        self.config.margins = MarginConfig { top, bottom, left, right };
+        self
+    }
+
+    pub fn text_direction(mut self, direction: TextDirection) -> Self {
+        self.config.text_direction = direction;
+        self
+    }
+
+    pub fn east_asian_width(mut self, enabled: bool) -> Self {
+        self.config.east_asian_width = enabled;
+        self
+    }
+
+    pub fn font_metrics(mut self, metrics: FontMetrics) -> Self {
+        self.config.font_metrics = Some(metrics);
+        self
+    }
+
+    pub fn hyphenation(mut self, hyphenation: HyphenationConfig) -> Self {
+        self.config.hyphenation = hyphenation;
+        self
+    }
+
+    pub fn page_numbering(mut self, numbering: PageNumberingConfig) -> Self {
+        self.config.page_numbering = numbering;
+        self
+    }
+
+    pub fn show_notes(mut self, show: bool) -> Self {
+        self.config.show_notes = show;
+        self
+    }
+
+    /// Replace hand-set line and character budgets with what the geometry
+    /// fits (`PageConfig::derive_metrics`) before validating
+    pub fn derived_metrics(mut self) -> Self {
+        self.derive_metrics = true;
+        self
+    }
+
+    /// Adjust an element type's style, starting from its current one
+    pub fn style(mut self, element_type: ElementType, f: impl FnOnce(ElementStyleBuilder) -> ElementStyleBuilder) -> Self {
+        let style = self.config.style_for(element_type).clone();
+        self.config.element_styles.insert(element_type, f(ElementStyleBuilder(style)).0);
+        self
+    }
+
+    /// Add or adjust the style of a custom element type
+    ///
+    /// A new name starts from the default style.
+    pub fn custom_style(mut self, name: &str, f: impl FnOnce(ElementStyleBuilder) -> ElementStyleBuilder) -> Self {
+        let style = self.config.custom_styles.remove(name).unwrap_or_default();
+        self.config.custom_styles.insert(name.to_string(), f(ElementStyleBuilder(style)).0);
+        self
+    }
+
+    pub fn scene_heading(self, f: impl FnOnce(ElementStyleBuilder) -> ElementStyleBuilder) -> Self {
+        self.style(ElementType::SceneHeading, f)
+    }
+
+    pub fn action(self, f: impl FnOnce(ElementStyleBuilder) -> ElementStyleBuilder) -> Self {
+        self.style(ElementType::Action, f)
+    }
+
+    pub fn character(self, f: impl FnOnce(ElementStyleBuilder) -> ElementStyleBuilder) -> Self {
+        self.style(ElementType::Character, f)
+    }
+
+    pub fn dialogue(self, f: impl FnOnce(ElementStyleBuilder) -> ElementStyleBuilder) -> Self {
+        self.style(ElementType::Dialogue, f)
+    }
+
+    pub fn parenthetical(self, f: impl FnOnce(ElementStyleBuilder) -> ElementStyleBuilder) -> Self {
+        self.style(ElementType::Parenthetical, f)
+    }
+
+    pub fn transition(self, f: impl FnOnce(ElementStyleBuilder) -> ElementStyleBuilder) -> Self {
+        self.style(ElementType::Transition, f)
+    }
+
+    /// Finish the configuration, rejecting values pagination can't work with
+    pub fn build(mut self) -> Result<PageConfig, PaginationError> {
+        if self.derive_metrics {
+            self.config.derive_metrics();
+        }
+        validate_config(&self.config)?;
+        Ok(self.config)
+    }
+}
+
+/// Chained edits to one `ElementStyle`, for `PageConfigBuilder::style`
+#[derive(Debug, Clone)]
+pub struct ElementStyleBuilder(ElementStyle);
+
+impl ElementStyleBuilder {
+    /// Left and right margins from the printable area, in inches
+    pub fn margins(mut self, left: f64, right: f64) -> Self {
+        self.0.margin_left = left;
+        self.0.margin_right = right;
+        self
+    }
+
+    pub fn max_chars(mut self, chars: u8) -> Self {
+        self.0.max_chars_per_line = chars;
+        self
+    }
+
+    /// Blank lines before and after the element
+    pub fn spacing(mut self, before: u8, after: u8) -> Self {
+        self.0.space_before = before;
+        self.0.space_after = after;
+        self
+    }
+
+    pub fn line_spacing(mut self, spacing: f64) -> Self {
+        self.0.line_spacing = spacing;
+        self
+    }
+
+    /// Allow splitting across pages, keeping at least `before` lines at the
+    /// bottom of a page and `after` at the top of the next
+    pub fn split(mut self, before: u8, after: u8) -> Self {
+        self.0.can_split = true;
+        self.0.min_lines_before_split = before;
+        self.0.min_lines_after_split = after;
+        self
+    }
+
+    pub fn no_split(mut self) -> Self {
+        self.0.can_split = false;
+        self
+    }
+
+    pub fn split_preference(mut self, preference: SplitPreference) -> Self {
+        self.0.split_preference = preference;
+        self
+    }
+
+    /// Keep with the next element and at least `lines` of it
+    pub fn keep_with_next(mut self, lines: u8) -> Self {
+        self.0.keep_with_next = true;
+        self.0.keep_with_next_lines = lines;
+        self
+    }
+
+    pub fn keep_with_previous(mut self) -> Self {
+        self.0.keep_with_previous = true;
+        self
+    }
+
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.0.force_uppercase = uppercase;
+        self
+    }
+
+    pub fn text_direction(mut self, direction: TextDirection) -> Self {
+        self.0.text_direction = Some(direction);
+        self
+    }
+
+    /// Take no space on the page (a zero-line anchor)
+    pub fn hidden(mut self) -> Self {
+        self.0.visible_in_pagination = false;
+        self
+    }
+
+    /// The style as edited so far
+    pub fn style(&self) -> &ElementStyle {
+        &self.0
+    }
+}
+
+impl PageConfig {
+    /// Build a configuration starting from the feature film preset
+    pub fn builder() -> PageConfigBuilder {
+        PageConfigBuilder::new(Self::feature_film())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_styles_and_validation() {
+        let config = PageConfig::builder()
+            .lines_per_page(52)
+            .dialogue(|s| s.max_chars(34).margins(2.5, 1.5))
+            .custom_style("insert", |s| s.max_chars(50).keep_with_next(1))
+            .build()
+            .unwrap();
+
+        assert_eq!(config.lines_per_page, 52);
+        let dialogue = config.style_for(ElementType::Dialogue);
+        assert_eq!(dialogue.max_chars_per_line, 34);
+        assert_eq!(dialogue.margin_left, 2.5);
+        // Untouched fields keep the preset's values
+        assert_eq!(dialogue.space_before, ElementStyle::default_for(ElementType::Dialogue).space_before);
+        assert!(config.custom_styles["insert"].keep_with_next);
+
+        let error = PageConfig::builder().action(|s| s.max_chars(0)).build().unwrap_err();
+        assert_eq!(error.code(), "invalid_config");
+        assert!(PageConfig::builder().lines_per_page(0).build().is_err());
+        // More lines than Letter's printable height fits
+        assert!(PageConfig::builder().lines_per_page(58).build().is_err());
+    }
+
+    #[test]
+    fn test_builder_derived_metrics() {
+        let config = PageConfig::builder().paper_size(PaperSize::A4).derived_metrics().build().unwrap();
+
+        assert_eq!(config.lines_per_page, config.derived_lines_per_page());
+        assert_ne!(config.lines_per_page, PageConfig::feature_film().lines_per_page);
+    }
+}
@@ -1,7 +1,12 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
+use super::ElementStyleOverride;
+use crate::utils::StableHasher;
 
 /// Unique identifier for each element, used for position tracking
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ElementId(pub String);
 
 impl ElementId {
@@ -11,10 +16,12 @@ impl ElementId {
 }
 
 /// All possible screenplay element types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ElementType {
     SceneHeading,
+    #[default]
     Action,
     Character,
     Dialogue,
@@ -26,16 +33,42 @@ pub enum ElementType {
     ActBreak,
     PageBreak,
     BlankLine,
+    Note,
+    Lyrics,
+    Centered,
+    Section,
+    Synopsis,
+    /// Video column cell of an AV script row
+    AvVideo,
+    /// Audio column cell; pairs with the immediately preceding video cell
+    AvAudio,
+    /// Placeholder for a scene deleted from a locked script; content is its scene number
+    OmittedScene,
+    /// Locked page(s) emptied by deletions; content is the page number or range ("12", "12-14")
+    OmittedPage,
 }
 
-impl Default for ElementType {
-    fn default() -> Self {
-        Self::Action
-    }
+/// Bold, italic or underline styling over a range of characters
+///
+/// `start` and `end` (exclusive) are character offsets into the element's
+/// content, or into a wrapped line for the runs `LineCalculator` maps onto
+/// lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StyleRun {
+    pub start: usize,
+    pub end: usize,
+    #[serde(default)]
+    pub bold: bool,
+    #[serde(default)]
+    pub italic: bool,
+    #[serde(default)]
+    pub underline: bool,
 }
 
 /// A single screenplay element with its content and metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Element {
     pub id: ElementId,
     pub element_type: ElementType,
@@ -52,6 +85,30 @@ pub struct Element {
     /// Whether this element forces a page break after it
     #[serde(default)]
     pub force_page_break_after: bool,
+
+    /// One-off style adjustments merged over the element type's default
+    #[serde(default)]
+    pub style_override: Option<ElementStyleOverride>,
+
+    /// Locked scene number of a scene heading (e.g., "12A"); headings without
+    /// one are numbered around the locked ones (see `number_scenes`)
+    #[serde(default)]
+    pub scene_number: Option<String>,
+
+    /// Character ranges of `content` (start, end exclusive) never broken
+    /// across lines, like a name or "U.S.S. Enterprise"
+    #[serde(default)]
+    pub no_break_ranges: Vec<(usize, usize)>,
+
+    /// Inline styling of `content`, carried onto wrapped lines and split parts
+    #[serde(default)]
+    pub style_runs: Vec<StyleRun>,
+
+    /// Integrator-defined type ("caption", "sfx_cue") styled by
+    /// `PageConfig::custom_styles`; pagination otherwise treats the element
+    /// as its `element_type`, which also decides whether it prints
+    #[serde(default)]
+    pub custom_type: Option<String>,
 }
 
 impl Element {
@@ -63,6 +120,11 @@ impl Element {
             character_name: None,
             dual_dialogue_position: None,
             force_page_break_after: false,
+            style_override: None,
+            scene_number: None,
+            no_break_ranges: Vec::new(),
+            style_runs: Vec::new(),
+            custom_type: None,
         }
     }
 
@@ -75,15 +137,138 @@ impl Element {
         self.force_page_break_after = true;
         self
     }
+
+    pub fn with_style_override(mut self, style_override: ElementStyleOverride) -> Self {
+        self.style_override = Some(style_override);
+        self
+    }
+
+    pub fn with_scene_number(mut self, number: impl Into<String>) -> Self {
+        self.scene_number = Some(number.into());
+        self
+    }
+
+    pub fn with_no_break_range(mut self, start: usize, end: usize) -> Self {
+        self.no_break_ranges.push((start, end));
+        self
+    }
+
+    pub fn with_style_run(mut self, run: StyleRun) -> Self {
+        self.style_runs.push(run);
+        self
+    }
+
+    pub fn with_custom_type(mut self, name: impl Into<String>) -> Self {
+        self.custom_type = Some(name.into());
+        self
+    }
+
+    pub fn with_dual_dialogue_position(mut self, position: DualDialoguePosition) -> Self {
+        self.dual_dialogue_position = Some(position);
+        self
+    }
+
+    /// Stable hash of everything about the element, as 16 hex digits
+    pub fn content_hash(&self) -> String {
+        let mut hasher = StableHasher::new();
+        hasher.write(&serde_json::to_vec(self).unwrap_or_default());
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Dual dialogue column this element prints in, from its type or its position
+    pub fn dual_column(&self) -> Option<DualDialoguePosition> {
+        match self.element_type {
+            ElementType::DualDialogueLeft => Some(DualDialoguePosition::Left),
+            ElementType::DualDialogueRight => Some(DualDialoguePosition::Right),
+            _ => self.dual_dialogue_position,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum DualDialoguePosition {
     Left,
     Right,
 }
 
+/// One change to a loaded document, for sending edits instead of the whole
+/// element list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ElementEdit {
+    /// Insert before position `index` (clamped to the end)
+    Insert { index: usize, element: Element },
+
+    /// Replace the element with the same ID
+    Replace { element: Element },
+
+    /// Remove the element with this ID
+    Remove { id: ElementId },
+}
+
+/// How one element list became another, from `diff_elements`
+///
+/// Elements are matched by ID. A surviving element is `modified` when its
+/// content hash differs and `moved` when it no longer keeps its order among
+/// the other survivors; it can be both.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ElementDiff {
+    /// In new order
+    pub added: Vec<ElementId>,
+
+    /// In old order
+    pub removed: Vec<ElementId>,
+
+    /// In new order
+    pub modified: Vec<ElementId>,
+
+    /// In new order
+    pub moved: Vec<ElementId>,
+}
+
+impl ElementDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty() && self.moved.is_empty()
+    }
+
+    /// Elements whose text now reads differently where it stands: added,
+    /// modified or moved, for `mark_revisions`
+    pub fn changed(&self) -> Vec<ElementId> {
+        let mut changed: Vec<ElementId> = self.added.iter().chain(&self.modified).cloned().collect();
+        changed.extend(self.moved.iter().filter(|id| !self.modified.contains(id)).cloned());
+        changed
+    }
+
+    /// Edits that turn the old list into `new`, for `Paginator::apply_edits`
+    ///
+    /// Removals come first, then inserts in new order (moved elements are
+    /// removed and inserted again), then replacements of the modified
+    /// elements that stayed put.
+    pub fn edits(&self, new: &[Element]) -> Vec<ElementEdit> {
+        let ids = |list: &'_ [ElementId]| -> HashSet<String> { list.iter().map(|id| id.0.clone()).collect() };
+        let (added, modified, moved) = (ids(&self.added), ids(&self.modified), ids(&self.moved));
+
+        let mut edits: Vec<ElementEdit> =
+            self.removed.iter().chain(&self.moved).map(|id| ElementEdit::Remove { id: id.clone() }).collect();
+        edits.extend(
+            new.iter()
+                .enumerate()
+                .filter(|(_, e)| added.contains(&e.id.0) || moved.contains(&e.id.0))
+                .map(|(index, element)| ElementEdit::Insert { index, element: element.clone() }),
+        );
+        edits.extend(
+            new.iter()
+                .filter(|e| modified.contains(&e.id.0) && !moved.contains(&e.id.0))
+                .map(|element| ElementEdit::Replace { element: element.clone() }),
+        );
+        edits
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +287,14 @@ mod tests {
             .with_character_name("JOHN");
         assert_eq!(element.character_name, Some("JOHN".to_string()));
     }
+
+    #[test]
+    fn test_dual_column() {
+        let typed = Element::new("1", ElementType::DualDialogueRight, "Hi.");
+        assert_eq!(typed.dual_column(), Some(DualDialoguePosition::Right));
+
+        let cue = Element::new("2", ElementType::Character, "JOHN").with_dual_dialogue_position(DualDialoguePosition::Left);
+        assert_eq!(cue.dual_column(), Some(DualDialoguePosition::Left));
+        assert_eq!(Element::new("3", ElementType::Dialogue, "Hi.").dual_column(), None);
+    }
 }
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use super::ElementId;
+
+/// Why `try_paginate` rejected its input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum PaginationError {
+    /// An element has an empty ID
+    EmptyElementId { index: usize },
+
+    /// Two elements share an ID, so positions would overwrite each other
+    DuplicateElementId { id: ElementId, first_index: usize, index: usize },
+
+    /// Dialogue or parenthetical with no character name and no preceding cue
+    DialogueWithoutCharacter { id: ElementId, index: usize },
+
+    /// A configuration value that pagination cannot work with
+    InvalidConfig { field: String, reason: String },
+
+    /// An element wraps to more lines than page line counters can hold
+    LineCountOverflow { id: ElementId, lines: u32 },
+}
+
+impl PaginationError {
+    /// Stable machine-readable code, matching the serialized `code` tag
+    pub fn code(&self) -> &'static str {
+        match self {
+            PaginationError::EmptyElementId { .. } => "empty_element_id",
+            PaginationError::DuplicateElementId { .. } => "duplicate_element_id",
+            PaginationError::DialogueWithoutCharacter { .. } => "dialogue_without_character",
+            PaginationError::InvalidConfig { .. } => "invalid_config",
+            PaginationError::LineCountOverflow { .. } => "line_count_overflow",
+        }
+    }
+}
+
+impl fmt::Display for PaginationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaginationError::EmptyElementId { index } => {
+                write!(f, "Element at index {} has an empty id", index)
+            }
+            PaginationError::DuplicateElementId { id, first_index, index } => write!(
+                f,
+                "Element id '{}' at index {} duplicates the element at index {}",
+                id.0, index, first_index
+            ),
+            PaginationError::DialogueWithoutCharacter { id, index } => write!(
+                f,
+                "Element '{}' at index {} is dialogue without a character",
+                id.0, index
+            ),
+            PaginationError::InvalidConfig { field, reason } => {
+                write!(f, "Invalid config field '{}': {}", field, reason)
+            }
+            PaginationError::LineCountOverflow { id, lines } => write!(
+                f,
+                "Element '{}' needs {} lines, more than the {} a page can track",
+                id.0,
+                lines,
+                u8::MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PaginationError {}
+
+/// How serious a `ConfigIssue` is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigIssueSeverity {
+    /// Pagination rejects the config
+    Error,
+
+    /// Pagination runs, but the pages won't print as configured
+    Warning,
+}
+
+/// A setting `PageConfig::validate` found pagination can't honor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConfigIssue {
+    /// Config field at fault ("element_styles.Dialogue.margin_left")
+    pub field: String,
+
+    pub severity: ConfigIssueSeverity,
+
+    pub message: String,
+}
+
+/// Why a call into the JavaScript bindings failed
+///
+/// Thrown to JS as an `Error` named `EngineError` with `code` and `detail`
+/// properties, so callers can branch on the code rather than the message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EngineError {
+    pub code: EngineErrorCode,
+    pub message: String,
+    /// Structured cause, such as the serialized `PaginationError` for
+    /// `InvalidDocument` and `InvalidConfig` (None when there is only the message)
+    #[serde(default)]
+    pub detail: Option<serde_json::Value>,
+}
+
+/// Stable machine-readable `EngineError` codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EngineErrorCode {
+    /// Elements (or an element or edit) couldn't be parsed
+    InvalidElementJson,
+    /// The config couldn't be parsed or can't be paginated with
+    InvalidConfig,
+    /// Another argument (options, locks, a result, a color, ...) couldn't be parsed
+    InvalidInput,
+    /// MessagePack input to the binary entry point couldn't be decoded
+    InvalidMessagePack,
+    /// The elements parsed but can't be paginated faithfully
+    InvalidDocument,
+    /// No format preset has the given name
+    UnknownPreset,
+    /// The output couldn't be serialized
+    SerializationFailed,
+    /// A progress callback asked to stop
+    Cancelled,
+    /// A JavaScript callback threw
+    CallbackFailed,
+}
+
+impl EngineError {
+    pub fn new(code: EngineErrorCode, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), detail: None }
+    }
+
+    /// Stable code as serialized (`INVALID_CONFIG`, ...)
+    pub fn code_str(&self) -> &'static str {
+        match self.code {
+            EngineErrorCode::InvalidElementJson => "INVALID_ELEMENT_JSON",
+            EngineErrorCode::InvalidConfig => "INVALID_CONFIG",
+            EngineErrorCode::InvalidInput => "INVALID_INPUT",
+            EngineErrorCode::InvalidMessagePack => "INVALID_MESSAGE_PACK",
+            EngineErrorCode::InvalidDocument => "INVALID_DOCUMENT",
+            EngineErrorCode::UnknownPreset => "UNKNOWN_PRESET",
+            EngineErrorCode::SerializationFailed => "SERIALIZATION_FAILED",
+            EngineErrorCode::Cancelled => "CANCELLED",
+            EngineErrorCode::CallbackFailed => "CALLBACK_FAILED",
+        }
+    }
+}
+
+impl From<PaginationError> for EngineError {
+    fn from(error: PaginationError) -> Self {
+        let code = match error {
+            PaginationError::InvalidConfig { .. } => EngineErrorCode::InvalidConfig,
+            _ => EngineErrorCode::InvalidDocument,
+        };
+        Self {
+            code,
+            message: error.to_string(),
+            detail: serde_json::to_value(&error).ok(),
+        }
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code_str(), self.message)
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_with_code_tag() {
+        let error = PaginationError::DuplicateElementId {
+            id: ElementId::new("7"),
+            first_index: 1,
+            index: 4,
+        };
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], error.code());
+        assert_eq!(json["first_index"], 1);
+        assert!(error.to_string().contains("'7'"));
+    }
+
+    #[test]
+    fn test_engine_error_from_pagination_error() {
+        let error = EngineError::from(PaginationError::EmptyElementId { index: 3 });
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], error.code_str());
+        assert_eq!(json["code"], "INVALID_DOCUMENT");
+        assert_eq!(json["detail"]["code"], "empty_element_id");
+        assert_eq!(json["message"], "Element at index 3 has an empty id");
+    }
+
+    #[test]
+    fn test_code_str_matches_serialized_code() {
+        let error = EngineError::new(EngineErrorCode::InvalidMessagePack, "Failed to decode elements");
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], error.code_str());
+        assert_eq!(json["code"], "INVALID_MESSAGE_PACK");
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use super::{ElementId, PageIdentifier, PaginationResult};
+
+/// Page breaks of a locked draft, kept when later drafts are paginated
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LockedPageSet {
+    pub pages: Vec<LockedPage>,
+}
+
+/// One page of a locked draft
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LockedPage {
+    pub identifier: PageIdentifier,
+
+    /// Elements that started on this page (continuations from the page
+    /// before are not listed)
+    pub elements: Vec<ElementId>,
+}
+
+impl LockedPageSet {
+    /// Lock the pages of a paginated draft
+    ///
+    /// Omitted pages are left out; their numbers stay retired as long as the
+    /// draft's `OmittedPage` markers are kept.
+    pub fn from_result(result: &PaginationResult) -> Self {
+        let pages = result
+            .pages
+            .iter()
+            .filter(|page| !matches!(page.identifier, PageIdentifier::Omitted(_)))
+            .map(|page| LockedPage {
+                identifier: page.identifier.clone(),
+                elements: page
+                    .elements
+                    .iter()
+                    .filter(|e| !e.is_continuation)
+                    .map(|e| e.element_id.clone())
+                    .collect(),
+            })
+            .collect();
+
+        Self { pages }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Page, PageElement};
+
+    #[test]
+    fn test_from_result_skips_continuations_and_omitted() {
+        let placed = |id: &str, is_continuation: bool| PageElement {
+            element_id: ElementId::new(id),
+            start_line: 1,
+            line_count: 1,
+            is_continuation,
+            line_range: None,
+            continuation_prefix: None,
+            line_positions: None,
+            frame: None,
+            text: None,
+            style_runs: None,
+            column: None,
+            revision_marks: None,
+            scene_number: None,
+        };
+
+        let mut result = PaginationResult::new();
+        let mut first = Page::new(PageIdentifier::Sequential(1));
+        first.elements = vec![placed("a", false), placed("b", false)];
+        let mut second = Page::new(PageIdentifier::Sequential(2));
+        second.elements = vec![placed("b", true), placed("c", false)];
+        result.pages = vec![first, second, Page::new(PageIdentifier::Omitted(3))];
+
+        let locks = LockedPageSet::from_result(&result);
+
+        assert_eq!(locks.pages.len(), 2);
+        assert_eq!(locks.pages[1].elements, vec![ElementId::new("c")]);
+    }
+}
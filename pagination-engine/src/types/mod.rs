@@ -1,9 +1,21 @@
 mod element;
 mod config;
+mod config_builder;
+mod error;
+mod lock;
 mod page;
+mod partial_config;
 mod result;
+mod revision;
+mod title_page;
 
 pub use element::*;
 pub use config::*;
+pub use config_builder::*;
+pub use error::*;
+pub use lock::*;
 pub use page::*;
+pub use partial_config::*;
 pub use result::*;
+pub use revision::*;
+pub use title_page::*;
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
-use super::ElementId;
+use std::collections::HashMap;
+use super::{DualDialoguePosition, Element, ElementId, RevisionColor, StyleRun};
+use crate::utils::StableHasher;
 
 /// Page identifier supporting A-pages for production scripts
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", content = "value")]
 pub enum PageIdentifier {
     /// Normal sequential page (1, 2, 3...)
@@ -61,6 +64,7 @@ impl Default for PageIdentifier {
 
 /// Reason for a page break
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum PageBreakReason {
     /// Page filled naturally
@@ -77,23 +81,42 @@ pub enum PageBreakReason {
 
     /// Dialogue split with continuation
     DialogueContinuation,
+
+    /// Kept where a page of the locked draft started
+    Locked,
+
+    /// Content pushed forward and the free lines spread over the page's gaps
+    /// (see `PageConfig::page_balance`)
+    StretchedSpacing,
+
+    /// Content pushed forward and the page deliberately left short because
+    /// the free lines were too many to stretch away
+    ShortPage,
 }
 
 /// A page break point in the document
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PageBreak {
     /// Element ID after which this break occurs
     pub after_element: ElementId,
 
-    /// If element was split, line number within element (0-indexed)
+    /// If element was split, line number within element (0-indexed) that
+    /// opens the next page
     pub split_at_line: Option<u32>,
 
     /// Reason for the break
+    #[serde(default = "forced_reason")]
     pub reason: PageBreakReason,
 }
 
+fn forced_reason() -> PageBreakReason {
+    PageBreakReason::Forced
+}
+
 /// Range of lines within a split element
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LineRange {
     /// Starting line within the element (0-indexed)
     pub start: u32,
@@ -101,8 +124,42 @@ pub struct LineRange {
     pub end: u32,
 }
 
+/// Absolute position of one printed line, in points from the page's top-left corner
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LinePosition {
+    /// Left edge of the text
+    pub x_pt: f64,
+
+    /// Top of the line box
+    pub y_pt: f64,
+
+    /// Width of the printed text
+    #[serde(default)]
+    pub width_pt: f64,
+
+    /// Height of the line box (`PageConfig::line_height_pt`)
+    #[serde(default)]
+    pub height_pt: f64,
+}
+
+/// Area an element's lines take on one page, in points from the page's top-left corner
+///
+/// Spans the element's text column between its style margins (narrowed for a
+/// dual dialogue column) and runs from the top of its first line to the
+/// bottom of its last.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ElementFrame {
+    pub x_pt: f64,
+    pub y_pt: f64,
+    pub width_pt: f64,
+    pub height_pt: f64,
+}
+
 /// An element's placement on a page
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PageElement {
     pub element_id: ElementId,
 
@@ -120,10 +177,44 @@ pub struct PageElement {
 
     /// Continuation prefix for character (e.g., "JOHN (CONT'D)")
     pub continuation_prefix: Option<String>,
+
+    /// Position of each line on this page (only with `RenderOptions::include_geometry`)
+    #[serde(default)]
+    pub line_positions: Option<Vec<LinePosition>>,
+
+    /// Area the element's lines take on this page (only with
+    /// `RenderOptions::include_geometry`)
+    #[serde(default)]
+    pub frame: Option<ElementFrame>,
+
+    /// Printed text on this page, including any CONT'D line before and MORE
+    /// marker after the content (only with `RenderOptions::include_text`)
+    #[serde(default)]
+    pub text: Option<Vec<String>>,
+
+    /// Style runs on each content line of `text`, markers excluded (only with
+    /// `RenderOptions::include_text`, and None when the element has no runs)
+    #[serde(default)]
+    pub style_runs: Option<Vec<Vec<StyleRun>>>,
+
+    /// Column of a dual dialogue block this element is printed in; both
+    /// columns of a block start on the same line
+    #[serde(default)]
+    pub column: Option<DualDialoguePosition>,
+
+    /// Revision asterisk for each line on this page (None when no line of
+    /// the element is revised; see `mark_revisions` and `mark_changed_lines`)
+    #[serde(default)]
+    pub revision_marks: Option<Vec<bool>>,
+
+    /// Scene number printed beside a scene heading (left empty by `PaginationBuilder`)
+    #[serde(default)]
+    pub scene_number: Option<String>,
 }
 
 /// A single page in the paginated output
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Page {
     pub identifier: PageIdentifier,
 
@@ -135,6 +226,34 @@ pub struct Page {
 
     /// Lines used on this page
     pub lines_used: u8,
+
+    /// Printed page number, formatted per `PageConfig::page_numbering`
+    /// (None for an unnumbered page)
+    #[serde(default)]
+    pub page_number: Option<String>,
+
+    /// Header above the content, e.g. "CONTINUED:" (see `PageConfig::continued_header`)
+    #[serde(default)]
+    pub header: Option<String>,
+
+    /// Draft name and date printed at the top (see `PageConfig::header_footer`)
+    #[serde(default)]
+    pub running_header: Option<String>,
+
+    /// Why the page ended (None for the last page)
+    #[serde(default)]
+    pub break_reason: Option<PageBreakReason>,
+
+    /// Paper color of the latest revision touching this page (None for the
+    /// original draft)
+    #[serde(default)]
+    pub revision_color: Option<RevisionColor>,
+
+    /// Stable hash of everything printed on the page, as 16 hex digits; a
+    /// page whose hash is unchanged between runs renders the same (left empty
+    /// by `PaginationBuilder`)
+    #[serde(default)]
+    pub content_hash: String,
 }
 
 impl Page {
@@ -144,7 +263,30 @@ impl Page {
             elements: Vec::new(),
             bottom_continuation: None,
             lines_used: 0,
+            page_number: None,
+            header: None,
+            running_header: None,
+            break_reason: None,
+            revision_color: None,
+            content_hash: String::new(),
+        }
+    }
+
+    /// Hash of the page and the content of the elements placed on it, for
+    /// `content_hash`
+    ///
+    /// The stored `content_hash` is part of the input, so recomputing after
+    /// changing the page without its elements at hand (as `mark_revisions`
+    /// does) still covers the contents hashed before.
+    pub fn compute_content_hash(&self, elements: &HashMap<&str, &Element>) -> String {
+        let mut hasher = StableHasher::new();
+        hasher.write(&serde_json::to_vec(self).unwrap_or_default());
+        for page_element in &self.elements {
+            if let Some(element) = elements.get(page_element.element_id.0.as_str()) {
+                hasher.write(&serde_json::to_vec(element).unwrap_or_default());
+            }
         }
+        format!("{:016x}", hasher.finish())
     }
 
     pub fn lines_remaining(&self, lines_per_page: u8) -> u8 {
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use super::{ElementStyle, ElementType, PageConfig, PaginationError};
+
+/// Part of a `PageConfig`, applied over a preset with `PageConfig::apply_overrides`
+///
+/// Shaped like the serialized config with any field left out:
+/// `{"lines_per_page": 57, "continuation_style": {"more_marker": "(MORE...)"}}`.
+/// Nested objects merge field by field, so an `element_styles` entry only
+/// needs the style fields it changes; arrays and other values replace the
+/// config's.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct PartialPageConfig(pub Map<String, Value>);
+
+impl PageConfig {
+    /// Merge overrides into this configuration
+    ///
+    /// An override naming an element type or custom style the config doesn't
+    /// have starts from that type's default style. Fails without changing the
+    /// config if the merged result isn't a valid `PageConfig`.
+    pub fn apply_overrides(&mut self, overrides: &PartialPageConfig) -> Result<(), PaginationError> {
+        let invalid = |e: serde_json::Error| PaginationError::InvalidConfig {
+            field: "overrides".to_string(),
+            reason: e.to_string(),
+        };
+
+        let mut base = serde_json::to_value(&*self).map_err(invalid)?;
+        seed_styles(&mut base, overrides).map_err(invalid)?;
+        for (key, value) in &overrides.0 {
+            merge(&mut base[key.as_str()], value);
+        }
+
+        *self = serde_json::from_value(base).map_err(invalid)?;
+        Ok(())
+    }
+
+    /// This configuration with `apply_overrides` applied
+    pub fn with_overrides(mut self, overrides: &PartialPageConfig) -> Result<Self, PaginationError> {
+        self.apply_overrides(overrides)?;
+        Ok(self)
+    }
+}
+
+/// Give overridden styles missing from the base a full default to merge into,
+/// since a partial style alone wouldn't deserialize
+fn seed_styles(base: &mut Value, overrides: &PartialPageConfig) -> Result<(), serde_json::Error> {
+    if let Some(Value::Object(styles)) = overrides.0.get("element_styles") {
+        for key in styles.keys() {
+            if base["element_styles"].get(key).is_none() {
+                let element_type: ElementType = serde_json::from_value(Value::String(key.clone()))?;
+                base["element_styles"][key.as_str()] = serde_json::to_value(ElementStyle::default_for(element_type))?;
+            }
+        }
+    }
+    if let Some(Value::Object(styles)) = overrides.0.get("custom_styles") {
+        for key in styles.keys() {
+            if base["custom_styles"].get(key).is_none() {
+                base["custom_styles"][key.as_str()] = serde_json::to_value(ElementStyle::default())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Deep merge: objects field by field, anything else replaced
+fn merge(base: &mut Value, value: &Value) {
+    match (base, value) {
+        (Value::Object(base), Value::Object(fields)) => {
+            for (key, value) in fields {
+                merge(base.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+        (base, value) => *base = value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(json: &str) -> PartialPageConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_apply_overrides_merges_nested_fields() {
+        let mut config = PageConfig::feature_film();
+
+        config
+            .apply_overrides(&overrides(
+                r#"{
+                    "lines_per_page": 57,
+                    "continuation_style": {"more_marker": "(MORE...)"},
+                    "element_styles": {"dialogue": {"max_chars_per_line": 33}},
+                    "custom_styles": {"insert": {"space_before": 2}}
+                }"#,
+            ))
+            .unwrap();
+
+        assert_eq!(config.lines_per_page, 57);
+        assert_eq!(config.continuation_style.more_marker, "(MORE...)");
+        assert_eq!(config.continuation_style.contd_marker, "(CONT'D)");
+        let dialogue = config.style_for(ElementType::Dialogue);
+        assert_eq!(dialogue.max_chars_per_line, 33);
+        assert_eq!(dialogue.margin_left, ElementStyle::default_for(ElementType::Dialogue).margin_left);
+        assert_eq!(config.custom_styles["insert"].space_before, 2);
+        assert_eq!(config.custom_styles["insert"].max_chars_per_line, ElementStyle::default().max_chars_per_line);
+    }
+
+    #[test]
+    fn test_apply_overrides_rejects_bad_values() {
+        let mut config = PageConfig::feature_film();
+
+        let error = config.apply_overrides(&overrides(r#"{"lines_per_page": "many"}"#)).unwrap_err();
+
+        assert_eq!(error.code(), "invalid_config");
+        assert_eq!(config.lines_per_page, 55);
+    }
+}
@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use super::{ElementId, Page, PageIdentifier};
+use std::ops::RangeBounds;
+use super::{ElementId, LineRange, Page, PageConfig, PageIdentifier};
+use crate::utils::StableHasher;
 
 /// Position of an element in the paginated document
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ElementPosition {
     /// Page(s) this element appears on
     pub pages: Vec<PageIdentifier>,
@@ -16,10 +19,16 @@ pub struct ElementPosition {
 
     /// Whether element was split across pages
     pub is_split: bool,
+
+    /// Character offset in the element's content where each wrapped line
+    /// starts (left empty by `PaginationBuilder`)
+    #[serde(default)]
+    pub line_offsets: Vec<u32>,
 }
 
 /// Warning generated during pagination
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PaginationWarning {
     pub element_id: Option<ElementId>,
     pub warning_type: WarningType,
@@ -27,6 +36,7 @@ pub struct PaginationWarning {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum WarningType {
     /// Element is longer than a full page
@@ -40,10 +50,132 @@ pub enum WarningType {
 
     /// Dual dialogue layout issue
     DualDialogueOverflow,
+
+    /// A break pinned with `PageConfig::forced_breaks` could not fall where
+    /// it was pinned
+    ForcedBreakMoved,
+}
+
+/// Why a page ended where it did, recorded with `RenderOptions::explain`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BreakExplanation {
+    /// The page that ended
+    pub page: PageIdentifier,
+
+    /// Element being placed when the page ended, if any
+    pub element_id: Option<ElementId>,
+
+    pub rule: BreakRule,
+
+    /// Lines left blank at the bottom of the page
+    pub free_lines: u8,
+}
+
+/// The pagination rule that decided a page break
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BreakRule {
+    /// A page break element, `force_page_break_after` or an omitted page
+    Forced,
+
+    /// The next act starts on a new page
+    ActBreak,
+
+    /// A page of the locked draft starts here
+    Locked,
+
+    /// The page filled up
+    PageFull,
+
+    /// The element split across the break
+    Split,
+
+    /// The element split with fewer lines on one side than the minimums allow
+    SplitBelowMinimum,
+
+    /// The element didn't fit and its type or style never splits
+    CannotSplit,
+
+    /// Splitting would leave fewer than the minimum lines before the break
+    SplitMinBeforeUnmet,
+
+    /// Splitting would leave fewer than the minimum lines after the break
+    SplitMinAfterUnmet,
+
+    /// A keep-with-next element moved to stay with the lines that follow it
+    KeepWithNext,
+
+    /// A scene heading moved to stay with the opening lines of its scene
+    SceneOpening,
+
+    /// A cue or parenthetical moved to stay with the dialogue it introduces
+    SpeechBlock,
+
+    /// Lines moved so a keep-with-previous element (such as a transition)
+    /// doesn't open the next page alone
+    KeepWithPrevious,
+
+    /// A dual dialogue block moved to stay whole
+    DualDialogue,
+}
+
+/// Length of a scene, measured in lines and eighths of a page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SceneLength {
+    /// Scene heading element that opens the scene
+    pub heading_element: ElementId,
+
+    /// Scene heading text
+    pub heading: String,
+
+    /// Page the scene starts on (None if it was never placed)
+    pub start_page: Option<PageIdentifier>,
+
+    /// Lines the scene occupies across all its pages
+    pub lines: u32,
+
+    /// Length in eighths of a page (minimum 1/8 for any placed scene)
+    pub eighths: u32,
+}
+
+impl SceneLength {
+    /// Format eighths the way schedules print them (e.g., "1 3/8", "5/8", "2")
+    pub fn display_eighths(&self) -> String {
+        let (whole, rest) = (self.eighths / 8, self.eighths % 8);
+        match (whole, rest) {
+            (0, r) => format!("{}/8", r),
+            (w, 0) => format!("{}", w),
+            (w, r) => format!("{} {}/8", w, r),
+        }
+    }
+}
+
+/// Outline entry for a scene heading, for navigation and bookmarks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SceneIndexEntry {
+    /// Scene heading element
+    pub element_id: ElementId,
+
+    /// Scene heading text
+    pub heading: String,
+
+    /// Scene number as printed (sequential, 1-based)
+    pub scene_number: String,
+
+    /// Page the heading appears on
+    pub page: PageIdentifier,
+
+    /// Line of the heading on that page (1-indexed)
+    pub start_line: u8,
 }
 
 /// Statistics about the pagination run
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PaginationStats {
     /// Total page count
     pub page_count: u32,
@@ -59,10 +191,66 @@ pub struct PaginationStats {
 
     /// Pagination timing in microseconds
     pub timing_us: u64,
+
+    /// Page span of each act, for documents with act breaks
+    #[serde(default)]
+    pub acts: Vec<ActStats>,
+
+    /// `PaginationResult::fingerprint` of the output (empty from `PaginationBuilder`)
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+/// Pages spanned by one act
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ActStats {
+    /// Act number, starting at 1
+    pub act: u32,
+
+    /// Act name taken from its closing act break (e.g. "ACT ONE"), empty if unnamed
+    pub label: String,
+
+    pub start_page: PageIdentifier,
+
+    pub end_page: PageIdentifier,
+
+    /// Pages the act touches, counting shared first and last pages
+    pub page_count: u32,
+}
+
+/// Parts of a `PaginationResult` to keep, for slimming what gets serialized
+///
+/// Everything is kept by default; a cheap preview that only needs page counts
+/// can drop the rest. Stats, including the fingerprint, always describe the
+/// full result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ResultOptions {
+    /// Keep `element_positions`
+    pub include_positions: bool,
+
+    /// Keep each page's element placements (pages themselves are always kept)
+    pub include_page_elements: bool,
+
+    /// Keep `warnings`
+    pub include_warnings: bool,
+}
+
+impl Default for ResultOptions {
+    fn default() -> Self {
+        Self {
+            include_positions: true,
+            include_page_elements: true,
+            include_warnings: true,
+        }
+    }
 }
 
 /// Complete result of pagination
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PaginationResult {
     /// All pages in order
     pub pages: Vec<Page>,
@@ -73,8 +261,25 @@ pub struct PaginationResult {
     /// Any warnings generated
     pub warnings: Vec<PaginationWarning>,
 
+    /// Length of each scene in eighths of a page
+    #[serde(default)]
+    pub scene_lengths: Vec<SceneLength>,
+
+    /// Every scene heading with its page and line, in script order
+    #[serde(default)]
+    pub scene_index: Vec<SceneIndexEntry>,
+
     /// Statistics
     pub stats: PaginationStats,
+
+    /// Unnumbered page 0 ahead of the script (None without a title page)
+    #[serde(default)]
+    pub title_page: Option<Page>,
+
+    /// Why each page ended, in page order (empty unless requested with
+    /// `RenderOptions::explain`)
+    #[serde(default)]
+    pub break_explanations: Vec<BreakExplanation>,
 }
 
 impl PaginationResult {
@@ -83,13 +288,19 @@ impl PaginationResult {
             pages: Vec::new(),
             element_positions: HashMap::new(),
             warnings: Vec::new(),
+            scene_lengths: Vec::new(),
+            scene_index: Vec::new(),
             stats: PaginationStats {
                 page_count: 0,
                 element_count: 0,
                 break_count: 0,
                 continuation_count: 0,
                 timing_us: 0,
+                acts: Vec::new(),
+                fingerprint: String::new(),
             },
+            title_page: None,
+            break_explanations: Vec::new(),
         }
     }
 
@@ -100,12 +311,315 @@ impl PaginationResult {
             .and_then(|pos| pos.pages.first())
     }
 
+    /// The element printed at a line of a page, with the character offset in
+    /// its content where that line starts
+    ///
+    /// A CONT'D line resolves to the continued content below it. Blank lines
+    /// and (MORE) markers hit nothing.
+    pub fn element_at(&self, page: &PageIdentifier, line: u8) -> Option<(ElementId, u32)> {
+        let page = self.pages.iter().find(|p| &p.identifier == page)?;
+
+        page.elements.iter().find_map(|page_element| {
+            let first = page_element.start_line;
+            let prefix_line = page_element.continuation_prefix.as_ref().map(|_| first.saturating_sub(1));
+            let hit = (line >= first && line < first + page_element.line_count) || prefix_line == Some(line);
+            if !hit {
+                return None;
+            }
+
+            let part_start = page_element.line_range.as_ref().map_or(0, |range| range.start);
+            let index = part_start + line.saturating_sub(first) as u32;
+            let offset = self
+                .element_positions
+                .get(&page_element.element_id.0)
+                .and_then(|position| position.line_offsets.get(index as usize).copied())
+                .unwrap_or(0);
+            Some((page_element.element_id.clone(), offset))
+        })
+    }
+
+    /// The page and line showing a character offset in an element's content
+    pub fn line_for_offset(&self, element_id: &str, char_offset: u32) -> Option<(PageIdentifier, u8)> {
+        self.locate(element_id, char_offset).map(|(page, line, _)| (page, line))
+    }
+
+    /// Where the caret for a character offset in an element's content goes:
+    /// page, line (1-indexed) and column in characters from the line's start
+    ///
+    /// An offset past the element's placed lines falls back to the start of
+    /// its first placement.
+    pub fn locate(&self, element_id: &str, char_offset: u32) -> Option<(PageIdentifier, u8, u32)> {
+        let position = self.element_positions.get(element_id)?;
+        let index = position.line_offsets.partition_point(|&start| start <= char_offset).saturating_sub(1) as u32;
+        let column = char_offset.saturating_sub(position.line_offsets.get(index as usize).copied().unwrap_or(0));
+
+        let mut fallback = None;
+        for page in self.pages.iter().filter(|p| position.pages.contains(&p.identifier)) {
+            for page_element in page.elements.iter().filter(|e| e.element_id.0 == element_id) {
+                let range = page_element
+                    .line_range
+                    .as_ref()
+                    .map_or(0..page_element.line_count as u32, |range| range.start..range.end);
+                if range.contains(&index) {
+                    let line = page_element.start_line + (index - range.start) as u8;
+                    return Some((page.identifier.clone(), line, column));
+                }
+                fallback.get_or_insert((page.identifier.clone(), page_element.start_line, 0));
+            }
+        }
+
+        fallback
+    }
+
+    /// Stable hash of the pages and element positions, as 16 hex digits
+    ///
+    /// Positions are hashed in element ID order, and stats (timing included)
+    /// are left out, so identical layouts always produce the same value.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = StableHasher::new();
+        for page in &self.pages {
+            hasher.write(&serde_json::to_vec(page).unwrap_or_default());
+        }
+
+        let mut positions: Vec<_> = self.element_positions.iter().collect();
+        positions.sort_unstable_by_key(|(id, _)| id.as_str());
+        for (id, position) in positions {
+            hasher.write(id.as_bytes());
+            hasher.write(&serde_json::to_vec(position).unwrap_or_default());
+        }
+
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Drop the parts `options` leaves out
+    pub fn retain(&mut self, options: &ResultOptions) {
+        if !options.include_positions {
+            self.element_positions.clear();
+        }
+        if !options.include_page_elements {
+            for page in self.pages.iter_mut().chain(self.title_page.as_mut()) {
+                page.elements.clear();
+            }
+        }
+        if !options.include_warnings {
+            self.warnings.clear();
+        }
+    }
+
+    /// The element fragments printed on a range of pages, in print order
+    ///
+    /// Pages are picked by printed number, so `20..=35` takes in 20A as well.
+    /// An element split across the range's edge comes back with the lines
+    /// inside it; one that prints entirely inside has no line range, even if
+    /// it breaks between two of the pages.
+    pub fn slice_pages(&self, range: impl RangeBounds<u32>) -> Vec<PageSliceElement> {
+        let mut parts: HashMap<&str, usize> = HashMap::new();
+        for page_element in self.pages.iter().flat_map(|page| &page.elements) {
+            *parts.entry(page_element.element_id.0.as_str()).or_default() += 1;
+        }
+
+        // Each element in the slice with the number of its parts there
+        let mut slice: Vec<(PageSliceElement, usize)> = Vec::new();
+        let pages = self.pages.iter().filter(|page| range.contains(&page.identifier.sort_key().0));
+        for page_element in pages.flat_map(|page| &page.elements) {
+            let line_range = page_element.line_range.clone();
+            match slice.last_mut() {
+                // The rest of an element continued from the page before
+                Some((last, sliced)) if last.element_id == page_element.element_id && page_element.is_continuation => {
+                    if let (Some(last_range), Some(range)) = (last.line_range.as_mut(), line_range) {
+                        last_range.end = range.end;
+                    }
+                    *sliced += 1;
+                }
+                _ => slice.push((PageSliceElement { element_id: page_element.element_id.clone(), line_range }, 1)),
+            }
+        }
+
+        slice
+            .into_iter()
+            .map(|(mut element, sliced)| {
+                if parts.get(element.element_id.0.as_str()) == Some(&sliced) {
+                    element.line_range = None;
+                }
+                element
+            })
+            .collect()
+    }
+
     /// Get page count
     pub fn page_count(&self) -> u32 {
         self.stats.page_count
     }
 }
 
+/// An element, or the part of one, printed on the pages `slice_pages` took
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PageSliceElement {
+    pub element_id: ElementId,
+
+    /// Lines of the element inside the slice (None when all of it is)
+    pub line_range: Option<LineRange>,
+}
+
+/// Pages an edit would move, from `simulate_edit`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EditImpact {
+    pub page_count_before: u32,
+    pub page_count_after: u32,
+
+    /// First page whose content would start somewhere else (None if no page moves)
+    pub first_shifted_page: Option<PageIdentifier>,
+
+    /// Every page, as numbered after the edit, whose content would start
+    /// somewhere else or that the edit adds
+    pub shifted_pages: Vec<PageIdentifier>,
+}
+
+/// An element whose first page changed between two paginations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MovedElement {
+    pub element_id: ElementId,
+    pub from: PageIdentifier,
+    pub to: PageIdentifier,
+}
+
+/// How pages shifted between two paginations, from `diff_results`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PageDiff {
+    /// Elements in both that start on a different page, in new page order
+    pub moved_elements: Vec<MovedElement>,
+
+    /// Pages in both whose content starts somewhere else, in new page order
+    pub shifted_pages: Vec<PageIdentifier>,
+
+    /// Pages only the new pagination has
+    pub added_pages: Vec<PageIdentifier>,
+
+    /// Pages only the old pagination has
+    pub removed_pages: Vec<PageIdentifier>,
+
+    /// New page count less the old one
+    pub page_count_change: i64,
+}
+
+/// The adjusted layout `paginate_to_target` settled on, with its pagination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CheatResult {
+    pub config: PageConfig,
+    pub result: PaginationResult,
+
+    /// Lines per page added to the base configuration (negative when removed)
+    pub lines_per_page_change: i16,
+
+    /// Characters added to dialogue lines (negative when removed)
+    pub dialogue_width_change: i16,
+}
+
+/// An element whose trimming would pull a nearly empty page back onto the
+/// page before it, from `cheat_suggestions`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CheatSuggestion {
+    pub element_id: ElementId,
+
+    /// Fewest lines the element must lose
+    pub lines_to_save: u32,
+
+    /// The short page that would disappear
+    pub page: PageIdentifier,
+
+    /// Lines printed on the short page
+    pub spill_lines: u8,
+}
+
+/// A page of a new pagination that the previous one doesn't have
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChangedPage {
+    /// Position in the new page list
+    pub index: usize,
+    pub page: Page,
+}
+
+/// A page carried over unchanged from the previous pagination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReusedPage {
+    /// Position in the new page list
+    pub index: usize,
+
+    /// Position of the same page in the previous page list
+    pub previous_index: usize,
+}
+
+/// A pagination expressed as changes to a previous one, for shipping only
+/// what an edit touched
+///
+/// Pages are matched by `content_hash`; every page of the new pagination is
+/// either in `changed_pages` or in `reused_pages`. Everything other than
+/// pages is sent whole in `result`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PaginationDelta {
+    /// New and changed pages, in page order
+    pub changed_pages: Vec<ChangedPage>,
+
+    /// Unchanged pages, in page order
+    pub reused_pages: Vec<ReusedPage>,
+
+    /// The new pagination with `pages` left empty
+    pub result: PaginationResult,
+}
+
+impl PaginationDelta {
+    /// The pages of `result` that differ from `previous`'s, with the rest
+    /// mapped onto the previous page list
+    ///
+    /// Pages without a content hash never match.
+    pub fn between(previous: &PaginationResult, mut result: PaginationResult) -> Self {
+        let previous_by_hash: HashMap<&str, usize> = previous
+            .pages
+            .iter()
+            .enumerate()
+            .filter(|(_, page)| !page.content_hash.is_empty())
+            .map(|(index, page)| (page.content_hash.as_str(), index))
+            .collect();
+
+        let mut changed_pages = Vec::new();
+        let mut reused_pages = Vec::new();
+        for (index, page) in std::mem::take(&mut result.pages).into_iter().enumerate() {
+            match previous_by_hash.get(page.content_hash.as_str()) {
+                Some(&previous_index) => reused_pages.push(ReusedPage { index, previous_index }),
+                None => changed_pages.push(ChangedPage { index, page }),
+            }
+        }
+
+        Self { changed_pages, reused_pages, result }
+    }
+
+    /// Rebuild the full result from the pagination this delta was taken against
+    ///
+    /// Returns None if `previous` lacks a page the delta reuses.
+    pub fn apply(self, previous: &PaginationResult) -> Option<PaginationResult> {
+        let mut pages: Vec<Option<Page>> = vec![None; self.changed_pages.len() + self.reused_pages.len()];
+        for changed in self.changed_pages {
+            *pages.get_mut(changed.index)? = Some(changed.page);
+        }
+        for reused in &self.reused_pages {
+            *pages.get_mut(reused.index)? = Some(previous.pages.get(reused.previous_index)?.clone());
+        }
+
+        let mut result = self.result;
+        result.pages = pages.into_iter().collect::<Option<_>>()?;
+        Some(result)
+    }
+}
+
 impl Default for PaginationResult {
     fn default() -> Self {
         Self::new()
@@ -116,6 +630,80 @@ impl Default for PaginationResult {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_display_eighths() {
+        let mut length = SceneLength {
+            heading_element: ElementId::new("1"),
+            heading: "INT. OFFICE - DAY".to_string(),
+            start_page: Some(PageIdentifier::Sequential(1)),
+            lines: 0,
+            eighths: 5,
+        };
+        assert_eq!(length.display_eighths(), "5/8");
+        length.eighths = 16;
+        assert_eq!(length.display_eighths(), "2");
+        length.eighths = 11;
+        assert_eq!(length.display_eighths(), "1 3/8");
+    }
+
+    #[test]
+    fn test_retain_result_options() {
+        use crate::layout::paginate;
+        use crate::types::{Element, ElementType, PageConfig};
+
+        let elements = vec![Element::new("1", ElementType::Action, "A short scene.")];
+        let mut result = paginate(&elements, &PageConfig::feature_film());
+
+        result.retain(&ResultOptions::default());
+        assert_eq!(result.pages[0].elements.len(), 1);
+        assert_eq!(result.element_positions.len(), 1);
+
+        let options: ResultOptions = serde_json::from_str(r#"{"include_page_elements": false}"#).unwrap();
+        result.retain(&options);
+        assert_eq!(result.pages.len(), 1);
+        assert!(result.pages[0].elements.is_empty());
+        assert_eq!(result.element_positions.len(), 1);
+    }
+
+    #[test]
+    fn test_slice_pages() {
+        use crate::layout::paginate;
+        use crate::types::{Element, ElementType, PageConfig};
+
+        let elements = vec![
+            Element::new("1", ElementType::Action, "First page.").with_force_page_break(),
+            Element::new("2", ElementType::Action, "Something happens.\n".repeat(120).trim_end()),
+            Element::new("3", ElementType::Action, "The end."),
+        ];
+        let result = paginate(&elements, &PageConfig::feature_film());
+        assert_eq!(result.page_count(), 4);
+
+        // Element 2 runs from page 2 to page 4, so pages 3-4 hold only its tail
+        let tail = result.slice_pages(3..=4);
+        let last_part = result.pages[3].elements[0].line_range.clone().unwrap();
+        let middle_part = result.pages[2].elements[0].line_range.clone().unwrap();
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[0].element_id, ElementId::new("2"));
+        assert_eq!(tail[0].line_range, Some(LineRange { start: middle_part.start, end: last_part.end }));
+        assert_eq!(tail[1], PageSliceElement { element_id: ElementId::new("3"), line_range: None });
+
+        let whole: Vec<_> = result.slice_pages(..).into_iter().map(|e| (e.element_id.0, e.line_range)).collect();
+        assert_eq!(whole, vec![("1".to_string(), None), ("2".to_string(), None), ("3".to_string(), None)]);
+        assert!(result.slice_pages(9..).is_empty());
+    }
+
+    #[test]
+    fn test_result_saved_before_scene_fields_loads() {
+        let mut json = serde_json::to_value(PaginationResult::new()).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("scene_lengths");
+        fields.remove("scene_index");
+
+        let result: PaginationResult = serde_json::from_value(json).unwrap();
+        assert!(result.scene_lengths.is_empty());
+        assert!(result.scene_index.is_empty());
+    }
+
     #[test]
     fn test_pagination_result_new() {
         let result = PaginationResult::new();
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use super::{Element, PageIdentifier, PaginationResult};
+use crate::layout::compare_drafts;
+
+/// Page colors for successive script revisions, in the standard order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RevisionColor {
+    #[default]
+    White,
+    Blue,
+    Pink,
+    Yellow,
+    Green,
+    Goldenrod,
+    Buff,
+    Salmon,
+    Cherry,
+}
+
+impl RevisionColor {
+    const SEQUENCE: [RevisionColor; 9] = [
+        RevisionColor::White,
+        RevisionColor::Blue,
+        RevisionColor::Pink,
+        RevisionColor::Yellow,
+        RevisionColor::Green,
+        RevisionColor::Goldenrod,
+        RevisionColor::Buff,
+        RevisionColor::Salmon,
+        RevisionColor::Cherry,
+    ];
+
+    /// Color of the nth revision after the white draft, cycling after cherry
+    pub fn for_revision(revision: u32) -> Self {
+        Self::SEQUENCE[revision as usize % Self::SEQUENCE.len()]
+    }
+
+    /// The color that follows this one
+    pub fn next(self) -> Self {
+        let index = Self::SEQUENCE.iter().position(|&c| c == self).unwrap_or(0);
+        Self::SEQUENCE[(index + 1) % Self::SEQUENCE.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RevisionColor::White => "White",
+            RevisionColor::Blue => "Blue",
+            RevisionColor::Pink => "Pink",
+            RevisionColor::Yellow => "Yellow",
+            RevisionColor::Green => "Green",
+            RevisionColor::Goldenrod => "Goldenrod",
+            RevisionColor::Buff => "Buff",
+            RevisionColor::Salmon => "Salmon",
+            RevisionColor::Cherry => "Cherry",
+        }
+    }
+}
+
+/// A page that has to be reissued in a revision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RevisedPage {
+    pub page: PageIdentifier,
+
+    /// Paper color the page is reissued on
+    pub color: RevisionColor,
+
+    /// Whether the page did not exist in the old draft
+    pub is_new: bool,
+}
+
+/// Pages to reissue between two drafts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RevisionReport {
+    pub color: RevisionColor,
+
+    /// Changed or added pages, in page order
+    pub revised_pages: Vec<RevisedPage>,
+
+    /// Old pages with no counterpart in the new draft
+    pub removed_pages: Vec<PageIdentifier>,
+
+    /// One-paragraph memo, e.g. "Blue revision: pages 3, 5-7, 12A (4 pages)."
+    pub summary: String,
+}
+
+/// A revision's place in the color sequence: the color, and how many times
+/// the sequence has come round to it (2 for double colors)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RevisionLevel {
+    /// Revisions issued after the white draft, this one included
+    pub revision: u32,
+    pub color: RevisionColor,
+    pub pass: u32,
+}
+
+impl RevisionLevel {
+    /// Level of the nth revision after the white draft: white through
+    /// cherry, then double white through double cherry, and so on
+    pub fn for_revision(revision: u32) -> Self {
+        let colors = RevisionColor::SEQUENCE.len() as u32;
+        Self {
+            revision,
+            color: RevisionColor::for_revision(revision),
+            pass: revision / colors + 1,
+        }
+    }
+
+    /// Name for memos and page headers ("Pink", "Double Blue")
+    pub fn label(&self) -> String {
+        match self.pass {
+            0 | 1 => self.color.name().to_string(),
+            2 => format!("Double {}", self.color.name()),
+            3 => format!("Triple {}", self.color.name()),
+            pass => format!("{}x {}", pass, self.color.name()),
+        }
+    }
+}
+
+/// Revisions one page has been issued in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PageRevisionHistory {
+    pub page: PageIdentifier,
+
+    /// Oldest first; the last is the color the page is printed on
+    pub revisions: Vec<RevisionLevel>,
+}
+
+/// Revision state carried from one pagination run to the next
+///
+/// Starts at the white draft. Each `record` issues the next color in the
+/// sequence, tags the new draft's pages with it and keeps every page's
+/// history, so hosts can persist the tracker alongside the script.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct RevisionTracker {
+    /// Revisions issued after the white draft
+    pub revision: u32,
+
+    /// Every page revised so far, in page order
+    pub pages: Vec<PageRevisionHistory>,
+}
+
+impl RevisionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The revision most recently issued (the white draft before any)
+    pub fn current(&self) -> RevisionLevel {
+        RevisionLevel::for_revision(self.revision)
+    }
+
+    /// Revisions a page has been issued in (None if it never was)
+    pub fn history(&self, page: &PageIdentifier) -> Option<&PageRevisionHistory> {
+        self.pages.iter().find(|history| &history.page == page)
+    }
+
+    /// Issue the next revision between two drafts
+    ///
+    /// The pages `compare_drafts` finds changed are added to their histories
+    /// in the new color. Every page of `new_result` then takes the color of
+    /// its latest revision, so pages untouched this time keep the color they
+    /// were last issued on.
+    pub fn record(
+        &mut self,
+        old_elements: &[Element],
+        old_result: &PaginationResult,
+        new_elements: &[Element],
+        new_result: &mut PaginationResult,
+    ) -> RevisionReport {
+        self.revision += 1;
+        let level = self.current();
+        let mut report = compare_drafts(old_elements, old_result, new_elements, new_result, level.color);
+        if level.pass > 1 {
+            report.summary = report.summary.replacen(level.color.name(), &level.label(), 1);
+        }
+
+        for revised in &report.revised_pages {
+            match self.pages.iter_mut().find(|history| history.page == revised.page) {
+                Some(history) => history.revisions.push(level),
+                None => self.pages.push(PageRevisionHistory { page: revised.page.clone(), revisions: vec![level] }),
+            }
+        }
+        self.pages.sort_by_key(|history| history.page.sort_key());
+
+        for page in &mut new_result.pages {
+            let color = self.history(&page.identifier).and_then(|history| history.revisions.last()).map(|l| l.color);
+            if page.revision_color != color {
+                page.revision_color = color;
+                page.content_hash = page.compute_content_hash(&HashMap::new());
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revision_color_sequence() {
+        assert_eq!(RevisionColor::for_revision(0), RevisionColor::White);
+        assert_eq!(RevisionColor::for_revision(1), RevisionColor::Blue);
+        assert_eq!(RevisionColor::White.next(), RevisionColor::Blue);
+        assert_eq!(RevisionColor::Cherry.next(), RevisionColor::White);
+        assert_eq!(RevisionColor::for_revision(9), RevisionColor::White);
+    }
+
+    #[test]
+    fn test_revision_level_double_colors() {
+        assert_eq!(RevisionLevel::for_revision(2).label(), "Pink");
+        let double = RevisionLevel::for_revision(10);
+        assert_eq!((double.color, double.pass), (RevisionColor::Blue, 2));
+        assert_eq!(double.label(), "Double Blue");
+        assert_eq!(RevisionLevel::for_revision(18).label(), "Triple White");
+    }
+
+    #[test]
+    fn test_revision_tracker_history() {
+        use crate::layout::paginate;
+        use crate::types::{ElementType, PageConfig};
+
+        let config = PageConfig::feature_film();
+        let white: Vec<Element> = (0..120)
+            .map(|i| Element::new(i.to_string(), ElementType::Action, format!("Action paragraph {}.", i)))
+            .collect();
+        let white_result = paginate(&white, &config);
+        let mut tracker = RevisionTracker::new();
+        assert_eq!(tracker.current().color, RevisionColor::White);
+
+        let mut blue = white.clone();
+        blue[0].content = "A rewritten opening.".to_string();
+        let mut blue_result = paginate(&blue, &config);
+        let report = tracker.record(&white, &white_result, &blue, &mut blue_result);
+        assert_eq!(report.color, RevisionColor::Blue);
+        assert_eq!(blue_result.pages[0].revision_color, Some(RevisionColor::Blue));
+        assert_eq!(blue_result.pages[1].revision_color, None);
+
+        // The pink pass touches a later page; page 1 stays blue
+        let mut pink = blue.clone();
+        pink[119].content = "A rewritten ending.".to_string();
+        let mut pink_result = paginate(&pink, &config);
+        tracker.record(&blue, &blue_result, &pink, &mut pink_result);
+        let last = pink_result.pages.last().unwrap();
+        assert_eq!(last.revision_color, Some(RevisionColor::Pink));
+        assert_eq!(pink_result.pages[0].revision_color, Some(RevisionColor::Blue));
+        assert_eq!(tracker.pages.len(), 2);
+        assert_eq!(tracker.history(&last.identifier).unwrap().revisions, vec![RevisionLevel::for_revision(2)]);
+    }
+}
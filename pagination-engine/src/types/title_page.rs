@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Title page content, laid out on an unnumbered page ahead of the script
+/// (see `layout_title_page`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct TitlePage {
+    pub title: String,
+
+    /// Line between the title and the authors (e.g., "Written by")
+    pub credit: Option<String>,
+
+    pub authors: Vec<String>,
+
+    /// Source material (e.g., "Based on the novel by ...")
+    pub source: Option<String>,
+
+    pub draft_date: Option<String>,
+
+    /// Contact block, one entry per line (name, address, phone, email)
+    pub contact: Vec<String>,
+}
+
+impl TitlePage {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self { title: title.into(), ..Self::default() }
+    }
+}
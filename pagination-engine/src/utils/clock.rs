@@ -0,0 +1,98 @@
+/// Source of elapsed time for pagination timing stats
+///
+/// `std::time::Instant` panics on wasm32-unknown-unknown, so timing goes
+/// through this trait and each target supplies a working implementation.
+pub trait Clock {
+    /// Current time in microseconds from an arbitrary fixed origin
+    fn now_us(&self) -> u64;
+}
+
+/// Clock that never advances; timing stats are reported as 0
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoClock;
+
+impl Clock for NoClock {
+    fn now_us(&self) -> u64 {
+        0
+    }
+}
+
+/// Monotonic clock for native builds
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SystemClock {
+    pub fn new() -> Self {
+        Self { origin: std::time::Instant::now() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn now_us(&self) -> u64 {
+        self.origin.elapsed().as_micros() as u64
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod performance {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = performance, js_name = now)]
+        pub fn performance_now() -> f64;
+    }
+}
+
+/// `performance.now()` clock for browsers and web workers
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerformanceClock;
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+impl Clock for PerformanceClock {
+    fn now_us(&self) -> u64 {
+        (performance::performance_now() * 1000.0) as u64
+    }
+}
+
+/// The best available clock for the current target
+pub fn default_clock() -> Box<dyn Clock> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Box::new(SystemClock::new());
+
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    return Box::new(PerformanceClock);
+
+    #[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+    return Box::new(NoClock);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_clock() {
+        assert_eq!(NoClock.now_us(), 0);
+    }
+
+    #[test]
+    fn test_system_clock_is_monotonic() {
+        let clock = SystemClock::new();
+        let first = clock.now_us();
+        assert!(clock.now_us() >= first);
+    }
+}
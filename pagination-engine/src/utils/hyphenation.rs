@@ -0,0 +1,236 @@
+use crate::types::HyphenationLanguage;
+
+/// A legal point at which a word may be broken across lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HyphenPoint {
+    /// Byte offset into the word where the break occurs
+    pub index: usize,
+
+    /// Break follows a hyphen already in the word (no hyphen is added)
+    pub explicit: bool,
+}
+
+/// Per-language hyphenation rules
+struct LanguageRules {
+    /// Minimum characters kept before a break
+    min_left: usize,
+
+    /// Minimum characters carried after a break
+    min_right: usize,
+
+    /// Consonant pairs that are never separated (e.g., "th")
+    inseparable: &'static [&'static str],
+}
+
+const ENGLISH: LanguageRules = LanguageRules {
+    min_left: 2,
+    min_right: 3,
+    inseparable: &["ch", "ck", "gh", "ng", "ph", "qu", "sh", "th", "wh"],
+};
+
+const GERMAN: LanguageRules = LanguageRules {
+    min_left: 2,
+    min_right: 2,
+    inseparable: &["ch", "ck", "ph", "sc", "sh", "th"],
+};
+
+const FRENCH: LanguageRules = LanguageRules {
+    min_left: 2,
+    min_right: 3,
+    inseparable: &[
+        "bl", "br", "ch", "cl", "cr", "dr", "fl", "fr", "gl", "gn", "gr", "gu", "ph", "pl", "pr",
+        "qu", "th", "tr", "vr",
+    ],
+};
+
+const SPANISH: LanguageRules = LanguageRules {
+    min_left: 2,
+    min_right: 2,
+    inseparable: &[
+        "bl", "br", "ch", "cl", "cr", "dr", "fl", "fr", "gl", "gr", "ll", "pl", "pr", "rr", "tr",
+    ],
+};
+
+fn rules_for(language: HyphenationLanguage) -> &'static LanguageRules {
+    match language {
+        HyphenationLanguage::EnUs | HyphenationLanguage::EnGb => &ENGLISH,
+        HyphenationLanguage::De => &GERMAN,
+        HyphenationLanguage::Fr => &FRENCH,
+        HyphenationLanguage::Es => &SPANISH,
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(
+        c,
+        'a' | 'e' | 'i' | 'o' | 'u' | 'y'
+            | 'à' | 'â' | 'ä' | 'á' | 'é' | 'è' | 'ê' | 'ë'
+            | 'í' | 'î' | 'ï' | 'ó' | 'ô' | 'ö' | 'ú' | 'ù' | 'û' | 'ü'
+    )
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_alphabetic() && !is_vowel(c)
+}
+
+/// Soft hyphen (U+00AD): an invisible, author-placed break point
+pub const SOFT_HYPHEN: char = '\u{ad}';
+
+/// Break points after each soft hyphen in a word
+///
+/// The soft hyphen stays at the end of the head; it prints as a hyphen only
+/// when the word breaks there.
+pub fn soft_hyphen_points(word: &str) -> Vec<HyphenPoint> {
+    word.char_indices()
+        .filter(|&(_, c)| c == SOFT_HYPHEN)
+        .map(|(i, c)| HyphenPoint { index: i + c.len_utf8(), explicit: false })
+        .collect()
+}
+
+/// Break points for a word listed in a hyphenation dictionary
+///
+/// Entries mark their points with hyphens ("tes-ta-ment") and match the
+/// word's letters case-insensitively, ignoring punctuation and markup around
+/// them. Returns None when no entry matches.
+pub fn dictionary_points(word: &str, dictionary: &[String]) -> Option<Vec<HyphenPoint>> {
+    let start = word.find(char::is_alphabetic)?;
+    let end = word.rfind(char::is_alphabetic).map(|i| i + word[i..].chars().next().map_or(1, char::len_utf8))?;
+    let core = word[start..end].to_lowercase();
+
+    let entry = dictionary.iter().find(|entry| entry.replace('-', "").to_lowercase() == core)?;
+
+    // Character counts before each marked hyphen, mapped to byte offsets in the word
+    let offsets: Vec<usize> = word[start..end].char_indices().map(|(i, _)| start + i).collect();
+    let mut letters = 0;
+    let mut points = Vec::new();
+    for c in entry.chars() {
+        if c == '-' {
+            if let Some(&index) = offsets.get(letters).filter(|_| letters > 0) {
+                points.push(HyphenPoint { index, explicit: false });
+            }
+        } else {
+            letters += 1;
+        }
+    }
+
+    Some(points)
+}
+
+/// Find the legal hyphenation points in a single word
+///
+/// Existing hyphens are always break points. Within alphabetic runs the
+/// syllable rules V-CV and VC-CV apply, keeping the language's inseparable
+/// consonant pairs together and honoring its minimum fragment lengths.
+pub fn hyphenation_points(word: &str, language: HyphenationLanguage) -> Vec<HyphenPoint> {
+    let rules = rules_for(language);
+    let chars: Vec<(usize, char)> = word
+        .char_indices()
+        .map(|(i, c)| (i, c.to_lowercase().next().unwrap_or(c)))
+        .collect();
+    let len = chars.len();
+    let mut points = Vec::new();
+
+    let pair = |i: usize| -> String { [chars[i].1, chars[i + 1].1].iter().collect() };
+    let inseparable = |i: usize| rules.inseparable.contains(&pair(i).as_str());
+
+    for i in 1..len {
+        let prev = chars[i - 1].1;
+        let cur = chars[i].1;
+
+        if prev == '-' {
+            points.push(HyphenPoint { index: chars[i].0, explicit: true });
+            continue;
+        }
+
+        if i < rules.min_left || len - i < rules.min_right {
+            continue;
+        }
+
+        // Both sides of the break must be letters within the same run
+        if !cur.is_alphabetic() || !prev.is_alphabetic() {
+            continue;
+        }
+
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let legal = if is_vowel(prev) && is_consonant(cur) {
+            // V-CV, or V-CCV when the consonants form an inseparable pair
+            match next {
+                Some(n) if is_vowel(n) => true,
+                Some(n) if is_consonant(n) && inseparable(i) => {
+                    chars.get(i + 2).is_some_and(|&(_, c)| is_vowel(c))
+                }
+                _ => false,
+            }
+        } else if is_consonant(prev) && is_consonant(cur) && i >= 2 {
+            // VC-CV, never splitting an inseparable pair
+            is_vowel(chars[i - 2].1)
+                && next.is_some_and(is_vowel)
+                && !inseparable(i - 1)
+        } else {
+            false
+        };
+
+        if legal {
+            points.push(HyphenPoint { index: chars[i].0, explicit: false });
+        }
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(word: &str, language: HyphenationLanguage) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut last = 0;
+        for point in hyphenation_points(word, language) {
+            parts.push(word[last..point.index].to_string());
+            last = point.index;
+        }
+        parts.push(word[last..].to_string());
+        parts
+    }
+
+    #[test]
+    fn test_english_syllables() {
+        assert_eq!(split("motherboard", HyphenationLanguage::EnUs), vec!["mo", "ther", "board"]);
+        assert_eq!(split("pattern", HyphenationLanguage::EnUs), vec!["pat", "tern"]);
+    }
+
+    #[test]
+    fn test_existing_hyphen_is_explicit() {
+        let points = hyphenation_points("self-contained", HyphenationLanguage::EnUs);
+        assert!(points.contains(&HyphenPoint { index: 5, explicit: true }));
+    }
+
+    #[test]
+    fn test_minimum_fragments() {
+        // "a-way" would leave a one-letter head, "ope-ra" a two-letter tail
+        assert!(hyphenation_points("away", HyphenationLanguage::EnUs).is_empty());
+        assert!(hyphenation_points("opera", HyphenationLanguage::EnUs).is_empty());
+    }
+
+    #[test]
+    fn test_soft_hyphen_points() {
+        let word = "super\u{ad}cali\u{ad}fragilistic";
+        let points = soft_hyphen_points(word);
+        assert_eq!(points.len(), 2);
+        assert_eq!(&word[..points[0].index], "super\u{ad}");
+    }
+
+    #[test]
+    fn test_dictionary_points() {
+        let dictionary = vec!["tes-ta-ment".to_string()];
+
+        let points = dictionary_points("*Testament,*", &dictionary).unwrap();
+        assert_eq!(points, vec![HyphenPoint { index: 4, explicit: false }, HyphenPoint { index: 6, explicit: false }]);
+        assert!(dictionary_points("testimony", &dictionary).is_none());
+    }
+
+    #[test]
+    fn test_non_alphabetic_words() {
+        assert!(hyphenation_points("1234567890", HyphenationLanguage::EnUs).is_empty());
+    }
+}
@@ -0,0 +1,383 @@
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// No-break space (U+00A0): prints as a space but never wraps
+pub const NO_BREAK_SPACE: char = '\u{a0}';
+
+/// Whitespace that wrapping may break at and normalization collapses
+///
+/// No-break spaces, including the figure and narrow variants, print as
+/// spaces but are kept as part of the word.
+pub fn is_breaking_space(c: char) -> bool {
+    c.is_whitespace() && !matches!(c, NO_BREAK_SPACE | '\u{2007}' | '\u{202f}')
+}
+
+/// Whether `c` is a bidi formatting mark (LRM, RLM, ALM, embeddings and isolates)
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{200e}' | '\u{200f}' | '\u{061c}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Text with its emphasis markers paired, so any slice of it measures the
+/// way it prints in place
+///
+/// A run of `*` (or of `_`) is markup only when it opens a span and a run
+/// of the same length closes it later: an opener is followed by a printed
+/// character, a closer preceded by one. Unpaired runs print, so a "______"
+/// signature line or "5 * 3" keeps its full width. Pairing over the whole
+/// text lets a span that covers several words hide its markers when the
+/// words are measured one at a time.
+#[derive(Debug, Clone)]
+pub struct Markup<'t> {
+    text: &'t str,
+
+    /// Byte index of every `*` and `_` that is markup, ascending
+    markers: Vec<usize>,
+}
+
+impl<'t> Markup<'t> {
+    pub fn new(text: &'t str) -> Self {
+        if !text.contains(['*', '_']) {
+            return Self { text, markers: Vec::new() };
+        }
+
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let mut open: Vec<(char, usize, usize)> = Vec::new();
+        let mut markers = Vec::new();
+
+        let mut i = 0;
+        while i < chars.len() {
+            let (start, c) = chars[i];
+            if c == '\\' {
+                i += 2;
+                continue;
+            }
+            if c != '*' && c != '_' {
+                i += 1;
+                continue;
+            }
+
+            let len = chars[i..].iter().take_while(|&&(_, next)| next == c).count();
+            let printed = |index: Option<usize>| {
+                index.and_then(|index| chars.get(index)).is_some_and(|&(_, c)| !c.is_whitespace())
+            };
+            let can_open = printed(Some(i + len));
+            let can_close = printed(i.checked_sub(1));
+
+            let opener = open.iter().rposition(|&(marker, run, _)| marker == c && run == len);
+            match opener.filter(|_| can_close) {
+                Some(index) => {
+                    let (_, _, opened_at) = open[index];
+                    markers.extend(opened_at..opened_at + len);
+                    markers.extend(start..start + len);
+                    open.truncate(index);
+                }
+                None if can_open => open.push((c, len, start)),
+                None => {}
+            }
+            i += len;
+        }
+
+        markers.sort_unstable();
+        Self { text, markers }
+    }
+
+    pub fn text(&self) -> &'t str {
+        self.text
+    }
+
+    /// Whether the character at byte `index` is an emphasis marker
+    fn is_marker(&self, index: usize) -> bool {
+        self.markers.binary_search(&index).is_ok()
+    }
+
+    /// Grapheme clusters of `range`, each with its byte index in the slice
+    /// and the cluster it prints (None for markers)
+    ///
+    /// Paired markers are invisible, as are soft hyphens and the bidi marks
+    /// right-to-left text carries, and a backslash escape prints only the
+    /// escaped cluster. Working on clusters keeps accented letters written
+    /// with combining marks ("ZOË") and emoji sequences whole and one
+    /// character wide.
+    fn printed_graphemes(&self, range: Range<usize>) -> impl Iterator<Item = (usize, Option<&'t str>)> + '_ {
+        let offset = range.start;
+        let mut graphemes = ascii_or_grapheme_indices(&self.text[range]);
+        std::iter::from_fn(move || {
+            let (i, grapheme) = graphemes()?;
+            Some(match grapheme {
+                "\\" => (i, graphemes().map(|(_, escaped)| escaped)),
+                "*" | "_" if self.is_marker(offset + i) => (i, None),
+                "\u{ad}" => (i, None),
+                _ if grapheme.chars().all(is_bidi_control) => (i, None),
+                _ => (i, Some(grapheme)),
+            })
+        })
+    }
+
+    /// Number of printed characters in `range`, as `visible_width`
+    pub fn visible_width(&self, range: Range<usize>) -> usize {
+        self.printed_graphemes(range).filter(|(_, printed)| printed.is_some()).count()
+    }
+
+    /// Printed width of `range` with per-character widths, as `visible_width_by`
+    pub fn visible_width_by(&self, range: Range<usize>, char_width: impl Fn(char) -> usize) -> usize {
+        self.printed_graphemes(range)
+            .filter_map(|(_, printed)| printed)
+            .map(|grapheme| grapheme_width(grapheme, &char_width))
+            .sum()
+    }
+
+    /// Byte index within `range` just past its first `width` printed
+    /// characters, as `byte_index_at_width`
+    pub fn byte_index_at_width(&self, range: Range<usize>, width: usize) -> usize {
+        let len = range.len();
+        let mut consumed = 0;
+
+        for (i, printed) in self.printed_graphemes(range) {
+            if consumed == width {
+                if printed.is_none() {
+                    continue;
+                }
+                return i;
+            }
+
+            if printed.is_some() {
+                consumed += 1;
+            }
+        }
+
+        len
+    }
+
+    /// Byte index within `range` ending its longest prefix no wider than
+    /// `width`, as `byte_index_at_width_by`
+    pub fn byte_index_at_width_by(&self, range: Range<usize>, width: usize, char_width: impl Fn(char) -> usize) -> usize {
+        let len = range.len();
+        let mut consumed = 0;
+
+        for (i, printed) in self.printed_graphemes(range) {
+            if let Some(grapheme) = printed {
+                let w = grapheme_width(grapheme, &char_width);
+                if consumed > 0 && consumed + w > width {
+                    return i;
+                }
+                consumed += w;
+            }
+        }
+
+        len
+    }
+}
+
+/// Grapheme clusters of `text` with their byte indices
+///
+/// ASCII text, by far the most common, skips segmentation: every byte is a
+/// cluster of its own except a CR LF pair.
+fn ascii_or_grapheme_indices<'t>(text: &'t str) -> impl FnMut() -> Option<(usize, &'t str)> {
+    let mut graphemes = (!text.is_ascii()).then(|| text.grapheme_indices(true));
+    let mut next = 0;
+    move || match &mut graphemes {
+        Some(graphemes) => graphemes.next(),
+        None if next < text.len() => {
+            let i = next;
+            next += if text[i..].starts_with("\r\n") { 2 } else { 1 };
+            Some((i, &text[i..next]))
+        }
+        None => None,
+    }
+}
+
+/// Width of a grapheme cluster: its base character's width
+fn grapheme_width(grapheme: &str, char_width: &impl Fn(char) -> usize) -> usize {
+    grapheme.chars().next().map_or(0, char_width)
+}
+
+/// Number of printed characters in text that may contain emphasis markup
+///
+/// Markers are stored in element content but never printed: paired `*`
+/// and `_` runs are invisible, and a backslash escape prints only the
+/// escaped character. Characters are grapheme clusters, so combining marks
+/// add no width.
+pub fn visible_width(text: &str) -> usize {
+    Markup::new(text).visible_width(0..text.len())
+}
+
+/// Byte index just past the first `width` printed characters of `text`
+///
+/// Markers that directly follow the last printed character stay with it, so
+/// a closing `*` is never carried onto a line of its own. The index is always
+/// a grapheme boundary.
+pub fn byte_index_at_width(text: &str, width: usize) -> usize {
+    Markup::new(text).byte_index_at_width(0..text.len(), width)
+}
+
+/// Printed width of text with per-character widths, skipping markup like `visible_width`
+pub fn visible_width_by(text: &str, char_width: impl Fn(char) -> usize) -> usize {
+    Markup::new(text).visible_width_by(0..text.len(), char_width)
+}
+
+/// Byte index ending the longest prefix no wider than `width`, measured with
+/// per-character widths
+///
+/// Always keeps at least one printed character so callers make progress.
+pub fn byte_index_at_width_by(text: &str, width: usize, char_width: impl Fn(char) -> usize) -> usize {
+    Markup::new(text).byte_index_at_width_by(0..text.len(), width, char_width)
+}
+
+/// Emphasis of a run of printed text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Emphasis {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+/// Split text into runs of uniform emphasis, without the markers
+///
+/// `***` toggles bold italic, `**` bold, `*` italic and `_` underline; a
+/// backslash escape prints the escaped character in the current run.
+/// Unpaired markers print as themselves, as `Markup` measures them.
+pub fn emphasis_runs(text: &str) -> Vec<(String, Emphasis)> {
+    let markup = Markup::new(text);
+    let mut runs: Vec<(String, Emphasis)> = Vec::new();
+    let mut current = Emphasis::default();
+    let mut chars = text.char_indices().peekable();
+
+    let mut push = |c: char, emphasis: Emphasis| match runs.last_mut() {
+        Some((run, last)) if *last == emphasis => run.push(c),
+        _ => runs.push((c.to_string(), emphasis)),
+    };
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    push(escaped, current);
+                }
+            }
+            '*' if markup.is_marker(i) => {
+                let mut stars = 1;
+                while stars < 3 && chars.next_if(|&(_, c)| c == '*').is_some() {
+                    stars += 1;
+                }
+                current.italic ^= stars != 2;
+                current.bold ^= stars != 1;
+            }
+            '_' if markup.is_marker(i) => current.underline = !current.underline,
+            _ => push(c, current),
+        }
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_width_by_character() {
+        let width = |c: char| if c == 'm' { 3 } else { 1 };
+        assert_eq!(visible_width_by("*mom*", width), 7);
+        assert_eq!(byte_index_at_width_by("mmmm", 7, width), 2);
+        assert_eq!(byte_index_at_width_by("mmmm", 1, width), 1);
+    }
+
+    #[test]
+    fn test_plain_text_width() {
+        assert_eq!(visible_width("INT. OFFICE - DAY"), 17);
+        // CR LF is one cluster, as segmentation would find it
+        assert_eq!(visible_width("a\r\nb"), 3);
+        assert_eq!(byte_index_at_width("a\r\nb", 2), 3);
+    }
+
+    #[test]
+    fn test_markers_are_invisible() {
+        assert_eq!(visible_width("*italic*"), 6);
+        assert_eq!(visible_width("**bold**"), 4);
+        assert_eq!(visible_width("***both***"), 4);
+        assert_eq!(visible_width("_under_"), 5);
+    }
+
+    #[test]
+    fn test_unpaired_markers_are_visible() {
+        assert_eq!(visible_width("______"), 6);
+        assert_eq!(visible_width("Sign: ______ Date: ____"), 23);
+        assert_eq!(visible_width("5 * 3 = 15"), 10);
+        assert_eq!(visible_width("snake_case"), 10);
+        assert_eq!(byte_index_at_width("______", 3), 3);
+    }
+
+    #[test]
+    fn test_odd_marker_counts() {
+        // The third star has no partner and prints
+        assert_eq!(visible_width("*one* and *two"), 12);
+        // A closing run must match the opening run's length
+        assert_eq!(visible_width("**bold*"), 7);
+        assert_eq!(visible_width("_a_ _b"), 4);
+    }
+
+    #[test]
+    fn test_markup_pairs_across_words() {
+        let text = "*two words* and 5 * 3";
+        let markup = Markup::new(text);
+        assert_eq!(markup.visible_width(0..4), 3);
+        assert_eq!(markup.visible_width(5..11), 5);
+        assert_eq!(markup.visible_width(18..19), 1);
+    }
+
+    #[test]
+    fn test_escaped_markers_are_visible() {
+        assert_eq!(visible_width(r"\*not italic\*"), 12);
+    }
+
+    #[test]
+    fn test_byte_index_keeps_closing_marker() {
+        assert_eq!(byte_index_at_width("**bold**text", 4), 8);
+        assert_eq!(byte_index_at_width("plain", 3), 3);
+        assert_eq!(byte_index_at_width("short", 10), 5);
+    }
+
+    #[test]
+    fn test_width_counts_grapheme_clusters() {
+        // "ZOË" with a combining diaeresis, and a family emoji sequence
+        assert_eq!(visible_width("ZOE\u{308}"), 3);
+        assert_eq!(visible_width("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}!"), 2);
+        assert_eq!(byte_index_at_width("ZOE\u{308}Y", 3), 5);
+        assert_eq!(byte_index_at_width_by("E\u{308}E\u{308}", 1, |_| 1), 3);
+    }
+
+    #[test]
+    fn test_bidi_marks_are_invisible() {
+        assert_eq!(visible_width("\u{200f}שלום\u{200f} (CONT'D)"), 13);
+        assert_eq!(visible_width_by("\u{2067}ab\u{2069}", |_| 2), 4);
+    }
+
+    #[test]
+    fn test_emphasis_runs() {
+        let bold = Emphasis { bold: true, ..Default::default() };
+        let all = Emphasis { bold: true, italic: true, underline: true };
+
+        let runs = emphasis_runs("A **big** _***deal***_ \\*");
+        assert_eq!(
+            runs,
+            vec![
+                ("A ".to_string(), Emphasis::default()),
+                ("big".to_string(), bold),
+                (" ".to_string(), Emphasis::default()),
+                ("deal".to_string(), all),
+                (" *".to_string(), Emphasis::default()),
+            ]
+        );
+
+        let underline = Emphasis { underline: true, ..Default::default() };
+        assert_eq!(
+            emphasis_runs("Sign: ______ and _here_ *"),
+            vec![
+                ("Sign: ______ and ".to_string(), Emphasis::default()),
+                ("here".to_string(), underline),
+                (" *".to_string(), Emphasis::default()),
+            ]
+        );
+    }
+}
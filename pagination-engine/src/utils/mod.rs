@@ -1,3 +1,11 @@
+mod clock;
+mod hash;
+mod hyphenation;
+mod markup;
 mod measurements;
 
+pub use clock::*;
+pub use hash::*;
+pub use hyphenation::*;
+pub use markup::*;
 pub use measurements::*;
@@ -0,0 +1,887 @@
+// Generated from the engine's JSON Schemas by `typescript_definitions`; do not edit.
+
+/** Act break handling for TV scripts */
+export interface ActBreakConfig {
+  /** Print act breaks as "END OF ..." (content "ACT ONE" prints "END OF ACT ONE") */
+  end_of_act_text?: boolean;
+  /** Start the next act on a fresh page */
+  new_page_after?: boolean;
+}
+
+/** Pages spanned by one act */
+export interface ActStats {
+  /** Act number, starting at 1 */
+  act: number;
+  end_page: PageIdentifier;
+  /** Act name taken from its closing act break (e.g. "ACT ONE"), empty if unnamed */
+  label: string;
+  /** Pages the act touches, counting shared first and last pages */
+  page_count: number;
+  start_page: PageIdentifier;
+}
+
+/** Why a page ended where it did, recorded with `RenderOptions::explain` */
+export interface BreakExplanation {
+  /** Element being placed when the page ended, if any */
+  element_id?: ElementId | null;
+  /** Lines left blank at the bottom of the page */
+  free_lines: number;
+  /** The page that ended */
+  page: PageIdentifier;
+  rule: BreakRule;
+}
+
+/** The pagination rule that decided a page break */
+export type BreakRule = "forced" | "act_break" | "locked" | "page_full" | "split" | "split_below_minimum" | "cannot_split" | "split_min_before_unmet" | "split_min_after_unmet" | "keep_with_next" | "scene_opening" | "speech_block" | "keep_with_previous" | "dual_dialogue";
+
+/** A page of a new pagination that the previous one doesn't have */
+export interface ChangedPage {
+  /** Position in the new page list */
+  index: number;
+  page: Page;
+}
+
+/** How far `paginate_to_target` may "cheat" the layout, either way from the base configuration */
+export interface CheatConstraints {
+  /** Most characters dialogue lines may gain or lose */
+  max_dialogue_width_change?: number;
+  /** Most lines per page that may be added or removed */
+  max_lines_per_page_change?: number;
+}
+
+/** The adjusted layout `paginate_to_target` settled on, with its pagination */
+export interface CheatResult {
+  config: PageConfig;
+  /** Characters added to dialogue lines (negative when removed) */
+  dialogue_width_change: number;
+  /** Lines per page added to the base configuration (negative when removed) */
+  lines_per_page_change: number;
+  result: PaginationResult;
+}
+
+/** An element whose trimming would pull a nearly empty page back onto the page before it, from `cheat_suggestions` */
+export interface CheatSuggestion {
+  element_id: ElementId;
+  /** Fewest lines the element must lose */
+  lines_to_save: number;
+  /** The short page that would disappear */
+  page: PageIdentifier;
+  /** Lines printed on the short page */
+  spill_lines: number;
+}
+
+/** A setting `PageConfig::validate` found pagination can't honor */
+export interface ConfigIssue {
+  /** Config field at fault ("element_styles.Dialogue.margin_left") */
+  field: string;
+  message: string;
+  severity: ConfigIssueSeverity;
+}
+
+/** How serious a `ConfigIssue` is */
+export type ConfigIssueSeverity = "error" | "warning";
+
+/** How continuation markers are formatted */
+export interface ContinuationStyle {
+  /** Markers for specific element types, replacing the ones above */
+  by_type?: Record<string, TypeContinuationStyle>;
+  /** Suffix added to character name on next page (e.g., "(CONT'D)") */
+  contd_marker: string;
+  /** Whether to use continuation markers at all */
+  enabled: boolean;
+  /** Marker at bottom of page (e.g., "(MORE)") */
+  more_marker: string;
+}
+
+/** Header printed on a page that continues an element split from the page before */
+export interface ContinuedHeader {
+  /** Lines reserved at the top of the page, including blank lines under the text */
+  lines?: number;
+  /** Header text */
+  text?: string;
+}
+
+/** Side-by-side layout of dual dialogue blocks */
+export interface DualDialogueConfig {
+  /** Width of each column in inches; the right column starts this far from the left margin */
+  column_width?: number;
+  /** Widest line in a column, capping the element's own `max_chars_per_line` */
+  max_chars_per_line?: number;
+}
+
+export type DualDialoguePosition = "left" | "right";
+
+/** Pages an edit would move, from `simulate_edit` */
+export interface EditImpact {
+  /** First page whose content would start somewhere else (None if no page moves) */
+  first_shifted_page?: PageIdentifier | null;
+  page_count_after: number;
+  page_count_before: number;
+  /** Every page, as numbered after the edit, whose content would start somewhere else or that the edit adds */
+  shifted_pages: PageIdentifier[];
+}
+
+/** A single screenplay element with its content and metadata */
+export interface Element {
+  /** Character name for dialogue/parenthetical (for CONT'D tracking) */
+  character_name?: string | null;
+  content: string;
+  /** Integrator-defined type ("caption", "sfx_cue") styled by `PageConfig::custom_styles`; pagination otherwise treats the element as its `element_type`, which also decides whether it prints */
+  custom_type?: string | null;
+  /** Whether this element is part of a dual dialogue block */
+  dual_dialogue_position?: DualDialoguePosition | null;
+  element_type: ElementType;
+  /** Whether this element forces a page break after it */
+  force_page_break_after?: boolean;
+  id: ElementId;
+  /** Character ranges of `content` (start, end exclusive) never broken across lines, like a name or "U.S.S. Enterprise" */
+  no_break_ranges?: [number, number][];
+  /** Locked scene number of a scene heading (e.g., "12A"); headings without one are numbered around the locked ones (see `number_scenes`) */
+  scene_number?: string | null;
+  /** One-off style adjustments merged over the element type's default */
+  style_override?: ElementStyleOverride | null;
+  /** Inline styling of `content`, carried onto wrapped lines and split parts */
+  style_runs?: StyleRun[];
+}
+
+/**
+ * How one element list became another, from `diff_elements`
+ *
+ * Elements are matched by ID. A surviving element is `modified` when its content hash differs and `moved` when it no longer keeps its order among the other survivors; it can be both.
+ */
+export interface ElementDiff {
+  /** In new order */
+  added: ElementId[];
+  /** In new order */
+  modified: ElementId[];
+  /** In new order */
+  moved: ElementId[];
+  /** In old order */
+  removed: ElementId[];
+}
+
+/** One change to a loaded document, for sending edits instead of the whole element list */
+export type ElementEdit = {
+  element: Element;
+  index: number;
+  op: "insert";
+} | {
+  element: Element;
+  op: "replace";
+} | {
+  id: ElementId;
+  op: "remove";
+};
+
+/**
+ * Area an element's lines take on one page, in points from the page's top-left corner
+ *
+ * Spans the element's text column between its style margins (narrowed for a dual dialogue column) and runs from the top of its first line to the bottom of its last.
+ */
+export interface ElementFrame {
+  height_pt: number;
+  width_pt: number;
+  x_pt: number;
+  y_pt: number;
+}
+
+/** Unique identifier for each element, used for position tracking */
+export type ElementId = string;
+
+/** Position of an element in the paginated document */
+export interface ElementPosition {
+  /** Ending line on last page (1-indexed) */
+  end_line: number;
+  /** Whether element was split across pages */
+  is_split: boolean;
+  /** Character offset in the element's content where each wrapped line starts (left empty by `PaginationBuilder`) */
+  line_offsets?: number[];
+  /** Page(s) this element appears on */
+  pages: PageIdentifier[];
+  /** Starting line on first page (1-indexed) */
+  start_line: number;
+}
+
+/** Style configuration for each element type */
+export interface ElementStyle {
+  /** Whether this element can be split across pages */
+  can_split: boolean;
+  /** Force uppercase for this element */
+  force_uppercase: boolean;
+  /** Must keep with following element */
+  keep_with_next: boolean;
+  /** Number of following lines required if keep_with_next is true */
+  keep_with_next_lines: number;
+  /** Must not open a page without the end of the preceding element */
+  keep_with_previous?: boolean;
+  /** Line spacing multiplier (1.0 = single, 2.0 = double) */
+  line_spacing: number;
+  /** Left margin from page's printable area in inches */
+  margin_left: number;
+  /** Right margin from page's printable area in inches */
+  margin_right: number;
+  /** Maximum characters per line */
+  max_chars_per_line: number;
+  /** Minimum lines that must appear on new page after split */
+  min_lines_after_split: number;
+  /** Minimum lines that must remain together when splitting */
+  min_lines_before_split: number;
+  /** Blank lines after this element type */
+  space_after: number;
+  /** Blank lines before this element type */
+  space_before: number;
+  /** Boundary a split moves back to, within `min_lines_before_split` */
+  split_preference?: SplitPreference;
+  /** Text direction for this element type (None for the page's `text_direction`) */
+  text_direction?: TextDirection | null;
+  /** Whether this element occupies space on the page (false = zero-line anchor) */
+  visible_in_pagination?: boolean;
+}
+
+/** Partial style applied over an element type's default for a single element */
+export interface ElementStyleOverride {
+  can_split?: boolean | null;
+  force_uppercase?: boolean | null;
+  keep_with_next?: boolean | null;
+  keep_with_next_lines?: number | null;
+  keep_with_previous?: boolean | null;
+  line_spacing?: number | null;
+  margin_left?: number | null;
+  margin_right?: number | null;
+  max_chars_per_line?: number | null;
+  min_lines_after_split?: number | null;
+  min_lines_before_split?: number | null;
+  space_after?: number | null;
+  space_before?: number | null;
+  split_preference?: SplitPreference | null;
+  text_direction?: TextDirection | null;
+}
+
+/** All possible screenplay element types */
+export type ElementType = "scene_heading" | "action" | "character" | "dialogue" | "parenthetical" | "transition" | "shot" | "dual_dialogue_left" | "dual_dialogue_right" | "act_break" | "page_break" | "blank_line" | "note" | "lyrics" | "centered" | "section" | "synopsis" | "av_video" | "av_audio" | "omitted_scene" | "omitted_page";
+
+/**
+ * Why a call into the JavaScript bindings failed
+ *
+ * Thrown to JS as an `Error` named `EngineError` with `code` and `detail` properties, so callers can branch on the code rather than the message.
+ */
+export interface EngineError {
+  code: EngineErrorCode;
+  /** Structured cause, such as the serialized `PaginationError` for `InvalidDocument` and `InvalidConfig` (None when there is only the message) */
+  detail?: unknown;
+  message: string;
+}
+
+/** Stable machine-readable `EngineError` codes */
+export type EngineErrorCode = "INVALID_ELEMENT_JSON" | "INVALID_CONFIG" | "INVALID_INPUT" | "INVALID_MESSAGE_PACK" | "INVALID_DOCUMENT" | "UNKNOWN_PRESET" | "SERIALIZATION_FAILED" | "CANCELLED" | "CALLBACK_FAILED";
+
+/**
+ * Character widths for a proportional font
+ *
+ * Replaces the fixed `char_width_pt` when wrapping: each line holds as much text as fits in the width of `max_chars_per_line` monospace characters.
+ */
+export interface FontMetrics {
+  /** Advance width of characters missing from `widths`, in points */
+  default_width_pt?: number;
+  /** Advance width of individual characters, in points */
+  widths?: Record<string, number>;
+}
+
+/**
+ * Running header with the draft name and date
+ *
+ * Page number placement is `PageConfig::page_numbering`.
+ */
+export interface HeaderFooterConfig {
+  draft_date?: string | null;
+  /** Draft name, e.g. "Blue Revision" */
+  draft_name?: string | null;
+  /** Page lines reserved for the header, including blank lines under it (0 prints it in the top margin and keeps every line for content) */
+  header_lines?: number;
+  /** Leave the first page without the header */
+  skip_first_page?: boolean;
+}
+
+/** Word hyphenation settings for line wrapping */
+export interface HyphenationConfig {
+  /** Words with their break points marked by hyphens ("tes-ta-ment"), used instead of the language rules for those words */
+  dictionary?: string[];
+  /** Break words at hyphenation points instead of wrapping whole words */
+  enabled: boolean;
+  /** Language whose hyphenation rules apply */
+  language: HyphenationLanguage;
+}
+
+/** Languages with built-in hyphenation rules */
+export type HyphenationLanguage = "en_us" | "en_gb" | "de" | "fr" | "es";
+
+/** Absolute position of one printed line, in points from the page's top-left corner */
+export interface LinePosition {
+  /** Height of the line box (`PageConfig::line_height_pt`) */
+  height_pt?: number;
+  /** Width of the printed text */
+  width_pt?: number;
+  /** Left edge of the text */
+  x_pt: number;
+  /** Top of the line box */
+  y_pt: number;
+}
+
+/** Range of lines within a split element */
+export interface LineRange {
+  /** Ending line within the element (exclusive) */
+  end: number;
+  /** Starting line within the element (0-indexed) */
+  start: number;
+}
+
+/** One page of a locked draft */
+export interface LockedPage {
+  /** Elements that started on this page (continuations from the page before are not listed) */
+  elements: ElementId[];
+  identifier: PageIdentifier;
+}
+
+/** Page breaks of a locked draft, kept when later drafts are paginated */
+export interface LockedPageSet {
+  pages: LockedPage[];
+}
+
+/** Margin configuration in inches */
+export interface MarginConfig {
+  bottom: number;
+  left: number;
+  right: number;
+  top: number;
+}
+
+/** An element whose first page changed between two paginations */
+export interface MovedElement {
+  element_id: ElementId;
+  from: PageIdentifier;
+  to: PageIdentifier;
+}
+
+/** Orphan/widow control settings */
+export interface OrphanControlConfig {
+  /** Character name must have at least N dialogue lines on same page */
+  character_min_dialogue_lines: number;
+  /** Minimum dialogue lines after a split */
+  dialogue_min_after_split: number;
+  /** Minimum dialogue lines before a split */
+  dialogue_min_before_split: number;
+  /** Move a dialogue split back to the last line ending a sentence, as long as `dialogue_min_before_split` lines stay on the page */
+  prefer_sentence_breaks?: boolean;
+  /** Scene heading must have at least N lines after it on same page */
+  scene_heading_min_following: number;
+}
+
+/** A single page in the paginated output */
+export interface Page {
+  /** Continuation marker at bottom (e.g., "(MORE)") */
+  bottom_continuation?: string | null;
+  /** Why the page ended (None for the last page) */
+  break_reason?: PageBreakReason | null;
+  /** Stable hash of everything printed on the page, as 16 hex digits; a page whose hash is unchanged between runs renders the same (left empty by `PaginationBuilder`) */
+  content_hash?: string;
+  /** Element placements on this page */
+  elements: PageElement[];
+  /** Header above the content, e.g. "CONTINUED:" (see `PageConfig::continued_header`) */
+  header?: string | null;
+  identifier: PageIdentifier;
+  /** Lines used on this page */
+  lines_used: number;
+  /** Printed page number, formatted per `PageConfig::page_numbering` (None for an unnumbered page) */
+  page_number?: string | null;
+  /** Paper color of the latest revision touching this page (None for the original draft) */
+  revision_color?: RevisionColor | null;
+  /** Draft name and date printed at the top (see `PageConfig::header_footer`) */
+  running_header?: string | null;
+}
+
+/** Judgment calls for pages that end early because content was pushed forward */
+export interface PageBalanceConfig {
+  /** Stretch or deliberately shorten such pages (off keeps the plain short page) */
+  enabled?: boolean;
+  /** Most blank lines added to any one gap between elements */
+  max_lines_per_gap?: number;
+  /** Most free lines that are stretched away; larger remainders leave a short page */
+  max_stretch_lines?: number;
+}
+
+/** A page break point in the document */
+export interface PageBreak {
+  /** Element ID after which this break occurs */
+  after_element: ElementId;
+  /** Reason for the break */
+  reason?: PageBreakReason;
+  /** If element was split, line number within element (0-indexed) that opens the next page */
+  split_at_line?: number | null;
+}
+
+/** Reason for a page break */
+export type PageBreakReason = "page_full" | "forced" | "act_break" | "orphan_prevention" | "dialogue_continuation" | "locked" | "stretched_spacing" | "short_page";
+
+/** Complete page configuration - ALL format variations expressed here */
+export interface PageConfig {
+  /** END OF ACT text and act page breaks */
+  act_breaks?: ActBreakConfig;
+  /** Character width in points (Courier 12pt = 7.2pt) */
+  char_width_pt: number;
+  /** Dialogue continuation configuration */
+  continuation_style: ContinuationStyle;
+  /** Header reserved on pages that continue a split element (None for no header) */
+  continued_header?: ContinuedHeader | null;
+  /** Styles for integrator-defined element types, by `Element::custom_type` name */
+  custom_styles?: Record<string, ElementStyle>;
+  /** Column width for dual dialogue blocks */
+  dual_dialogue?: DualDialogueConfig;
+  /** Count East Asian wide characters (CJK) as two monospace cells when wrapping; ignored with `font_metrics` */
+  east_asian_width?: boolean;
+  /** Styles for each element type */
+  element_styles: Record<string, ElementStyle>;
+  /** Proportional font widths (None for the monospace `char_width_pt`) */
+  font_metrics?: FontMetrics | null;
+  /** Breaks pinned by an external locking workflow, honored wherever the element they follow is placed */
+  forced_breaks?: PageBreak[];
+  /** Draft name and date header on every page */
+  header_footer?: HeaderFooterConfig;
+  /** Hyphenation settings (off by default) */
+  hyphenation?: HyphenationConfig;
+  /** Line height in points (Courier 12pt = 12pt) */
+  line_height_pt: number;
+  /** Lines per page (typically 55-58 for US Letter) */
+  lines_per_page: number;
+  /** Page margins */
+  margins: MarginConfig;
+  /** Orphan/widow control settings */
+  orphan_control: OrphanControlConfig;
+  /** Stretching or shortening pages that end with a few free lines */
+  page_balance?: PageBalanceConfig;
+  /** Page number start, format and placement */
+  page_numbering?: PageNumberingConfig;
+  paper_size: PaperSize;
+  /** Reading pace for runtime estimates */
+  runtime?: RuntimeConfig;
+  /** Notes production draft: render notes as boxed text that takes up space */
+  show_notes?: boolean;
+  /** Direction of the script's text (elements may override it in their style) */
+  text_direction?: TextDirection;
+  /** Block placement on the title page */
+  title_page?: TitlePageConfig;
+}
+
+/** How pages shifted between two paginations, from `diff_results` */
+export interface PageDiff {
+  /** Pages only the new pagination has */
+  added_pages: PageIdentifier[];
+  /** Elements in both that start on a different page, in new page order */
+  moved_elements: MovedElement[];
+  /** New page count less the old one */
+  page_count_change: number;
+  /** Pages only the old pagination has */
+  removed_pages: PageIdentifier[];
+  /** Pages in both whose content starts somewhere else, in new page order */
+  shifted_pages: PageIdentifier[];
+}
+
+/** An element's placement on a page */
+export interface PageElement {
+  /** Column of a dual dialogue block this element is printed in; both columns of a block start on the same line */
+  column?: DualDialoguePosition | null;
+  /** Continuation prefix for character (e.g., "JOHN (CONT'D)") */
+  continuation_prefix?: string | null;
+  element_id: ElementId;
+  /** Area the element's lines take on this page (only with `RenderOptions::include_geometry`) */
+  frame?: ElementFrame | null;
+  /** If this is a continued element (from previous page) */
+  is_continuation: boolean;
+  /** Number of lines this element occupies on this page */
+  line_count: number;
+  /** Position of each line on this page (only with `RenderOptions::include_geometry`) */
+  line_positions?: LinePosition[] | null;
+  /** If this is a partial element (split), which lines from the original */
+  line_range?: LineRange | null;
+  /** Revision asterisk for each line on this page (None when no line of the element is revised; see `mark_revisions` and `mark_changed_lines`) */
+  revision_marks?: boolean[] | null;
+  /** Scene number printed beside a scene heading (left empty by `PaginationBuilder`) */
+  scene_number?: string | null;
+  /** Starting line on this page (1-indexed) */
+  start_line: number;
+  /** Style runs on each content line of `text`, markers excluded (only with `RenderOptions::include_text`, and None when the element has no runs) */
+  style_runs?: StyleRun[][] | null;
+  /** Printed text on this page, including any CONT'D line before and MORE marker after the content (only with `RenderOptions::include_text`) */
+  text?: string[] | null;
+}
+
+/** Page identifier supporting A-pages for production scripts */
+export type PageIdentifier = {
+  type: "Sequential";
+  value: number;
+} | {
+  type: "Inserted";
+  value: {
+    base: number;
+    suffix: string;
+  };
+} | {
+  type: "Omitted";
+  value: number;
+};
+
+/** Where the page number prints on the page */
+export type PageNumberPosition = "top_right" | "top_center" | "bottom_center" | "bottom_right";
+
+/** Page number conventions (shooting vs submission drafts differ) */
+export interface PageNumberingConfig {
+  /** Label format; `{n}` is replaced by the page number (e.g. "{n}." gives "42.") */
+  format?: string;
+  position?: PageNumberPosition;
+  /** Number of the first page */
+  start_number?: number;
+  /** Leave the first page unnumbered */
+  suppress_first_page?: boolean;
+}
+
+/** Revisions one page has been issued in */
+export interface PageRevisionHistory {
+  page: PageIdentifier;
+  /** Oldest first; the last is the color the page is printed on */
+  revisions: RevisionLevel[];
+}
+
+/** An element, or the part of one, printed on the pages `slice_pages` took */
+export interface PageSliceElement {
+  element_id: ElementId;
+  /** Lines of the element inside the slice (None when all of it is) */
+  line_range?: LineRange | null;
+}
+
+/**
+ * A pagination expressed as changes to a previous one, for shipping only what an edit touched
+ *
+ * Pages are matched by `content_hash`; every page of the new pagination is either in `changed_pages` or in `reused_pages`. Everything other than pages is sent whole in `result`.
+ */
+export interface PaginationDelta {
+  /** New and changed pages, in page order */
+  changed_pages: ChangedPage[];
+  /** The new pagination with `pages` left empty */
+  result: PaginationResult;
+  /** Unchanged pages, in page order */
+  reused_pages: ReusedPage[];
+}
+
+/** Why `try_paginate` rejected its input */
+export type PaginationError = {
+  code: "empty_element_id";
+  index: number;
+} | {
+  code: "duplicate_element_id";
+  first_index: number;
+  id: ElementId;
+  index: number;
+} | {
+  code: "dialogue_without_character";
+  id: ElementId;
+  index: number;
+} | {
+  code: "invalid_config";
+  field: string;
+  reason: string;
+} | {
+  code: "line_count_overflow";
+  id: ElementId;
+  lines: number;
+};
+
+/** Complete result of pagination */
+export interface PaginationResult {
+  /** Why each page ended, in page order (empty unless requested with `RenderOptions::explain`) */
+  break_explanations?: BreakExplanation[];
+  /** Quick lookup: element ID -> position */
+  element_positions: Record<string, ElementPosition>;
+  /** All pages in order */
+  pages: Page[];
+  /** Every scene heading with its page and line, in script order */
+  scene_index?: SceneIndexEntry[];
+  /** Length of each scene in eighths of a page */
+  scene_lengths?: SceneLength[];
+  /** Statistics */
+  stats: PaginationStats;
+  /** Unnumbered page 0 ahead of the script (None without a title page) */
+  title_page?: Page | null;
+  /** Any warnings generated */
+  warnings: PaginationWarning[];
+}
+
+/** Statistics about the pagination run */
+export interface PaginationStats {
+  /** Page span of each act, for documents with act breaks */
+  acts?: ActStats[];
+  /** Number of page breaks */
+  break_count: number;
+  /** Number of dialogue continuations (MORE/CONT'D) */
+  continuation_count: number;
+  /** Total elements processed */
+  element_count: number;
+  /** `PaginationResult::fingerprint` of the output (empty from `PaginationBuilder`) */
+  fingerprint?: string;
+  /** Total page count */
+  page_count: number;
+  /** Pagination timing in microseconds */
+  timing_us: number;
+}
+
+/** Warning generated during pagination */
+export interface PaginationWarning {
+  element_id?: ElementId | null;
+  message: string;
+  warning_type: WarningType;
+}
+
+/** Paper size definitions */
+export type PaperSize = "us_letter" | "a4";
+
+export type PartialPageConfig = Record<string, unknown>;
+
+/** Optional rendering data attached to the paginated pages */
+export interface RenderOptions {
+  /** Record the rule behind every page break in `PaginationResult::break_explanations` */
+  explain?: boolean;
+  /** Attach absolute x/y positions (in points) for every line of every element */
+  include_geometry?: boolean;
+  /** Attach the exact wrapped text each element prints on each page */
+  include_text?: boolean;
+}
+
+/**
+ * Parts of a `PaginationResult` to keep, for slimming what gets serialized
+ *
+ * Everything is kept by default; a cheap preview that only needs page counts can drop the rest. Stats, including the fingerprint, always describe the full result.
+ */
+export interface ResultOptions {
+  /** Keep each page's element placements (pages themselves are always kept) */
+  include_page_elements?: boolean;
+  /** Keep `element_positions` */
+  include_positions?: boolean;
+  /** Keep `warnings` */
+  include_warnings?: boolean;
+}
+
+/** A page carried over unchanged from the previous pagination */
+export interface ReusedPage {
+  /** Position in the new page list */
+  index: number;
+  /** Position of the same page in the previous page list */
+  previous_index: number;
+}
+
+/** A page that has to be reissued in a revision */
+export interface RevisedPage {
+  /** Paper color the page is reissued on */
+  color: RevisionColor;
+  /** Whether the page did not exist in the old draft */
+  is_new: boolean;
+  page: PageIdentifier;
+}
+
+/** Page colors for successive script revisions, in the standard order */
+export type RevisionColor = "white" | "blue" | "pink" | "yellow" | "green" | "goldenrod" | "buff" | "salmon" | "cherry";
+
+/** A revision's place in the color sequence: the color, and how many times the sequence has come round to it (2 for double colors) */
+export interface RevisionLevel {
+  color: RevisionColor;
+  pass: number;
+  /** Revisions issued after the white draft, this one included */
+  revision: number;
+}
+
+/** Pages to reissue between two drafts */
+export interface RevisionReport {
+  color: RevisionColor;
+  /** Old pages with no counterpart in the new draft */
+  removed_pages: PageIdentifier[];
+  /** Changed or added pages, in page order */
+  revised_pages: RevisedPage[];
+  /** One-paragraph memo, e.g. "Blue revision: pages 3, 5-7, 12A (4 pages)." */
+  summary: string;
+}
+
+/**
+ * Revision state carried from one pagination run to the next
+ *
+ * Starts at the white draft. Each `record` issues the next color in the sequence, tags the new draft's pages with it and keeps every page's history, so hosts can persist the tracker alongside the script.
+ */
+export interface RevisionTracker {
+  /** Every page revised so far, in page order */
+  pages?: PageRevisionHistory[];
+  /** Revisions issued after the white draft */
+  revision?: number;
+}
+
+/**
+ * Reading pace for `estimate_runtime`
+ *
+ * Rates left unset follow the one-page-per-minute convention: a full page of `lines_per_page` lines takes sixty seconds.
+ */
+export interface RuntimeConfig {
+  /** Seconds per page line of action and everything else */
+  action_seconds_per_line?: number | null;
+  /** Seconds per page line of dialogue, parentheticals, cues and lyrics */
+  dialogue_seconds_per_line?: number | null;
+}
+
+/** Estimated screen time of a paginated script */
+export interface RuntimeEstimate {
+  /** Each scene, in script order */
+  scenes: SceneRuntime[];
+  /** Whole script, material before the first scene heading included */
+  total_seconds: number;
+}
+
+/** Outline entry for a scene heading, for navigation and bookmarks */
+export interface SceneIndexEntry {
+  /** Scene heading element */
+  element_id: ElementId;
+  /** Scene heading text */
+  heading: string;
+  /** Page the heading appears on */
+  page: PageIdentifier;
+  /** Scene number as printed (sequential, 1-based) */
+  scene_number: string;
+  /** Line of the heading on that page (1-indexed) */
+  start_line: number;
+}
+
+/** Length of a scene, measured in lines and eighths of a page */
+export interface SceneLength {
+  /** Length in eighths of a page (minimum 1/8 for any placed scene) */
+  eighths: number;
+  /** Scene heading text */
+  heading: string;
+  /** Scene heading element that opens the scene */
+  heading_element: ElementId;
+  /** Lines the scene occupies across all its pages */
+  lines: number;
+  /** Page the scene starts on (None if it was never placed) */
+  start_page?: PageIdentifier | null;
+}
+
+/** Screen time of one scene, from `estimate_runtime` */
+export interface SceneRuntime {
+  heading_element: ElementId;
+  seconds: number;
+  /** Scene heading text */
+  slug: string;
+}
+
+/** One scene of the outline, from `scene_report` */
+export interface SceneSummary {
+  /** Lines of action */
+  action_lines: number;
+  /** Lines of dialogue, parentheticals and lyrics */
+  dialogue_lines: number;
+  /** Share of dialogue among dialogue and action lines, from 0.0 to 1.0 */
+  dialogue_ratio: number;
+  /** Length in eighths of a page, as in `PaginationResult::scene_lengths` */
+  eighths: number;
+  /** Elements in the scene, heading included */
+  element_count: number;
+  heading_element: ElementId;
+  /** Number of pages the scene prints on */
+  page_span: number;
+  /** Printed scene number ("12A") */
+  scene_number: string;
+  /** Scene heading text ("INT. OFFICE - DAY") */
+  slug: string;
+  /** Page the heading prints on (None if it wasn't placed) */
+  start_page?: PageIdentifier | null;
+}
+
+/** Reduced page set for printing actor sides, from `extract_sides` */
+export interface Sides {
+  /** Pages left out after the last page of the sides */
+  omitted_after: PageIdentifier[];
+  pages: SidesPage[];
+}
+
+/** Scenes to pull into sides, by number or by who speaks in them */
+export interface SidesFilter {
+  /** Character names, matched against cues without extensions and ignoring case */
+  characters?: string[];
+  /** Printed scene numbers ("12", "12A") */
+  scene_numbers?: string[];
+}
+
+/** A page of sides: an original page holding selected material */
+export interface SidesPage {
+  /** Pages left out between the previous page of the sides and this one */
+  omitted_before: PageIdentifier[];
+  /** The page as paginated, identifier unchanged */
+  page: Page;
+  /** Line spans (first, last) of scenes that weren't selected, to strike through when printing */
+  struck_lines: [number, number][];
+}
+
+/**
+ * Where a split element prefers to break
+ *
+ * Each preference falls back to the next: paragraph break, then sentence end, then any line.
+ */
+export type SplitPreference = "any_line" | "sentence_end" | "paragraph_break";
+
+/**
+ * Bold, italic or underline styling over a range of characters
+ *
+ * `start` and `end` (exclusive) are character offsets into the element's content, or into a wrapped line for the runs `LineCalculator` maps onto lines.
+ */
+export interface StyleRun {
+  bold?: boolean;
+  end: number;
+  italic?: boolean;
+  start: number;
+  underline?: boolean;
+}
+
+/** Direction text runs in, and the side of the page element indents are measured from */
+export type TextDirection = "ltr" | "rtl";
+
+/** Title page content, laid out on an unnumbered page ahead of the script (see `layout_title_page`) */
+export interface TitlePage {
+  authors?: string[];
+  /** Contact block, one entry per line (name, address, phone, email) */
+  contact?: string[];
+  /** Line between the title and the authors (e.g., "Written by") */
+  credit?: string | null;
+  draft_date?: string | null;
+  /** Source material (e.g., "Based on the novel by ...") */
+  source?: string | null;
+  title?: string;
+}
+
+/** Placement of the title page blocks (see `TitlePage`) */
+export interface TitlePageConfig {
+  /** Blank lines between blocks (title, credit, authors, source; and blocks sharing a corner) */
+  block_spacing?: number;
+  contact_corner?: TitlePageCorner;
+  draft_date_corner?: TitlePageCorner;
+  /** Page line the centered title starts on */
+  title_line?: number;
+}
+
+/** Corner of the title page a block prints in */
+export type TitlePageCorner = "top_left" | "top_right" | "bottom_left" | "bottom_right";
+
+/** Continuation markers for one element type */
+export interface TypeContinuationStyle {
+  /** Whether each marker takes a page line; when false it prints in the margin and content keeps the line */
+  consumes_line?: boolean;
+  /** Marker at the top of the next page; speech appends it to the character name, other types print it on its own */
+  contd_marker?: string | null;
+  /** Column the markers print at, in characters from the printable left edge (None for the character cue's indent) */
+  indent?: number | null;
+  /** Marker at the bottom of the page before the break (None for no marker) */
+  more_marker?: string | null;
+}
+
+export type WarningType = "element_exceeds_page" | "unpreventable_orphan" | "configuration_warning" | "dual_dialogue_overflow" | "forced_break_moved";
@@ -0,0 +1,937 @@
+//! JavaScript bindings (enabled by the `wasm` feature)
+
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::layout::{self, try_paginate, try_paginate_with_options, LineCache, PaginationRun};
+use crate::types::*;
+use crate::utils::default_clock;
+
+// Declarations for every JSON contract, generated by `typescript_definitions`
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_DEFINITIONS: &'static str = include_str!("verso.d.ts");
+
+/// Initialize panic hook for better error messages in WASM
+#[wasm_bindgen(start)]
+pub fn init() {
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+}
+
+/// Main entry point for pagination from JavaScript
+///
+/// # Arguments
+///
+/// * `elements_json` - JSON string of Element array
+/// * `config_json` - JSON string of PageConfig
+///
+/// # Returns
+///
+/// JSON string of PaginationResult. Every binding fails with a JS `Error`
+/// named `EngineError` carrying a stable `code` (`INVALID_ELEMENT_JSON`,
+/// `INVALID_CONFIG`, ...) and, for input pagination rejects, the
+/// `PaginationError` as `detail`.
+#[wasm_bindgen]
+pub fn paginate_document(elements_json: &str, config_json: &str) -> Result<String, JsValue> {
+    // Deserialize inputs
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    // Run pagination
+    let result = try_paginate(&elements, &config).map_err(|e| js_error(e.into()))?;
+
+    // Serialize output
+    to_json(&result, "result")
+}
+
+/// Parse Fountain source and paginate it; returns JSON of PaginationResult
+#[cfg(feature = "fountain")]
+#[wasm_bindgen]
+pub fn paginate_fountain_document(source: &str, config_json: &str) -> Result<String, JsValue> {
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    let result = crate::fountain::paginate_fountain(source, &config);
+
+    to_json(&result, "result")
+}
+
+/// Pagination entry point with rendering options (line geometry, wrapped text)
+///
+/// `options_json` is a JSON string of RenderOptions; returns JSON of PaginationResult
+#[wasm_bindgen]
+pub fn paginate_document_with_options(
+    elements_json: &str,
+    config_json: &str,
+    options_json: &str,
+) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+    let options: RenderOptions = from_json(options_json, EngineErrorCode::InvalidInput, "options")?;
+
+    let result = try_paginate_with_options(&elements, &config, &options).map_err(|e| js_error(e.into()))?;
+
+    to_json(&result, "result")
+}
+
+/// `paginate_document` returning only the parts of the result asked for
+///
+/// `result_options_json` is a JSON ResultOptions; leaving out positions,
+/// page elements or warnings keeps preview payloads small
+#[wasm_bindgen]
+pub fn paginate_document_with_result_options(
+    elements_json: &str,
+    config_json: &str,
+    result_options_json: &str,
+) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+    let result_options: ResultOptions =
+        from_json(result_options_json, EngineErrorCode::InvalidInput, "result options")?;
+
+    let mut result = try_paginate(&elements, &config).map_err(|e| js_error(e.into()))?;
+    result.retain(&result_options);
+
+    to_json(&result, "result")
+}
+
+/// `paginate_document` returning only the pages that changed since an
+/// earlier run
+///
+/// `previous_result_json` is the PaginationResult the UI already holds;
+/// returns JSON of PaginationDelta, whose `reused_pages` map the unchanged
+/// pages onto it
+#[wasm_bindgen]
+pub fn paginate_delta(elements_json: &str, config_json: &str, previous_result_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+    let previous: PaginationResult = from_json(previous_result_json, EngineErrorCode::InvalidInput, "previous result")?;
+
+    let result = try_paginate(&elements, &config).map_err(|e| js_error(e.into()))?;
+    let delta = PaginationDelta::between(&previous, result);
+
+    to_json(&delta, "delta")
+}
+
+/// How pages shifted between two paginations
+///
+/// Takes the old and new PaginationResult as JSON; returns a JSON PageDiff
+#[wasm_bindgen]
+pub fn diff_results(old_result_json: &str, new_result_json: &str) -> Result<String, JsValue> {
+    let parse_result = |json: &str| -> Result<PaginationResult, JsValue> {
+        from_json(json, EngineErrorCode::InvalidInput, "result")
+    };
+
+    let diff = layout::diff_results(&parse_result(old_result_json)?, &parse_result(new_result_json)?);
+
+    to_json(&diff, "page diff")
+}
+
+/// How one element list became another: added, removed, modified and
+/// moved element IDs
+///
+/// Takes the old and new elements as JSON; returns a JSON ElementDiff
+#[wasm_bindgen]
+pub fn diff_elements(old_elements_json: &str, new_elements_json: &str) -> Result<String, JsValue> {
+    let parse_elements = |json: &str| -> Result<Vec<Element>, JsValue> {
+        from_json(json, EngineErrorCode::InvalidElementJson, "elements")
+    };
+
+    let diff = layout::diff_elements(&parse_elements(old_elements_json)?, &parse_elements(new_elements_json)?);
+
+    to_json(&diff, "element diff")
+}
+
+/// Paginate a revised draft keeping the page breaks of a locked one
+///
+/// `locks_json` is a JSON LockedPageSet; returns JSON of PaginationResult
+/// with new material on A-pages
+#[wasm_bindgen]
+pub fn paginate_locked_document(elements_json: &str, config_json: &str, locks_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+    let locks: LockedPageSet = from_json(locks_json, EngineErrorCode::InvalidInput, "locked pages")?;
+    check_input(&elements, &config)?;
+
+    let result = layout::paginate_locked(&elements, &config, &locks);
+
+    to_json(&result, "result")
+}
+
+/// Pagination entry point taking and returning JS objects directly
+///
+/// Avoids the JSON string round trip of `paginate_document`. `elements` is an
+/// array of Element objects and `config` a PageConfig object; returns a
+/// PaginationResult object with maps as plain objects. Invalid input is
+/// rejected like `paginate_document`.
+#[wasm_bindgen]
+pub fn paginate_elements(elements: JsValue, config: JsValue) -> Result<JsValue, JsValue> {
+    let elements: Vec<Element> = from_js(elements, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_js(config, EngineErrorCode::InvalidConfig, "config")?;
+
+    let result = try_paginate(&elements, &config).map_err(|e| js_error(e.into()))?;
+    to_js(&result, "result")
+}
+
+/// Long-lived engine holding a parsed config and document between runs
+///
+/// Wraps `Paginator`: the config and elements cross the boundary once, edits
+/// send only the changed element, and `paginate` re-wraps and re-places only
+/// what the edits since the last run invalidated. Config and elements are
+/// JS objects as in `paginate_elements`, and invalid ones are rejected with
+/// the same `EngineError`.
+#[wasm_bindgen]
+pub struct VersoEngine {
+    paginator: layout::Paginator,
+}
+
+#[wasm_bindgen]
+impl VersoEngine {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: JsValue) -> Result<VersoEngine, JsValue> {
+        let config: PageConfig = from_js(config, EngineErrorCode::InvalidConfig, "config")?;
+        layout::validate_config(&config).map_err(|e| js_error(e.into()))?;
+        Ok(Self { paginator: layout::Paginator::new(config) })
+    }
+
+    /// Replace the config; every element is wrapped again on the next run
+    pub fn set_config(&mut self, config: JsValue) -> Result<(), JsValue> {
+        let config: PageConfig = from_js(config, EngineErrorCode::InvalidConfig, "config")?;
+        layout::validate_config(&config).map_err(|e| js_error(e.into()))?;
+        self.paginator.set_config(config);
+        Ok(())
+    }
+
+    /// Replace the whole document
+    pub fn set_elements(&mut self, elements: JsValue) -> Result<(), JsValue> {
+        let elements: Vec<Element> = from_js(elements, EngineErrorCode::InvalidElementJson, "elements")?;
+        layout::validate_elements(&elements, self.paginator.config()).map_err(|e| js_error(e.into()))?;
+        self.paginator.set_elements(elements);
+        Ok(())
+    }
+
+    /// Insert an element before position `index` (clamped to the end)
+    pub fn insert_element(&mut self, index: usize, element: JsValue) -> Result<(), JsValue> {
+        let element: Element = from_js(element, EngineErrorCode::InvalidElementJson, "element")?;
+        check_edits(&self.paginator, &[ElementEdit::Insert { index, element: element.clone() }])?;
+        self.paginator.insert_element(index, element);
+        Ok(())
+    }
+
+    /// Replace the element with the same ID; returns false if there is none
+    pub fn update_element(&mut self, element: JsValue) -> Result<bool, JsValue> {
+        let element: Element = from_js(element, EngineErrorCode::InvalidElementJson, "element")?;
+        check_edits(&self.paginator, &[ElementEdit::Replace { element: element.clone() }])?;
+        Ok(self.paginator.update_element(element))
+    }
+
+    /// Remove the element with the given ID; returns false if there is none
+    pub fn remove_element(&mut self, id: &str) -> bool {
+        self.paginator.remove_element(id).is_some()
+    }
+
+    /// Apply an array of ElementEdit objects (`{"op": "insert" | "replace" |
+    /// "remove", ...}`) in order; returns the IDs of replaced or removed
+    /// elements that weren't found. A patch leaving an invalid document is
+    /// rejected whole.
+    pub fn apply_patch(&mut self, edits: JsValue) -> Result<JsValue, JsValue> {
+        let edits: Vec<ElementEdit> = from_js(edits, EngineErrorCode::InvalidElementJson, "edits")?;
+        check_edits(&self.paginator, &edits)?;
+        to_js(&self.paginator.apply_edits(edits), "missing element IDs")
+    }
+
+    /// Paginate the current document; returns a PaginationResult object
+    pub fn paginate(&mut self) -> Result<JsValue, JsValue> {
+        to_js(&self.paginator.repaginate(), "result")
+    }
+
+    /// `paginate` with a RenderOptions object (line geometry, wrapped text)
+    pub fn paginate_with_options(&mut self, options: JsValue) -> Result<JsValue, JsValue> {
+        let options: RenderOptions = from_js(options, EngineErrorCode::InvalidInput, "options")?;
+        to_js(&self.paginator.repaginate_with_options(&options), "result")
+    }
+}
+
+/// Reject edits that would leave the engine's document invalid, before any
+/// of them is applied
+fn check_edits(paginator: &layout::Paginator, edits: &[ElementEdit]) -> Result<(), JsValue> {
+    let mut elements = paginator.elements().to_vec();
+    for edit in edits {
+        match edit {
+            ElementEdit::Insert { index, element } => elements.insert((*index).min(elements.len()), element.clone()),
+            ElementEdit::Replace { element } => {
+                if let Some(slot) = elements.iter_mut().find(|e| e.id == element.id) {
+                    *slot = element.clone();
+                }
+            }
+            ElementEdit::Remove { id } => {
+                if let Some(index) = elements.iter().position(|e| e.id == *id) {
+                    elements.remove(index);
+                }
+            }
+        }
+    }
+    layout::validate_elements(&elements, paginator.config()).map_err(|e| js_error(e.into()))
+}
+
+/// Convert a failure into a JS `Error` named `EngineError`
+///
+/// `code` holds the stable `EngineErrorCode` and `detail` the structured
+/// cause, if any (a `PaginationError` for rejected input).
+fn js_error(error: EngineError) -> JsValue {
+    let js_error = js_sys::Error::new(&error.message);
+    js_error.set_name("EngineError");
+
+    let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("code"), &JsValue::from_str(error.code_str()));
+    if let Some(detail) = error.detail.as_ref().and_then(|detail| to_js(detail, "error detail").ok()) {
+        let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("detail"), &detail);
+    }
+
+    js_error.into()
+}
+
+fn fail(code: EngineErrorCode, message: String) -> JsValue {
+    js_error(EngineError::new(code, message))
+}
+
+/// Reject what `try_paginate` would, for entry points that drive their own run
+fn check_input(elements: &[Element], config: &PageConfig) -> Result<(), JsValue> {
+    layout::validate_config(config)
+        .and_then(|()| layout::validate_elements(elements, config))
+        .map_err(|e| js_error(e.into()))
+}
+
+/// JS object variant of `paginate_document_with_options`
+#[wasm_bindgen]
+pub fn paginate_elements_with_options(
+    elements: JsValue,
+    config: JsValue,
+    options: JsValue,
+) -> Result<JsValue, JsValue> {
+    let elements: Vec<Element> = from_js(elements, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_js(config, EngineErrorCode::InvalidConfig, "config")?;
+    let options: RenderOptions = from_js(options, EngineErrorCode::InvalidInput, "options")?;
+
+    let result = try_paginate_with_options(&elements, &config, &options).map_err(|e| js_error(e.into()))?;
+    to_js(&result, "result")
+}
+
+/// Cooperative pagination for very long documents
+///
+/// Processes `chunk_size` elements at a time, calling `on_progress` with the
+/// percent complete (0-100) and yielding to the JS event loop between chunks
+/// so the page stays responsive. Resolves to a PaginationResult object, or
+/// rejects with a `CANCELLED` EngineError once `on_progress` returns `false`.
+#[wasm_bindgen]
+pub async fn paginate_chunked(
+    elements: JsValue,
+    config: JsValue,
+    chunk_size: usize,
+    on_progress: js_sys::Function,
+) -> Result<JsValue, JsValue> {
+    let elements: Vec<Element> = from_js(elements, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_js(config, EngineErrorCode::InvalidConfig, "config")?;
+    check_input(&elements, &config)?;
+
+    let clock = default_clock();
+    let started_us = clock.now_us();
+    let mut cache = LineCache::new();
+    cache.begin_run();
+
+    let mut run = PaginationRun::new(&elements, &config);
+    loop {
+        let done = run.advance(&mut cache, chunk_size.max(1));
+        report_progress(&on_progress, run.progress())?;
+        if done {
+            break;
+        }
+        yield_to_event_loop().await?;
+    }
+
+    // Timing is wall-clock time, including the time spent yielded
+    let mut result = run.finish(&RenderOptions::default());
+    result.stats.timing_us = clock.now_us().saturating_sub(started_us);
+    to_js(&result, "result")
+}
+
+/// Blocking pagination that reports progress, for Web Workers
+///
+/// Calls `on_progress` with the percent complete (0-100) after every
+/// `every` elements without yielding, so a worker can post progress to the
+/// page. Returns a PaginationResult object, or throws a
+/// `CANCELLED` EngineError once `on_progress` returns `false`.
+#[wasm_bindgen]
+pub fn paginate_with_progress(
+    elements: JsValue,
+    config: JsValue,
+    every: usize,
+    on_progress: js_sys::Function,
+) -> Result<JsValue, JsValue> {
+    let elements: Vec<Element> = from_js(elements, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_js(config, EngineErrorCode::InvalidConfig, "config")?;
+    check_input(&elements, &config)?;
+
+    let clock = default_clock();
+    let started_us = clock.now_us();
+    let mut cache = LineCache::new();
+    cache.begin_run();
+
+    let mut run = PaginationRun::new(&elements, &config);
+    loop {
+        let done = run.advance(&mut cache, every.max(1));
+        report_progress(&on_progress, run.progress())?;
+        if done {
+            break;
+        }
+    }
+
+    let mut result = run.finish(&RenderOptions::default());
+    result.stats.timing_us = clock.now_us().saturating_sub(started_us);
+    to_js(&result, "result")
+}
+
+/// Call the progress callback, failing with `CANCELLED` if it returns `false`
+fn report_progress(on_progress: &js_sys::Function, fraction: f64) -> Result<(), JsValue> {
+    let returned = on_progress
+        .call1(&JsValue::NULL, &JsValue::from_f64((fraction * 100.0).round()))
+        .map_err(|_| fail(EngineErrorCode::CallbackFailed, "Progress callback threw".to_string()))?;
+
+    if returned == JsValue::FALSE {
+        return Err(fail(EngineErrorCode::Cancelled, "Pagination cancelled".to_string()));
+    }
+    Ok(())
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(callback: &js_sys::Function, delay_ms: i32);
+}
+
+/// Resolve on a fresh macrotask so pending events and rendering can run
+async fn yield_to_event_loop() -> Result<(), JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| set_timeout(&resolve, 0));
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map(|_| ())
+        .map_err(|_| fail(EngineErrorCode::CallbackFailed, "Failed to yield to the event loop".to_string()))
+}
+
+fn from_js<T: DeserializeOwned>(value: JsValue, code: EngineErrorCode, what: &str) -> Result<T, JsValue> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| fail(code, format!("Failed to parse {}: {}", what, e)))
+}
+
+fn from_json<T: DeserializeOwned>(json: &str, code: EngineErrorCode, what: &str) -> Result<T, JsValue> {
+    serde_json::from_str(json).map_err(|e| fail(code, format!("Failed to parse {}: {}", what, e)))
+}
+
+fn to_json<T: Serialize>(value: &T, what: &str) -> Result<String, JsValue> {
+    serde_json::to_string(value)
+        .map_err(|e| fail(EngineErrorCode::SerializationFailed, format!("Failed to serialize {}: {}", what, e)))
+}
+
+fn parse_color(color: &str) -> Result<RevisionColor, JsValue> {
+    serde_json::from_value(serde_json::Value::String(color.to_string()))
+        .map_err(|e| fail(EngineErrorCode::InvalidInput, format!("Failed to parse revision color: {}", e)))
+}
+
+fn to_js<T: Serialize>(value: &T, what: &str) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| fail(EngineErrorCode::SerializationFailed, format!("Failed to serialize {}: {}", what, e)))
+}
+
+/// Pagination entry point using MessagePack for input and output
+///
+/// `elements` is a MessagePack-encoded Element array and `config` a
+/// MessagePack-encoded PageConfig; returns the MessagePack-encoded
+/// PaginationResult (maps encoded with field names). Input that doesn't
+/// decode fails with `INVALID_MESSAGE_PACK`.
+#[wasm_bindgen]
+pub fn paginate_document_bin(elements: &[u8], config: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let elements: Vec<Element> = rmp_serde::from_slice(elements)
+        .map_err(|e| fail(EngineErrorCode::InvalidMessagePack, format!("Failed to decode elements: {}", e)))?;
+
+    let config: PageConfig = rmp_serde::from_slice(config)
+        .map_err(|e| fail(EngineErrorCode::InvalidMessagePack, format!("Failed to decode config: {}", e)))?;
+
+    let result = try_paginate(&elements, &config).map_err(|e| js_error(e.into()))?;
+
+    rmp_serde::to_vec_named(&result)
+        .map_err(|e| fail(EngineErrorCode::SerializationFailed, format!("Failed to encode result: {}", e)))
+}
+
+/// Get the default Feature Film configuration as JSON
+#[wasm_bindgen]
+pub fn get_feature_film_config() -> Result<String, JsValue> {
+    let config = PageConfig::feature_film();
+    to_json(&config, "config")
+}
+
+/// Get a named format preset's configuration as JSON (see `PageConfig::PRESETS`)
+#[wasm_bindgen]
+pub fn get_preset_config(name: &str) -> Result<String, JsValue> {
+    let config = PageConfig::preset(name).ok_or_else(|| fail(EngineErrorCode::UnknownPreset, format!("Unknown preset: {}", name)))?;
+    to_json(&config, "config")
+}
+
+/// Settings in a config pagination can't honor
+///
+/// Takes a PageConfig as JSON; returns a JSON ConfigIssue array, errors
+/// first, empty when the config is sound
+#[wasm_bindgen]
+pub fn validate_config(config_json: &str) -> Result<String, JsValue> {
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    to_json(&config.validate(), "config issues")
+}
+
+/// Paginate with a named preset adjusted by partial overrides
+///
+/// `overrides_json` is a JSON object shaped like PageConfig with only the
+/// fields to change (see `PartialPageConfig`); returns JSON of PaginationResult
+#[wasm_bindgen]
+pub fn paginate_with_overrides(elements_json: &str, preset_name: &str, overrides_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let overrides: PartialPageConfig = from_json(overrides_json, EngineErrorCode::InvalidConfig, "overrides")?;
+
+    let config = PageConfig::preset(preset_name)
+        .ok_or_else(|| fail(EngineErrorCode::UnknownPreset, format!("Unknown preset: {}", preset_name)))?
+        .with_overrides(&overrides)
+        .map_err(|e| js_error(e.into()))?;
+
+    let result = try_paginate(&elements, &config).map_err(|e| js_error(e.into()))?;
+
+    to_json(&result, "result")
+}
+
+/// Calculate lines for a single element (useful for preview)
+#[wasm_bindgen]
+pub fn calculate_element_lines(element_json: &str, config_json: &str) -> Result<u32, JsValue> {
+    let element: Element = from_json(element_json, EngineErrorCode::InvalidElementJson, "element")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    let calculator = layout::LineCalculator::new(&config);
+    let lines = calculator.calculate(&element);
+
+    Ok(lines.total_lines)
+}
+
+/// Cheat lines per page and dialogue width toward a target page count
+///
+/// `constraints_json` is a JSON CheatConstraints bounding the adjustments;
+/// returns JSON of CheatResult with the chosen config and its pagination
+#[wasm_bindgen]
+pub fn paginate_to_target(
+    elements_json: &str,
+    config_json: &str,
+    target_pages: u32,
+    constraints_json: &str,
+) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+    let constraints: CheatConstraints = from_json(constraints_json, EngineErrorCode::InvalidInput, "constraints")?;
+
+    let cheat = layout::paginate_to_target(&elements, &config, target_pages, &constraints)
+        .map_err(|e| js_error(e.into()))?;
+
+    to_json(&cheat, "result")
+}
+
+/// Elements to trim so nearly empty spill pages pull back
+///
+/// Returns a JSON array of CheatSuggestion
+#[wasm_bindgen]
+pub fn cheat_suggestions(elements_json: &str, config_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    let suggestions = layout::cheat_suggestions(&elements, &config);
+
+    to_json(&suggestions, "suggestions")
+}
+
+/// Which pages would move if an element printed `new_line_count` lines
+///
+/// Returns a JSON EditImpact; fails with INVALID_INPUT for an unknown element
+#[wasm_bindgen]
+pub fn simulate_edit(elements_json: &str, config_json: &str, element_id: &str, new_line_count: u32) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    let impact = layout::simulate_edit(&elements, &config, element_id, new_line_count)
+        .ok_or_else(|| fail(EngineErrorCode::InvalidInput, format!("Unknown element '{}'", element_id)))?;
+
+    to_json(&impact, "edit impact")
+}
+
+/// Calculate each scene's length in eighths of a page
+///
+/// Returns a JSON string of the SceneLength array
+#[wasm_bindgen]
+pub fn calculate_eighths(elements_json: &str, config_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    let result = try_paginate(&elements, &config).map_err(|e| js_error(e.into()))?;
+
+    to_json(&result.scene_lengths, "scene lengths")
+}
+
+/// Fast approximate page count for live status displays
+///
+/// Skips break rules, so it may differ from `paginate_document` by a page
+#[wasm_bindgen]
+pub fn estimate_page_count(elements_json: &str, config_json: &str) -> Result<u32, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    Ok(layout::estimate_page_count(&elements, &config))
+}
+
+/// JSON Schemas for every type the bindings take or return (see `schemas`)
+///
+/// Returns a JSON object keyed by type name
+#[cfg(feature = "schema")]
+#[wasm_bindgen]
+pub fn get_schemas() -> Result<String, JsValue> {
+    to_json(&crate::schemas_json(), "schemas")
+}
+
+/// Outline of a paginated script's scenes
+///
+/// Takes the elements and their PaginationResult as JSON; returns a JSON
+/// array of SceneSummary
+#[wasm_bindgen]
+pub fn scene_report(elements_json: &str, result_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+
+    let report = crate::reports::scene_report(&elements, &result);
+
+    to_json(&report, "scene report")
+}
+
+/// Estimated screen time, in total and per scene
+///
+/// Takes the elements, the config (whose `runtime` sets the pace) and their
+/// PaginationResult as JSON; returns a JSON RuntimeEstimate
+#[wasm_bindgen]
+pub fn estimate_runtime(elements_json: &str, config_json: &str, result_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+    let result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+
+    let estimate = crate::reports::estimate_runtime(&elements, &config, &result);
+
+    to_json(&estimate, "runtime")
+}
+
+/// Pages of actor sides: the scenes selected by number or character
+///
+/// Takes a PaginationResult, its elements and a SidesFilter as JSON; returns
+/// a JSON Sides
+#[wasm_bindgen]
+pub fn extract_sides(result_json: &str, elements_json: &str, filter_json: &str) -> Result<String, JsValue> {
+    let result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let filter: crate::reports::SidesFilter = from_json(filter_json, EngineErrorCode::InvalidInput, "sides filter")?;
+
+    let sides = crate::reports::extract_sides(&result, &elements, &filter);
+
+    to_json(&sides, "sides")
+}
+
+/// Pages to reissue between two paginated drafts, with a memo summary
+///
+/// Takes each draft's elements and PaginationResult as JSON and a revision
+/// color ("blue", "pink", ...); returns a JSON RevisionReport
+#[wasm_bindgen]
+pub fn compare_drafts(
+    old_elements_json: &str,
+    old_result_json: &str,
+    new_elements_json: &str,
+    new_result_json: &str,
+    color: &str,
+) -> Result<String, JsValue> {
+    let parse_elements = |json: &str| -> Result<Vec<Element>, JsValue> {
+        from_json(json, EngineErrorCode::InvalidElementJson, "elements")
+    };
+    let parse_result = |json: &str| -> Result<PaginationResult, JsValue> {
+        from_json(json, EngineErrorCode::InvalidInput, "result")
+    };
+    let color = parse_color(color)?;
+
+    let report = layout::compare_drafts(
+        &parse_elements(old_elements_json)?,
+        &parse_result(old_result_json)?,
+        &parse_elements(new_elements_json)?,
+        &parse_result(new_result_json)?,
+        color,
+    );
+
+    to_json(&report, "report")
+}
+
+/// Issue the next revision color between two paginated drafts
+///
+/// Takes a RevisionTracker (`{}` before the first revision) and each draft's
+/// elements and PaginationResult as JSON; returns JSON `{"tracker": ...,
+/// "result": ..., "report": ...}` with the advanced tracker, the new result
+/// with its pages colored and the RevisionReport
+#[wasm_bindgen]
+pub fn record_revision(
+    tracker_json: &str,
+    old_elements_json: &str,
+    old_result_json: &str,
+    new_elements_json: &str,
+    new_result_json: &str,
+) -> Result<String, JsValue> {
+    let parse_elements = |json: &str| -> Result<Vec<Element>, JsValue> {
+        from_json(json, EngineErrorCode::InvalidElementJson, "elements")
+    };
+    let parse_result = |json: &str| -> Result<PaginationResult, JsValue> {
+        from_json(json, EngineErrorCode::InvalidInput, "result")
+    };
+    let mut tracker: RevisionTracker = from_json(tracker_json, EngineErrorCode::InvalidInput, "revision tracker")?;
+
+    let mut result = parse_result(new_result_json)?;
+    let report = tracker.record(
+        &parse_elements(old_elements_json)?,
+        &parse_result(old_result_json)?,
+        &parse_elements(new_elements_json)?,
+        &mut result,
+    );
+
+    to_json(&serde_json::json!({ "tracker": tracker, "result": result, "report": report }), "revision")
+}
+
+/// Flag the lines of changed elements and color their pages for a revision
+///
+/// Takes a PaginationResult as JSON, a JSON array of changed element IDs and
+/// a revision color ("blue", "pink", ...); returns the marked result as JSON
+#[wasm_bindgen]
+pub fn mark_revisions(result_json: &str, changed_json: &str, color: &str) -> Result<String, JsValue> {
+    let mut result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+    let changed: Vec<ElementId> = from_json(changed_json, EngineErrorCode::InvalidInput, "changed elements")?;
+    let color = parse_color(color)?;
+
+    layout::mark_revisions(&mut result, &changed, color);
+
+    to_json(&result, "result")
+}
+
+/// Flag only the wrapped lines that changed since the previous draft
+///
+/// Takes a PaginationResult, the previous and current elements and the
+/// config as JSON, and a revision color ("blue", "pink", ...); returns the
+/// marked result as JSON
+#[wasm_bindgen]
+pub fn mark_changed_lines(
+    result_json: &str,
+    old_elements_json: &str,
+    new_elements_json: &str,
+    config_json: &str,
+    color: &str,
+) -> Result<String, JsValue> {
+    let parse_elements = |json: &str| -> Result<Vec<Element>, JsValue> {
+        from_json(json, EngineErrorCode::InvalidElementJson, "elements")
+    };
+    let mut result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+    let color = parse_color(color)?;
+
+    layout::mark_changed_lines(
+        &mut result,
+        &parse_elements(old_elements_json)?,
+        &parse_elements(new_elements_json)?,
+        &config,
+        color,
+    );
+
+    to_json(&result, "result")
+}
+
+/// Final Draft XML for elements paginated with a config
+///
+/// Takes the elements, PaginationResult and config as JSON; returns the FDX
+/// document with the result's page breaks
+#[wasm_bindgen]
+pub fn export_fdx_document(elements_json: &str, result_json: &str, config_json: &str) -> Result<String, JsValue> {
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    Ok(crate::export::export_fdx(&elements, &result, &config))
+}
+
+/// Paginated HTML for a PaginationResult, one positioned `<div class="page">` per page
+#[wasm_bindgen]
+pub fn render_html_document(result_json: &str, elements_json: &str, config_json: &str) -> Result<String, JsValue> {
+    let result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    Ok(crate::export::render_html(&result, &elements, &config))
+}
+
+/// The element and character offset under a click on a paginated page
+///
+/// Takes the PaginationResult, elements and config as JSON, a 0-based index
+/// into the result's pages and a point in points from the page's top-left
+/// corner; returns JSON `{"element_id": ..., "char_offset": ...}`, or `null`
+/// when the point isn't on a line of text
+#[wasm_bindgen]
+pub fn hit_test(
+    result_json: &str,
+    elements_json: &str,
+    config_json: &str,
+    page_index: usize,
+    x_pt: f64,
+    y_pt: f64,
+) -> Result<String, JsValue> {
+    let result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+    let elements: Vec<Element> = from_json(elements_json, EngineErrorCode::InvalidElementJson, "elements")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    let hit = layout::hit_test(&result, &elements, &config, page_index, x_pt, y_pt).map(|(element_id, char_offset)| {
+        serde_json::json!({ "element_id": element_id, "char_offset": char_offset })
+    });
+
+    to_json(&hit, "hit")
+}
+
+/// Where the caret for a character offset in an element goes
+///
+/// Takes the PaginationResult as JSON; returns JSON `{"page": ..., "line":
+/// ..., "column": ...}` with the PageIdentifier, 1-indexed line and column in
+/// characters, or `null` for an element that wasn't placed
+#[wasm_bindgen]
+pub fn locate(result_json: &str, element_id: &str, char_offset: u32) -> Result<String, JsValue> {
+    let result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+
+    let caret = result
+        .locate(element_id, char_offset)
+        .map(|(page, line, column)| serde_json::json!({ "page": page, "line": line, "column": column }));
+
+    to_json(&caret, "caret")
+}
+
+/// The element fragments printed on pages `first_page` through `last_page`
+///
+/// Takes the PaginationResult as JSON; returns a JSON array of
+/// PageSliceElement
+#[wasm_bindgen]
+pub fn slice_pages(result_json: &str, first_page: u32, last_page: u32) -> Result<String, JsValue> {
+    let result: PaginationResult = from_json(result_json, EngineErrorCode::InvalidInput, "result")?;
+
+    to_json(&result.slice_pages(first_page..=last_page), "page slice")
+}
+
+/// Lay out a title page as an unnumbered page 0
+///
+/// Takes a TitlePage and a PageConfig as JSON; returns the Page as JSON
+#[wasm_bindgen]
+pub fn layout_title_page(title_page_json: &str, config_json: &str) -> Result<String, JsValue> {
+    let title_page: TitlePage = from_json(title_page_json, EngineErrorCode::InvalidInput, "title page")?;
+    let config: PageConfig = from_json(config_json, EngineErrorCode::InvalidConfig, "config")?;
+
+    let page = layout::layout_title_page(&title_page, &config);
+
+    to_json(&page, "page")
+}
+
+/// Version of the pagination engine
+#[wasm_bindgen]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate_document() {
+        let elements_json = r#"[
+            {"id": "1", "element_type": "scene_heading", "content": "INT. OFFICE - DAY"},
+            {"id": "2", "element_type": "action", "content": "A busy office."}
+        ]"#;
+
+        let config_json = serde_json::to_string(&PageConfig::feature_film()).unwrap();
+
+        let result = paginate_document(elements_json, &config_json).unwrap();
+        let parsed: PaginationResult = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(parsed.stats.page_count, 1);
+        assert_eq!(parsed.stats.element_count, 2);
+    }
+
+    #[test]
+    fn test_paginate_document_with_geometry() {
+        let elements_json = r#"[{"id": "1", "element_type": "action", "content": "A busy office."}]"#;
+        let config_json = serde_json::to_string(&PageConfig::feature_film()).unwrap();
+
+        let result =
+            paginate_document_with_options(elements_json, &config_json, r#"{"include_geometry": true}"#)
+                .unwrap();
+        let parsed: PaginationResult = serde_json::from_str(&result).unwrap();
+
+        assert!(parsed.pages[0].elements[0].line_positions.is_some());
+    }
+
+    #[test]
+    fn test_paginate_document_bin() {
+        let elements = vec![
+            Element::new("1", ElementType::SceneHeading, "INT. OFFICE - DAY"),
+            Element::new("2", ElementType::Action, "A busy office."),
+        ];
+        let elements_bin = rmp_serde::to_vec_named(&elements).unwrap();
+        let config_bin = rmp_serde::to_vec_named(&PageConfig::feature_film()).unwrap();
+
+        let result_bin = paginate_document_bin(&elements_bin, &config_bin).unwrap();
+        let parsed: PaginationResult = rmp_serde::from_slice(&result_bin).unwrap();
+
+        assert_eq!(parsed.stats.page_count, 1);
+        assert_eq!(parsed.pages[0].identifier, PageIdentifier::Sequential(1));
+    }
+
+    #[test]
+    fn test_get_feature_film_config() {
+        let config_json = get_feature_film_config().unwrap();
+        let config: PageConfig = serde_json::from_str(&config_json).unwrap();
+
+        assert_eq!(config.lines_per_page, 55);
+    }
+
+    #[test]
+    fn test_get_preset_config() {
+        let config_json = get_preset_config("bbc_screenplay").unwrap();
+        let config: PageConfig = serde_json::from_str(&config_json).unwrap();
+
+        assert_eq!(config.lines_per_page, 58);
+    }
+
+    #[test]
+    fn test_calculate_eighths() {
+        let elements_json = r#"[
+            {"id": "1", "element_type": "scene_heading", "content": "INT. OFFICE - DAY"},
+            {"id": "2", "element_type": "action", "content": "A busy office."}
+        ]"#;
+        let config_json = serde_json::to_string(&PageConfig::feature_film()).unwrap();
+
+        let lengths_json = calculate_eighths(elements_json, &config_json).unwrap();
+        let lengths: Vec<SceneLength> = serde_json::from_str(&lengths_json).unwrap();
+
+        assert_eq!(lengths.len(), 1);
+        assert_eq!(lengths[0].eighths, 1);
+    }
+
+    #[test]
+    fn test_calculate_element_lines() {
+        let element_json = r#"{"id": "1", "element_type": "action", "content": "A short action."}"#;
+        let config_json = serde_json::to_string(&PageConfig::feature_film()).unwrap();
+
+        let lines = calculate_element_lines(element_json, &config_json).unwrap();
+        assert_eq!(lines, 1);
+    }
+}